@@ -3,11 +3,14 @@ use std::time::{Duration, Instant};
 
 use globset::{Glob, GlobMatcher};
 use rand::RngExt;
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use unicode_width::UnicodeWidthStr;
 
+use crate::audio::{COMMIT_INTRO_PATH, COMMIT_OUTRO_PATH};
+use crate::cli::FileOrder;
+use crate::config::EditPauses;
 use crate::git::{CommitMetadata, DiffHunk, FileChange, FileStatus, LineChangeType};
-use crate::syntax::Highlighter;
+use crate::syntax::{HighlightSpan, Highlighter, LineHighlights};
 
 /// A rule that specifies typing speed for files matching a glob pattern
 #[derive(Debug, Clone)]
@@ -37,17 +40,13 @@ impl SpeedRule {
     }
 }
 
-// Duration multipliers relative to typing speed
-const CURSOR_MOVE_PAUSE: f64 = 0.5; // Cursor movement between lines (base speed)
+// Duration multipliers relative to typing speed (pauses around edits are in EditPauses)
 const CURSOR_MOVE_SHORT_MULTIPLIER: f64 = 1.0; // Speed for short distances (1-50 lines)
 const CURSOR_MOVE_MEDIUM_MULTIPLIER: f64 = 0.3; // Speed for medium distances (51-200 lines)
 const CURSOR_MOVE_LONG_MULTIPLIER: f64 = 0.05; // Speed for long distances (201+ lines)
 const MAX_SCROLL_STEPS: usize = 60; // Maximum animation steps for any scroll distance
 const MIN_LOG_STEPS: usize = 50; // Minimum steps for logarithmic scaling (aligned with SHORT threshold)
 const LOG_SCALE_FACTOR: f64 = 8.0; // Scaling factor for logarithmic step calculation
-const DELETE_LINE_PAUSE: f64 = 10.0; // After deleting a line
-const INSERT_LINE_PAUSE: f64 = 6.7; // After inserting a line
-const HUNK_PAUSE: f64 = 50.0; // Between hunks
 const CHECKOUT_PAUSE: f64 = 16.7; // After git checkout command
 const CHECKOUT_OUTPUT_PAUSE: f64 = 33.3; // After git checkout output
 const OPEN_FILE_FIRST_PAUSE: f64 = 33.3; // Before opening first file
@@ -62,6 +61,10 @@ const GIT_PUSH_PAUSE: f64 = 16.7; // After git push command
 const PUSH_OUTPUT_PAUSE: f64 = 10.0; // Between push output lines
 const PUSH_FINAL_PAUSE: f64 = 66.7; // After final push output
 
+// Live speed changes (< and >) stay within 1/8x to 8x of the configured speed
+const MIN_SPEED_SCALE: f64 = 0.125;
+const MAX_SPEED_SCALE: f64 = 8.0;
+
 const MAX_LINE_CHECKPOINTS: usize = 200;
 const MAX_CHANGE_CHECKPOINTS: usize = 64;
 
@@ -72,10 +75,9 @@ pub struct EditorBuffer {
     pub cursor_line: usize,
     pub cursor_col: usize,
     pub scroll_offset: usize,
-    pub cached_highlights: Vec<crate::syntax::HighlightSpan>,
-    /// Pre-calculated highlights for old and new content
-    pub old_highlights: Vec<crate::syntax::HighlightSpan>,
-    pub new_highlights: Vec<crate::syntax::HighlightSpan>,
+    /// Pre-calculated highlights for old and new content, by line
+    pub old_highlights: LineHighlights,
+    pub new_highlights: LineHighlights,
     /// Store old and new content for byte offset calculation
     pub old_content_lines: Vec<String>,
     pub new_content_lines: Vec<String>,
@@ -84,6 +86,10 @@ pub struct EditorBuffer {
     pub new_content_line_offsets: Vec<usize>,
     /// Track which lines are additions (for green highlighting)
     pub added_lines: std::collections::HashSet<usize>,
+    /// Changed character ranges within added lines (word-level diff)
+    pub inline_changes: std::collections::HashMap<usize, Vec<(usize, usize)>>,
+    /// Line shown struck through just before it is deleted
+    pub deleting_line: Option<usize>,
 }
 
 impl EditorBuffer {
@@ -94,14 +100,15 @@ impl EditorBuffer {
             cursor_line: 0,
             cursor_col: 0,
             scroll_offset: 0,
-            cached_highlights: Vec::new(),
-            old_highlights: Vec::new(),
-            new_highlights: Vec::new(),
+            old_highlights: LineHighlights::default(),
+            new_highlights: LineHighlights::default(),
             old_content_lines: Vec::new(),
             new_content_lines: Vec::new(),
             old_content_line_offsets: Vec::new(),
             new_content_line_offsets: Vec::new(),
             added_lines: std::collections::HashSet::new(),
+            inline_changes: std::collections::HashMap::new(),
+            deleting_line: None,
         }
     }
 
@@ -118,14 +125,15 @@ impl EditorBuffer {
             cursor_line: 0,
             cursor_col: 0,
             scroll_offset: 0,
-            cached_highlights: Vec::new(),
-            old_highlights: Vec::new(),
-            new_highlights: Vec::new(),
+            old_highlights: LineHighlights::default(),
+            new_highlights: LineHighlights::default(),
             old_content_lines: Vec::new(),
             new_content_lines: Vec::new(),
             old_content_line_offsets: Vec::new(),
             new_content_line_offsets: Vec::new(),
             added_lines: std::collections::HashSet::new(),
+            inline_changes: std::collections::HashMap::new(),
+            deleting_line: None,
         }
     }
 
@@ -165,6 +173,126 @@ impl EditorBuffer {
     }
 }
 
+/// Highlighted token positions in a file's new content, for token typing
+struct TokenMap {
+    content: String,
+    line_starts: Vec<usize>,
+    spans: Vec<HighlightSpan>,
+}
+
+impl TokenMap {
+    fn new(path: &str, content: &str) -> Self {
+        let mut highlighter = Highlighter::new();
+        let spans = if highlighter.set_language_from_path(path) {
+            highlighter.highlight(content)
+        } else {
+            Vec::new()
+        };
+        let mut line_starts = vec![0];
+        line_starts.extend(content.match_indices('\n').map(|(i, _)| i + 1));
+        Self {
+            content: content.to_string(),
+            line_starts,
+            spans,
+        }
+    }
+
+    /// Character ranges of the highlighted tokens on 1-based line `line_no`,
+    /// or none if `line` doesn't match the content there.
+    fn line_spans(&self, line_no: usize, line: &str) -> Vec<(usize, usize)> {
+        let Some(&start) = line_no.checked_sub(1).and_then(|i| self.line_starts.get(i)) else {
+            return Vec::new();
+        };
+        let end = start + line.len();
+        if self.content.get(start..end) != Some(line) {
+            return Vec::new();
+        }
+
+        let char_col = |byte: usize| line.get(..byte).map(|s| s.chars().count());
+        self.spans
+            .iter()
+            .filter(|s| s.start < end && s.end > start)
+            .filter_map(|s| {
+                let from = char_col(s.start.max(start) - start)?;
+                let to = char_col(s.end.min(end) - start)?;
+                Some((from, to))
+            })
+            .collect()
+    }
+}
+
+#[derive(PartialEq)]
+enum CharClass {
+    Word,
+    Space,
+    Symbol,
+}
+
+fn char_class(ch: char) -> CharClass {
+    if ch.is_alphanumeric() || ch == '_' {
+        CharClass::Word
+    } else if ch.is_whitespace() {
+        CharClass::Space
+    } else {
+        CharClass::Symbol
+    }
+}
+
+/// Split a line from character `start` on into typing tokens. Highlighted spans
+/// stay whole; the rest splits into words, whitespace runs and single symbols.
+fn split_tokens(line: &str, start: usize, spans: &[(usize, usize)]) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = start;
+
+    while pos < chars.len() {
+        let span_end = spans
+            .iter()
+            .filter(|(from, to)| *from == pos && *to > pos)
+            .map(|(_, to)| *to)
+            .max();
+        let end = match span_end {
+            Some(end) => end.min(chars.len()),
+            None => {
+                let next_span = spans
+                    .iter()
+                    .map(|(from, _)| *from)
+                    .filter(|from| *from > pos)
+                    .min()
+                    .unwrap_or(chars.len())
+                    .min(chars.len());
+                let class = char_class(chars[pos]);
+                let mut end = pos + 1;
+                if class != CharClass::Symbol {
+                    while end < next_span && char_class(chars[end]) == class {
+                        end += 1;
+                    }
+                }
+                end
+            }
+        };
+        tokens.push(chars[pos..end].iter().collect());
+        pos = end;
+    }
+
+    tokens
+}
+
+/// What a pause follows, so playback can tell pauses apart without comparing lengths
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseKind {
+    /// After a typed line or a binary file's summary line
+    LineEnd,
+    /// Between hunks
+    Hunk,
+    /// Around a deleted line
+    Deletion,
+    /// Cursor movement between lines
+    CursorMove,
+    /// Terminal commands, dialogs and file switches
+    Other,
+}
+
 /// Individual animation step
 #[derive(Debug, Clone)]
 pub enum AnimationStep {
@@ -173,9 +301,19 @@ pub enum AnimationStep {
         col: usize,
         ch: char,
     },
+    /// A whole syntax token typed in one step (token typing mode)
+    InsertText {
+        line: usize,
+        col: usize,
+        text: String,
+    },
     InsertLine {
         line: usize,
         content: String,
+        inline_changes: Vec<(usize, usize)>,
+    },
+    MarkDeletion {
+        line: usize,
     },
     DeleteLine {
         line: usize,
@@ -186,7 +324,10 @@ pub enum AnimationStep {
     },
     Pause {
         multiplier: f64,
+        kind: PauseKind,
     },
+    /// Pause before opening the next file (fixed length when a file gap is set)
+    FileGap,
     StartAudio {
         chunk_id: usize, // Start playing this audio chunk (non-blocking)
     },
@@ -213,6 +354,31 @@ pub enum AnimationStep {
     ResetState,
 }
 
+/// A playback milestone, collected for `--events` while recording is enabled
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlaybackEvent {
+    CommitStart {
+        commit: String,
+    },
+    FileOpen {
+        path: String,
+    },
+    ChunkStart {
+        chunk_id: usize,
+    },
+    ChunkEnd {
+        chunk_id: usize,
+    },
+    /// An added line finished typing (1-based line number in the new file)
+    LineTyped {
+        path: String,
+        line: usize,
+    },
+    CommitEnd {
+        commit: String,
+    },
+}
+
 /// Animation state machine
 #[derive(Debug, Clone, PartialEq)]
 pub enum AnimationState {
@@ -245,7 +411,6 @@ struct ManualCheckpoint {
     line_offset: isize,
     dialog_title: Option<String>,
     dialog_typing_text: String,
-    speed_ms: u64,
 }
 
 impl ManualCheckpoint {
@@ -264,17 +429,89 @@ impl ManualCheckpoint {
             line_offset: engine.line_offset,
             dialog_title: engine.dialog_title.clone(),
             dialog_typing_text: engine.dialog_typing_text.clone(),
-            speed_ms: engine.speed_ms,
         }
     }
 }
 
+/// Engine state right after `load_commit`; seeking replays steps from here
+#[derive(Clone)]
+struct SeekOrigin {
+    checkpoint: ManualCheckpoint,
+    current_metadata: Option<CommitMetadata>,
+    pending_metadata: Option<CommitMetadata>,
+}
+
+/// Part of a commit's steps that carries narration and can be rebuilt on its own
+#[derive(Clone, Copy, PartialEq)]
+enum StepSection {
+    Intro,
+    File(usize),
+    Outro,
+}
+
+impl StepSection {
+    /// File path the section's narration chunks are generated for
+    fn narration_path(self, metadata: &CommitMetadata) -> &str {
+        match self {
+            StepSection::Intro => COMMIT_INTRO_PATH,
+            StepSection::File(index) => &metadata.changes[index].path,
+            StepSection::Outro => COMMIT_OUTRO_PATH,
+        }
+    }
+}
+
+/// Where a section's steps start and the narration chunks they were built with
+struct SectionSteps {
+    start: usize,
+    section: StepSection,
+    narration: Vec<usize>,
+}
+
 #[derive(Clone, Copy, PartialEq)]
 enum CheckpointKind {
     Line,
     Change,
 }
 
+/// Columns between the editor pane's left edge and its line numbers
+pub const EDITOR_PADDING: usize = 2;
+
+/// Columns the editor's gutter takes: the right-aligned line number, a space and
+/// a two-column separator; nothing when line numbers are off
+pub fn gutter_width(line_number_width: Option<usize>) -> usize {
+    line_number_width.map_or(0, |width| width + 3)
+}
+
+/// The buffer lines the editor shows, worked out by the engine so drawing
+/// matches the scroll position and wrapped line heights
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EditorWindow {
+    pub first_line: usize,
+    /// One past the last line shown; the last one may be cut off at the bottom
+    pub end_line: usize,
+    /// Digits of the line numbers, None when they are off
+    pub line_number_width: Option<usize>,
+    pub wrap: bool,
+}
+
+/// Indices of `metadata.changes` in playback order. Without narration there is
+/// no development flow to follow, so `flow` falls back to file tree order.
+pub fn file_play_order(metadata: &CommitMetadata, order: FileOrder) -> Vec<usize> {
+    let mut indices = metadata.sorted_file_indices();
+    match order {
+        FileOrder::Flow => {}
+        FileOrder::ReverseFlow => indices.reverse(),
+        FileOrder::Alpha => {
+            indices.sort_by(|&a, &b| metadata.changes[a].path.cmp(&metadata.changes[b].path))
+        }
+        FileOrder::Size => indices.sort_by_key(|&i| {
+            let (additions, deletions) = metadata.changes[i].line_counts();
+            std::cmp::Reverse(additions + deletions)
+        }),
+    }
+    indices
+}
+
 /// Main animation engine
 pub struct AnimationEngine {
     pub buffer: EditorBuffer,
@@ -288,8 +525,13 @@ pub struct AnimationEngine {
     pause_until: Option<Instant>,
     pub cursor_visible: bool,
     cursor_blink_timer: Instant,
+    cursor_blink: bool,
     viewport_height: usize,
     content_width: usize,
+    /// Whether the editor has a line-number gutter (narrows the text when wrapping)
+    show_line_numbers: bool,
+    /// Soft-wrap long lines, so one buffer line can take several rows
+    wrap: bool,
     pub current_file_index: usize,
     pub current_file_path: Option<String>,
     pub terminal_lines: Vec<String>,
@@ -314,6 +556,8 @@ pub struct AnimationEngine {
     pending_metadata: Option<CommitMetadata>,
     /// Speed rules for different file patterns
     speed_rules: Vec<SpeedRule>,
+    /// Factor applied to typing speeds by the `<` / `>` keys (1.0 = as configured)
+    speed_scale: f64,
     paused: bool,
     line_checkpoints: VecDeque<ManualCheckpoint>,
     change_checkpoints: VecDeque<ManualCheckpoint>,
@@ -325,6 +569,45 @@ pub struct AnimationEngine {
     audio_chunk_finished: bool,
     /// Set of chunk IDs that have already finished (to avoid waiting for already-completed audio)
     finished_audio_chunks: std::collections::HashSet<usize>,
+    /// Highlight only the changed words within modified lines
+    word_diff: bool,
+    /// Show deleted lines struck through for a beat before removing them
+    show_deletions: bool,
+    /// Remove deleted lines instantly instead of playing them
+    only_additions: bool,
+    /// Files changing more lines than this are summarized instead of animated
+    collapse_threshold: Option<usize>,
+    /// Collapsed files of the current commit the user chose to play in full
+    expanded_files: HashSet<usize>,
+    /// Order of files within a commit
+    file_order: FileOrder,
+    /// Type added lines a syntax token at a time instead of a character at a time
+    type_by_token: bool,
+    /// Fixed pause between files in milliseconds (None scales with typing speed)
+    file_gap_ms: Option<u64>,
+    /// Scale of the pause after each typed line (longer in presentation mode)
+    line_pause_scale: f64,
+    /// Pauses around typed edits
+    pauses: EditPauses,
+    /// Summarize every file as a `path | +N −M` line instead of typing its diff
+    stat_mode: bool,
+    /// Click played for each keystroke, if enabled
+    keystroke_sound: Option<crate::audio::keystroke::KeystrokeSound>,
+    /// Chunk IDs triggered since the last drain (for subtitle timing)
+    started_audio_chunks: Vec<usize>,
+    /// Replay origin for seeking within the loaded commit
+    seek_origin: Option<SeekOrigin>,
+    /// Set while seeking fast-forwards through steps (suppresses audio and scrolling)
+    replaying: bool,
+    /// Chunk to restart on resume after seeking while paused
+    deferred_audio_chunk: Option<usize>,
+    /// Narration silenced by the user; chunks are skipped rather than waited on
+    muted: bool,
+    /// Collect playback events for the UI to drain (off unless `--events` is used)
+    record_events: bool,
+    events: Vec<PlaybackEvent>,
+    /// Narrated sections of the loaded commit, in step order
+    sections: Vec<SectionSteps>,
 }
 
 impl AnimationEngine {
@@ -345,8 +628,11 @@ impl AnimationEngine {
             pause_until: None,
             cursor_visible: true,
             cursor_blink_timer: now,
+            cursor_blink: true,
             viewport_height: 20, // Default, will be updated from UI
             content_width: 80,   // Default, will be updated from UI
+            show_line_numbers: true,
+            wrap: true,
             current_file_index: 0,
             current_file_path: None,
             terminal_lines: Vec::new(),
@@ -361,6 +647,7 @@ impl AnimationEngine {
             current_metadata: None,
             pending_metadata: None,
             speed_rules: Vec::new(),
+            speed_scale: 1.0,
             paused: false,
             line_checkpoints: VecDeque::new(),
             change_checkpoints: VecDeque::new(),
@@ -368,6 +655,26 @@ impl AnimationEngine {
             current_audio_chunk: None,
             audio_chunk_finished: false,
             finished_audio_chunks: std::collections::HashSet::new(),
+            word_diff: false,
+            show_deletions: false,
+            only_additions: false,
+            collapse_threshold: None,
+            expanded_files: HashSet::new(),
+            file_order: FileOrder::Flow,
+            type_by_token: false,
+            file_gap_ms: None,
+            line_pause_scale: 1.0,
+            pauses: EditPauses::default(),
+            stat_mode: false,
+            keystroke_sound: None,
+            started_audio_chunks: Vec::new(),
+            seek_origin: None,
+            replaying: false,
+            deferred_audio_chunk: None,
+            muted: false,
+            record_events: false,
+            events: Vec::new(),
+            sections: Vec::new(),
         }
     }
 
@@ -391,9 +698,215 @@ impl AnimationEngine {
             let now = Instant::now();
             self.last_update = now;
             self.last_frame = now;
-            if let Some(audio_player) = &self.audio_player {
+            // Snap back to the cursor after scrolling around while paused
+            self.update_scroll();
+            if let Some(audio_player) = self.audio_player.as_ref().filter(|_| !self.muted) {
                 audio_player.resume();
             }
+            if let Some(chunk_id) = self.deferred_audio_chunk.take() {
+                self.start_audio_chunk(chunk_id);
+            }
+        }
+    }
+
+    /// Mute or unmute narration, returning whether it is now muted. Muting cuts
+    /// off the chunk being spoken and lets the animation run on without waiting;
+    /// after unmuting, narration picks up at the next chunk.
+    pub fn toggle_mute(&mut self) -> bool {
+        self.muted = !self.muted;
+        if let Some(audio_player) = &self.audio_player {
+            if self.muted {
+                audio_player.stop_chunks();
+                audio_player.pause();
+            } else if !self.paused {
+                audio_player.resume();
+            }
+        }
+        if self.muted {
+            self.current_audio_chunk = None;
+            self.audio_chunk_finished = false;
+            self.deferred_audio_chunk = None;
+        }
+        self.muted
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+
+    /// Fraction of the current commit's steps already executed (0.0-1.0).
+    pub fn progress(&self) -> f32 {
+        if self.steps.is_empty() {
+            return 0.0;
+        }
+        self.current_step.min(self.steps.len()) as f32 / self.steps.len() as f32
+    }
+
+    /// Jump to `fraction` (0.0-1.0) of the way through the current commit.
+    /// Editor, terminal and file tree state are rebuilt by replaying steps from the
+    /// start without delays, and the narration chunk covering the target restarts.
+    pub fn seek_to_fraction(&mut self, fraction: f32) -> bool {
+        let target = (fraction.clamp(0.0, 1.0) * self.steps.len() as f32).round() as usize;
+        self.seek_to_step(target)
+    }
+
+    /// Jump to just after the given file of the current commit is opened.
+    pub fn seek_to_file(&mut self, file_index: usize) -> bool {
+        let Some(switch) = self.steps.iter().position(|step| {
+            matches!(step, AnimationStep::SwitchFile { file_index: i, .. } if *i == file_index)
+        }) else {
+            return false;
+        };
+        self.seek_to_step(switch + 1)
+    }
+
+    /// Jump to the start of the next or previous file in playback order.
+    pub fn skip_file(&mut self, forward: bool) -> bool {
+        let switches: Vec<usize> = self
+            .steps
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| matches!(step, AnimationStep::SwitchFile { .. }))
+            .map(|(i, _)| i)
+            .collect();
+        // Files opened so far; the last of them is the current one
+        let opened = switches.iter().filter(|&&i| i < self.current_step).count();
+        let target = if forward {
+            switches.get(opened)
+        } else {
+            opened.checked_sub(2).and_then(|i| switches.get(i))
+        };
+        match target {
+            Some(&switch) => self.seek_to_step(switch + 1),
+            None => false,
+        }
+    }
+
+    /// Scroll the editor by `delta` lines; the next animation step re-centers the cursor.
+    pub fn scroll_by(&mut self, delta: isize) {
+        let offset = self.buffer.scroll_offset.saturating_add_signed(delta);
+        self.buffer.scroll_offset = offset.min(self.max_scroll_offset());
+    }
+
+    /// First line of the last screenful, counting wrapped rows
+    fn max_scroll_offset(&self) -> usize {
+        let line_number_width = self.line_number_width(self.buffer.scroll_offset);
+        let mut rows = 0;
+        for (index, line) in self.buffer.lines.iter().enumerate().rev() {
+            rows += self.calculate_line_display_height(line, line_number_width);
+            if rows > self.viewport_height {
+                return index + 1;
+            }
+        }
+        0
+    }
+
+    /// Scroll the editor a screen up or down, keeping one line of overlap like a pager.
+    pub fn scroll_page(&mut self, forward: bool) {
+        let page = self.viewport_height.saturating_sub(1).max(1) as isize;
+        self.scroll_by(if forward { page } else { -page });
+    }
+
+    fn seek_to_step(&mut self, target: usize) -> bool {
+        let Some(origin) = self.seek_origin.clone() else {
+            return false;
+        };
+        if self.steps.is_empty() {
+            return false;
+        }
+        let target = target.min(self.steps.len());
+
+        if let Some(audio_player) = &self.audio_player {
+            audio_player.stop_chunks();
+        }
+        self.current_audio_chunk = None;
+        self.audio_chunk_finished = false;
+        self.finished_audio_chunks.clear();
+        self.deferred_audio_chunk = None;
+
+        let paused = self.paused;
+        self.apply_checkpoint(origin.checkpoint);
+        self.paused = paused;
+        self.current_metadata = origin.current_metadata;
+        self.pending_metadata = origin.pending_metadata;
+        self.clear_checkpoints();
+
+        self.replaying = true;
+        while self.current_step < target {
+            let step = self.steps[self.current_step].clone();
+            if !matches!(
+                step,
+                AnimationStep::StartAudio { .. } | AnimationStep::WaitForAudio { .. }
+            ) {
+                self.execute_step(step);
+            }
+            self.current_step += 1;
+        }
+        self.replaying = false;
+
+        self.pause_until = None;
+        self.next_step_delay = self.speed_ms;
+        self.update_scroll();
+        if self.current_step >= self.steps.len() {
+            self.finish();
+        }
+        let now = Instant::now();
+        self.last_update = now;
+        self.last_frame = now;
+
+        if let Some(chunk_id) = self.audio_chunk_at(target) {
+            if self.paused {
+                self.deferred_audio_chunk = Some(chunk_id);
+            } else {
+                self.start_audio_chunk(chunk_id);
+            }
+        }
+        true
+    }
+
+    /// Narration chunk covering the current position: the one being waited on,
+    /// or the one whose StartAudio..WaitForAudio window contains the current step
+    pub fn active_audio_chunk(&self) -> Option<usize> {
+        self.current_audio_chunk
+            .or_else(|| self.audio_chunk_at(self.current_step))
+    }
+
+    /// Play the active narration chunk again from its start. Returns false when
+    /// there is no narration or no chunk covers the current position.
+    pub fn replay_audio_chunk(&mut self) -> bool {
+        let (Some(chunk_id), Some(audio_player)) = (self.active_audio_chunk(), &self.audio_player)
+        else {
+            return false;
+        };
+        audio_player.stop_chunks();
+        if self.paused {
+            self.deferred_audio_chunk = Some(chunk_id);
+        } else {
+            self.start_audio_chunk(chunk_id);
+        }
+        true
+    }
+
+    /// Chunk whose StartAudio..WaitForAudio window contains `step_index`, if any
+    fn audio_chunk_at(&self, step_index: usize) -> Option<usize> {
+        let mut active = None;
+        for step in &self.steps[..step_index.min(self.steps.len())] {
+            match step {
+                AnimationStep::StartAudio { chunk_id } => active = Some(*chunk_id),
+                AnimationStep::WaitForAudio { chunk_id } if active == Some(*chunk_id) => {
+                    active = None;
+                }
+                _ => {}
+            }
+        }
+        active
+    }
+
+    fn start_audio_chunk(&mut self, chunk_id: usize) {
+        if let Some(audio_player) = self.audio_player.as_ref().filter(|_| !self.muted) {
+            audio_player.trigger_chunk(chunk_id);
+            self.started_audio_chunks.push(chunk_id);
+            self.emit(PlaybackEvent::ChunkStart { chunk_id });
         }
     }
 
@@ -404,7 +917,7 @@ impl AnimationEngine {
         }
 
         if self.current_step >= self.steps.len() {
-            self.state = AnimationState::Finished;
+            self.finish();
             return false;
         }
 
@@ -418,10 +931,10 @@ impl AnimationEngine {
             executed = true;
 
             if self.current_step >= self.steps.len() {
-                self.state = AnimationState::Finished;
+                self.finish();
             }
 
-            if Self::is_boundary_step(&step, mode) {
+            if self.is_boundary_step(&step, mode) {
                 break;
             }
         }
@@ -471,17 +984,18 @@ impl AnimationEngine {
         self.line_offset = snapshot.line_offset;
         self.dialog_title = snapshot.dialog_title;
         self.dialog_typing_text = snapshot.dialog_typing_text;
-        self.speed_ms = snapshot.speed_ms;
+        self.speed_ms = self.current_speed();
         self.pause_until = None;
         self.paused = true;
         self.state = AnimationState::Playing;
     }
 
-    fn is_boundary_step(step: &AnimationStep, mode: StepMode) -> bool {
+    fn is_boundary_step(&self, step: &AnimationStep, mode: StepMode) -> bool {
         match mode {
             StepMode::Line => matches!(
                 step,
                 AnimationStep::Pause { .. }
+                    | AnimationStep::FileGap
                     | AnimationStep::SwitchFile { .. }
                     | AnimationStep::TerminalPrompt
                     | AnimationStep::TerminalOutput { .. }
@@ -492,7 +1006,7 @@ impl AnimationEngine {
                 | AnimationStep::TerminalPrompt
                 | AnimationStep::TerminalOutput { .. }
                 | AnimationStep::ResetState => true,
-                AnimationStep::Pause { multiplier } => Self::is_change_pause(*multiplier),
+                AnimationStep::Pause { kind, .. } => *kind == PauseKind::Hunk,
                 _ => false,
             },
         }
@@ -510,22 +1024,16 @@ impl AnimationEngine {
                 self.record_checkpoint(CheckpointKind::Change);
                 self.record_checkpoint(CheckpointKind::Line);
             }
-            AnimationStep::Pause { multiplier } => {
-                if self.active_pane == ActivePane::Editor {
-                    self.record_checkpoint(CheckpointKind::Line);
-                    if Self::is_change_pause(*multiplier) {
-                        self.record_checkpoint(CheckpointKind::Change);
-                    }
+            AnimationStep::Pause { kind, .. } if self.active_pane == ActivePane::Editor => {
+                self.record_checkpoint(CheckpointKind::Line);
+                if *kind == PauseKind::Hunk {
+                    self.record_checkpoint(CheckpointKind::Change);
                 }
             }
             _ => {}
         }
     }
 
-    fn is_change_pause(multiplier: f64) -> bool {
-        (multiplier - HUNK_PAUSE).abs() < f64::EPSILON
-    }
-
     fn record_checkpoint(&mut self, kind: CheckpointKind) {
         if self.current_step == 0 {
             return;
@@ -574,15 +1082,137 @@ impl AnimationEngine {
         self.speed_rules = rules;
     }
 
+    /// Collect playback events for `take_events`
+    pub fn set_record_events(&mut self, enabled: bool) {
+        self.record_events = enabled;
+    }
+
+    /// Drain the playback events since the last call, in order
+    pub fn take_events(&mut self) -> Vec<PlaybackEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Record an event unless steps are being replayed for a seek
+    fn emit(&mut self, event: PlaybackEvent) {
+        if self.record_events && !self.replaying {
+            self.events.push(event);
+        }
+    }
+
+    /// Mark the commit as played to the end
+    fn finish(&mut self) {
+        if self.state == AnimationState::Finished {
+            return;
+        }
+        self.state = AnimationState::Finished;
+        let commit = self
+            .current_metadata
+            .as_ref()
+            .or(self.pending_metadata.as_ref())
+            .map(|m| m.hash.clone())
+            .unwrap_or_default();
+        self.emit(PlaybackEvent::CommitEnd { commit });
+    }
+
+    /// Drain the audio chunks triggered since the last call, in trigger order
+    pub fn take_started_audio_chunks(&mut self) -> Vec<usize> {
+        std::mem::take(&mut self.started_audio_chunks)
+    }
+
+    /// Enable word-level highlighting within modified lines
+    pub fn set_word_diff(&mut self, enabled: bool) {
+        self.word_diff = enabled;
+    }
+
+    /// Strike through deleted lines for a beat before removing them
+    pub fn set_show_deletions(&mut self, enabled: bool) {
+        self.show_deletions = enabled;
+    }
+
+    /// Blink the editor cursor, or keep it steadily visible
+    pub fn set_cursor_blink(&mut self, enabled: bool) {
+        self.cursor_blink = enabled;
+        self.cursor_visible = true;
+    }
+
+    /// Summarize files changing more than `threshold` lines instead of animating them
+    pub fn set_collapse_threshold(&mut self, threshold: Option<usize>) {
+        self.collapse_threshold = threshold;
+    }
+
+    /// Summarize every file as a `path | +N −M` line instead of typing its diff
+    pub fn set_stat_mode(&mut self, enabled: bool) {
+        self.stat_mode = enabled;
+    }
+
+    /// Arrange each commit's files by development flow, path or size
+    pub fn set_file_order(&mut self, order: FileOrder) {
+        self.file_order = order;
+    }
+
+    /// Remove deleted lines without a pause so only additions are played
+    pub fn set_only_additions(&mut self, enabled: bool) {
+        self.only_additions = enabled;
+    }
+
+    /// Reveal whole syntax tokens per typing step instead of single characters
+    pub fn set_type_by_token(&mut self, enabled: bool) {
+        self.type_by_token = enabled;
+    }
+
+    /// Pause this many milliseconds between files instead of scaling with speed
+    pub fn set_file_gap(&mut self, gap_ms: Option<u64>) {
+        self.file_gap_ms = gap_ms;
+    }
+
+    /// Stretch the pause after each typed line by `scale`
+    pub fn set_line_pause_scale(&mut self, scale: f64) {
+        self.line_pause_scale = scale;
+    }
+
+    /// Use these pauses around typed edits instead of the defaults
+    pub fn set_pauses(&mut self, pauses: EditPauses) {
+        self.pauses = pauses;
+    }
+
+    /// Play a click for each typed character or token
+    pub fn set_keystroke_sound(&mut self, sound: crate::audio::keystroke::KeystrokeSound) {
+        self.keystroke_sound = Some(sound);
+    }
+
     /// Get the speed for a given file path based on speed rules
-    /// Returns the first matching rule's speed, or the base speed if no match
+    /// Returns the first matching rule's speed, or the base speed if no match,
+    /// scaled by any live speed change
     fn get_speed_for_file(&self, path: &str) -> u64 {
-        for rule in &self.speed_rules {
-            if rule.matches(path) {
-                return rule.speed_ms;
-            }
+        let speed = self
+            .speed_rules
+            .iter()
+            .find(|rule| rule.matches(path))
+            .map_or(self.base_speed_ms, |rule| rule.speed_ms);
+        (speed as f64 * self.speed_scale).round() as u64
+    }
+
+    /// Typing speed for the file currently open (the base speed before any file).
+    fn current_speed(&self) -> u64 {
+        match &self.current_file_path {
+            Some(path) => self.get_speed_for_file(path),
+            None => (self.base_speed_ms as f64 * self.speed_scale).round() as u64,
         }
-        self.base_speed_ms
+    }
+
+    /// Halve the delay per typed character (`faster`) or double it, for the rest
+    /// of playback including later files and commits. Stays within 1/8x and 8x of
+    /// the configured speed; returns the new delay in milliseconds. Narration
+    /// chunks keep their length, so they may finish early or late for the typing.
+    pub fn adjust_speed(&mut self, faster: bool) -> u64 {
+        let scale = if faster {
+            self.speed_scale / 2.0
+        } else {
+            self.speed_scale * 2.0
+        };
+        self.speed_scale = scale.clamp(MIN_SPEED_SCALE, MAX_SPEED_SCALE);
+        self.speed_ms = self.current_speed();
+        self.speed_ms
     }
 
     /// Sets the viewport height for scroll calculations.
@@ -590,6 +1220,44 @@ impl AnimationEngine {
         self.viewport_height = height;
     }
 
+    /// Shows or hides the line-number gutter.
+    pub fn set_line_numbers(&mut self, enabled: bool) {
+        self.show_line_numbers = enabled;
+    }
+
+    /// Digits of the line numbers when the editor shows lines from `first_line`,
+    /// sized to the largest number that can fit the viewport; None when they are off
+    pub fn line_number_width(&self, first_line: usize) -> Option<usize> {
+        self.show_line_numbers.then(|| {
+            let last_line = (first_line + self.viewport_height).min(self.buffer.lines.len());
+            last_line.to_string().len().max(3)
+        })
+    }
+
+    /// Wraps long lines onto extra rows instead of cutting them off.
+    pub fn set_wrap(&mut self, enabled: bool) {
+        self.wrap = enabled;
+    }
+
+    /// Lines from the scroll position that fill the viewport once wrapped
+    pub fn editor_window(&self) -> EditorWindow {
+        let first_line = self.buffer.scroll_offset.min(self.buffer.lines.len());
+        let line_number_width = self.line_number_width(first_line);
+        let mut end_line = first_line;
+        let mut rows = 0;
+        while end_line < self.buffer.lines.len() && rows < self.viewport_height {
+            rows +=
+                self.calculate_line_display_height(&self.buffer.lines[end_line], line_number_width);
+            end_line += 1;
+        }
+        EditorWindow {
+            first_line,
+            end_line,
+            line_number_width,
+            wrap: self.wrap,
+        }
+    }
+
     /// Sets the content width for line wrapping calculations.
     pub fn set_content_width(&mut self, width: usize) {
         self.content_width = width;
@@ -622,6 +1290,58 @@ impl AnimationEngine {
 
     /// Load a commit and generate animation steps
     pub fn load_commit(&mut self, metadata: &CommitMetadata) {
+        self.expanded_files.clear();
+        self.emit(PlaybackEvent::CommitStart {
+            commit: metadata.hash.clone(),
+        });
+        self.build_steps(metadata);
+    }
+
+    /// Expand the collapsed or stat-summarized file being shown and play it in full
+    /// from where it opens. Returns false when the current file isn't summarized.
+    pub fn expand_current_file(&mut self) -> bool {
+        // Until the intro finishes, the file index still refers to the previous commit
+        if self.pending_metadata.is_some() {
+            return false;
+        }
+        let Some(metadata) = self.current_metadata.clone() else {
+            return false;
+        };
+        let index = self.current_file_index;
+        let collapsed = metadata.changes.get(index).is_some_and(|c| {
+            c.exceeds_collapse_threshold(self.collapse_threshold)
+                || (self.stat_mode
+                    && !c.is_excluded
+                    && !matches!(c.status, FileStatus::Deleted | FileStatus::Renamed))
+        });
+        if !collapsed || !self.expanded_files.insert(index) {
+            return false;
+        }
+        self.build_steps(&metadata);
+        self.seek_to_file(index)
+    }
+
+    /// Playback order of the commit's files. Narrated development flow is played
+    /// in the order it is narrated, with files that aren't narrated last.
+    fn ordered_file_indices(&self, metadata: &CommitMetadata) -> Vec<usize> {
+        let mut indices = file_play_order(metadata, self.file_order);
+        if let (Some(player), FileOrder::Flow | FileOrder::ReverseFlow) =
+            (&self.audio_player, self.file_order)
+        {
+            indices.sort_by_cached_key(|&index| {
+                player
+                    .get_chunks_for_file(&metadata.changes[index].path)
+                    .iter()
+                    .map(|c| c.chunk_id)
+                    .min()
+                    .unwrap_or(usize::MAX)
+            });
+        }
+        indices
+    }
+
+    /// Generate the animation steps for a commit, starting from its intro
+    fn build_steps(&mut self, metadata: &CommitMetadata) {
         // Store pending metadata to be applied on ResetState
         self.pending_metadata = Some(metadata.clone());
 
@@ -633,6 +1353,7 @@ impl AnimationEngine {
         self.finished_audio_chunks.clear();
         self.current_audio_chunk = None;
         self.audio_chunk_finished = false;
+        self.deferred_audio_chunk = None;
 
         // Check if this is a working tree diff (not a real commit)
         let is_working_tree = metadata.hash == "working-tree";
@@ -642,6 +1363,7 @@ impl AnimationEngine {
             self.add_terminal_command("git diff --stat");
             self.steps.push(AnimationStep::Pause {
                 multiplier: CHECKOUT_PAUSE,
+                kind: PauseKind::Other,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: format!("📝 {}", metadata.message),
@@ -655,6 +1377,7 @@ impl AnimationEngine {
             });
             self.steps.push(AnimationStep::Pause {
                 multiplier: CHECKOUT_OUTPUT_PAUSE,
+                kind: PauseKind::Other,
             });
         } else {
             // Time travel to commit date
@@ -662,18 +1385,21 @@ impl AnimationEngine {
             self.add_terminal_command(&format!("time-travel {}", datetime_str));
             self.steps.push(AnimationStep::Pause {
                 multiplier: CHECKOUT_PAUSE,
+                kind: PauseKind::Other,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: "⚡ Initializing temporal displacement field...".to_string(),
             });
             self.steps.push(AnimationStep::Pause {
                 multiplier: CHECKOUT_OUTPUT_PAUSE * 0.5,
+                kind: PauseKind::Other,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: "✨ Warping through spacetime...".to_string(),
             });
             self.steps.push(AnimationStep::Pause {
                 multiplier: CHECKOUT_OUTPUT_PAUSE * 0.5,
+                kind: PauseKind::Other,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: format!("🕰️  Arrived at {}", datetime_str),
@@ -687,154 +1413,283 @@ impl AnimationEngine {
             });
             self.steps.push(AnimationStep::Pause {
                 multiplier: CHECKOUT_OUTPUT_PAUSE,
+                kind: PauseKind::Other,
             });
         }
 
         // Apply new metadata after intro animation
         self.steps.push(AnimationStep::ResetState);
+        self.sections.clear();
+        self.add_section(metadata, StepSection::Intro);
+        for index in self.ordered_file_indices(metadata) {
+            self.add_section(metadata, StepSection::File(index));
+        }
+        self.add_section(metadata, StepSection::Outro);
 
-        // Sort file changes to match FileTree display order (directory -> filename)
-        let sorted_indices = metadata.sorted_file_indices();
-
-        // Process all file changes in sorted order
-        for &index in &sorted_indices {
-            let change = &metadata.changes[index];
-            match (change.is_excluded, &change.status) {
-                // Skip excluded files (lock files and generated files)
-                (true, _) => {
-                    // Switch to the excluded file to show in file tree
-                    let old_content = change.old_content.clone().unwrap_or_default();
-                    let new_content = change.new_content.clone().unwrap_or_default();
-                    self.steps.push(AnimationStep::SwitchFile {
-                        file_index: index,
-                        old_content,
-                        new_content,
-                        path: change.path.clone(),
-                    });
+        // Start with empty editor (no file opened yet)
+        self.buffer = EditorBuffer::new();
+        self.clear_checkpoints();
 
-                    self.steps.push(AnimationStep::Pause {
-                        multiplier: OPEN_FILE_PAUSE,
-                    });
-                    let reason = change
-                        .exclusion_reason
-                        .as_deref()
-                        .unwrap_or("excluded file");
-                    self.steps.push(AnimationStep::TerminalOutput {
-                        text: format!("📦 {} (skipped - {})", change.path, reason),
-                    });
-                    self.steps.push(AnimationStep::Pause {
-                        multiplier: OPEN_CMD_PAUSE,
-                    });
-                }
-                // For deleted files, skip editor animation and only run rm + git add
-                (false, FileStatus::Deleted) => {
-                    // Switch to the deleted file to show in file tree
-                    let old_content = change.old_content.clone().unwrap_or_default();
-                    self.steps.push(AnimationStep::SwitchFile {
-                        file_index: index,
-                        old_content,
-                        new_content: String::new(),
-                        path: change.path.clone(),
-                    });
+        let mut checkpoint = ManualCheckpoint::new(self);
+        checkpoint.step_index = 0;
+        self.seek_origin = Some(SeekOrigin {
+            checkpoint,
+            current_metadata: self.current_metadata.clone(),
+            pending_metadata: self.pending_metadata.clone(),
+        });
+    }
 
-                    self.steps.push(AnimationStep::Pause {
-                        multiplier: GIT_ADD_PAUSE,
-                    });
-                    self.add_terminal_command(&format!("rm {}", change.path));
-                    self.steps.push(AnimationStep::Pause {
-                        multiplier: GIT_ADD_CMD_PAUSE,
-                    });
-                    self.add_terminal_command(&format!("git add {}", change.path));
+    /// Append one narrated section of a commit and remember what it was built with
+    fn add_section(&mut self, metadata: &CommitMetadata, section: StepSection) {
+        let start = self.steps.len();
+        let narration = self.narration_ids(section.narration_path(metadata));
+        self.add_section_steps(metadata, section);
+        self.sections.push(SectionSteps {
+            start,
+            section,
+            narration,
+        });
+    }
+
+    fn add_section_steps(&mut self, metadata: &CommitMetadata, section: StepSection) {
+        match section {
+            StepSection::Intro => self.add_narration(COMMIT_INTRO_PATH),
+            StepSection::File(index) => self.add_file_steps(index, &metadata.changes[index]),
+            StepSection::Outro => {
+                self.add_narration(COMMIT_OUTRO_PATH);
+                self.add_commit_steps(metadata);
+            }
+        }
+    }
+
+    /// Rebuild the sections whose narration arrived after the commit was loaded,
+    /// so it still plays (`--no-voiceover-wait`). Sections already opened keep
+    /// whatever narration they had.
+    pub fn refresh_narration(&mut self) -> bool {
+        let Some(metadata) = self
+            .pending_metadata
+            .clone()
+            .or_else(|| self.current_metadata.clone())
+        else {
+            return false;
+        };
+        let mut refreshed = false;
+        for i in 0..self.sections.len() {
+            let SectionSteps { start, section, .. } = self.sections[i];
+            let end = self
+                .sections
+                .get(i + 1)
+                .map_or(self.steps.len(), |next| next.start);
+            if self.section_opened(start, end, section) {
+                continue;
+            }
+            let narration = self.narration_ids(section.narration_path(&metadata));
+            if narration == self.sections[i].narration {
+                continue;
+            }
+
+            // Steps before a file's SwitchFile don't depend on narration, so any
+            // of them already played come out the same
+            let tail = self.steps.split_off(end);
+            self.steps.truncate(start);
+            self.add_section_steps(&metadata, section);
+            let new_end = self.steps.len();
+            self.steps.extend(tail);
+            for later in &mut self.sections[i + 1..] {
+                later.start = later.start - end + new_end;
+            }
+            self.sections[i].narration = narration;
+            refreshed = true;
+        }
+        refreshed
+    }
+
+    /// Whether playback has reached a section's narration: a file counts once its
+    /// SwitchFile has run, the intro and outro once their first step has
+    fn section_opened(&self, start: usize, end: usize, section: StepSection) -> bool {
+        let opening = match section {
+            StepSection::File(_) => self.steps[start..end]
+                .iter()
+                .position(|step| matches!(step, AnimationStep::SwitchFile { .. }))
+                .map_or(start, |offset| start + offset),
+            StepSection::Intro | StepSection::Outro => start,
+        };
+        opening < self.current_step
+    }
+
+    /// Open, edit and stage one file
+    fn add_file_steps(&mut self, index: usize, change: &FileChange) {
+        match (change.is_excluded, &change.status) {
+            // Skip excluded files (lock files and generated files)
+            (true, _) => {
+                // Switch to the excluded file to show in file tree
+                let old_content = change.old_content.clone().unwrap_or_default();
+                let new_content = change.new_content.clone().unwrap_or_default();
+                self.steps.push(AnimationStep::SwitchFile {
+                    file_index: index,
+                    old_content,
+                    new_content,
+                    path: change.path.clone(),
+                });
+
+                self.steps.push(AnimationStep::FileGap);
+                let reason = change
+                    .exclusion_reason
+                    .as_deref()
+                    .unwrap_or("excluded file");
+                self.steps.push(AnimationStep::TerminalOutput {
+                    text: format!("📦 {} (skipped - {})", change.path, reason),
+                });
+                self.steps.push(AnimationStep::Pause {
+                    multiplier: OPEN_CMD_PAUSE,
+                    kind: PauseKind::Other,
+                });
+            }
+            // For deleted files, skip editor animation and only run rm + git add
+            (false, FileStatus::Deleted) => {
+                // Switch to the deleted file to show in file tree
+                let old_content = change.old_content.clone().unwrap_or_default();
+                self.steps.push(AnimationStep::SwitchFile {
+                    file_index: index,
+                    old_content,
+                    new_content: String::new(),
+                    path: change.path.clone(),
+                });
+
+                self.steps.push(AnimationStep::Pause {
+                    multiplier: GIT_ADD_PAUSE,
+                    kind: PauseKind::Other,
+                });
+                self.add_terminal_command(&format!("rm {}", change.path));
+                self.steps.push(AnimationStep::Pause {
+                    multiplier: GIT_ADD_CMD_PAUSE,
+                    kind: PauseKind::Other,
+                });
+                self.add_terminal_command(&format!("git add {}", change.path));
+                self.steps.push(AnimationStep::Pause {
+                    multiplier: GIT_ADD_CMD_PAUSE,
+                    kind: PauseKind::Other,
+                });
+            }
+            // For renamed/moved files, skip editor animation and only run mv + git add
+            (false, FileStatus::Renamed) => {
+                // Switch to the renamed file to show in file tree
+                let old_content = change.old_content.clone().unwrap_or_default();
+                let new_content = change.new_content.clone().unwrap_or_default();
+                self.steps.push(AnimationStep::SwitchFile {
+                    file_index: index,
+                    old_content,
+                    new_content,
+                    path: change.path.clone(),
+                });
+
+                self.steps.push(AnimationStep::Pause {
+                    multiplier: GIT_ADD_PAUSE,
+                    kind: PauseKind::Other,
+                });
+                if let Some(old_path) = &change.old_path {
+                    self.add_terminal_command(&format!("mv {} {}", old_path, change.path));
                     self.steps.push(AnimationStep::Pause {
                         multiplier: GIT_ADD_CMD_PAUSE,
+                        kind: PauseKind::Other,
                     });
                 }
-                // For renamed/moved files, skip editor animation and only run mv + git add
-                (false, FileStatus::Renamed) => {
-                    // Switch to the renamed file to show in file tree
-                    let old_content = change.old_content.clone().unwrap_or_default();
-                    let new_content = change.new_content.clone().unwrap_or_default();
-                    self.steps.push(AnimationStep::SwitchFile {
-                        file_index: index,
-                        old_content,
-                        new_content,
-                        path: change.path.clone(),
-                    });
-
-                    self.steps.push(AnimationStep::Pause {
-                        multiplier: GIT_ADD_PAUSE,
-                    });
-                    if let Some(old_path) = &change.old_path {
-                        self.add_terminal_command(&format!("mv {} {}", old_path, change.path));
-                        self.steps.push(AnimationStep::Pause {
-                            multiplier: GIT_ADD_CMD_PAUSE,
-                        });
-                    }
-                    self.add_terminal_command(&format!("git add {}", change.path));
+                self.add_terminal_command(&format!("git add {}", change.path));
+                self.steps.push(AnimationStep::Pause {
+                    multiplier: GIT_ADD_CMD_PAUSE,
+                    kind: PauseKind::Other,
+                });
+            }
+            // Normal files (Added, Modified, etc.) - full editor animation
+            (false, _) => {
+                // Open file in editor
+                if index == 0 {
                     self.steps.push(AnimationStep::Pause {
-                        multiplier: GIT_ADD_CMD_PAUSE,
+                        multiplier: OPEN_FILE_FIRST_PAUSE,
+                        kind: PauseKind::Other,
                     });
+                } else {
+                    self.steps.push(AnimationStep::FileGap);
                 }
-                // Normal files (Added, Modified, etc.) - full editor animation
-                (false, _) => {
-                    // Open file in editor
-                    if index == 0 {
-                        self.steps.push(AnimationStep::Pause {
-                            multiplier: OPEN_FILE_FIRST_PAUSE,
-                        });
-                    } else {
-                        self.steps.push(AnimationStep::Pause {
-                            multiplier: OPEN_FILE_PAUSE,
-                        });
-                    }
-                    // Show "Open File..." dialog and type the file path
-                    self.steps.push(AnimationStep::OpenFileDialogStart);
-                    self.steps.push(AnimationStep::Pause { multiplier: 5.0 });
+                // Show "Open File..." dialog and type the file path
+                self.steps.push(AnimationStep::OpenFileDialogStart);
+                self.steps.push(AnimationStep::Pause {
+                    multiplier: 5.0,
+                    kind: PauseKind::Other,
+                });
 
-                    // Type each character of the file path
-                    for ch in change.path.chars() {
-                        self.steps.push(AnimationStep::DialogTypeChar { ch });
-                    }
+                // Type each character of the file path
+                for ch in change.path.chars() {
+                    self.steps.push(AnimationStep::DialogTypeChar { ch });
+                }
 
-                    self.steps.push(AnimationStep::Pause {
-                        multiplier: OPEN_CMD_PAUSE,
-                    });
+                self.steps.push(AnimationStep::Pause {
+                    multiplier: OPEN_CMD_PAUSE,
+                    kind: PauseKind::Other,
+                });
 
-                    // Add file switch step with both old and new content
-                    let old_content = change.old_content.clone().unwrap_or_default();
+                // Add file switch step with both old and new content; a collapsed
+                // file opens already in its final state, a stat summary empty
+                let expanded = self.expanded_files.contains(&index);
+                let summarized = self.stat_mode && !expanded;
+                let collapsed = !summarized
+                    && !expanded
+                    && change.exceeds_collapse_threshold(self.collapse_threshold);
+                let (old_content, new_content) = if summarized {
+                    (String::new(), String::new())
+                } else {
                     let new_content = change.new_content.clone().unwrap_or_default();
-                    self.steps.push(AnimationStep::SwitchFile {
-                        file_index: index,
-                        old_content,
-                        new_content,
-                        path: change.path.clone(),
-                    });
+                    let old_content = if collapsed {
+                        new_content.clone()
+                    } else {
+                        change.old_content.clone().unwrap_or_default()
+                    };
+                    (old_content, new_content)
+                };
+                self.steps.push(AnimationStep::SwitchFile {
+                    file_index: index,
+                    old_content,
+                    new_content,
+                    path: change.path.clone(),
+                });
 
-                    // Add pause before starting file animation
-                    self.steps.push(AnimationStep::Pause {
-                        multiplier: FILE_SWITCH_PAUSE,
-                    });
+                // Add pause before starting file animation
+                self.steps.push(AnimationStep::Pause {
+                    multiplier: FILE_SWITCH_PAUSE,
+                    kind: PauseKind::Other,
+                });
 
-                    // Generate animation steps for this file
+                // Generate animation steps for this file
+                if summarized {
+                    self.generate_steps_for_stat_summary(change);
+                } else if collapsed {
+                    self.generate_steps_for_collapsed_file(change);
+                } else {
                     self.generate_steps_for_file(change);
-
-                    // Git add this file after editing
-                    self.steps.push(AnimationStep::Pause {
-                        multiplier: GIT_ADD_PAUSE,
-                    });
-                    self.add_terminal_command(&format!("git add {}", change.path));
-                    self.steps.push(AnimationStep::Pause {
-                        multiplier: GIT_ADD_CMD_PAUSE,
-                    });
                 }
+
+                // Git add this file after editing
+                self.steps.push(AnimationStep::Pause {
+                    multiplier: GIT_ADD_PAUSE,
+                    kind: PauseKind::Other,
+                });
+                self.add_terminal_command(&format!("git add {}", change.path));
+                self.steps.push(AnimationStep::Pause {
+                    multiplier: GIT_ADD_CMD_PAUSE,
+                    kind: PauseKind::Other,
+                });
             }
         }
+    }
 
+    /// Commit and push once every file is staged
+    fn add_commit_steps(&mut self, metadata: &CommitMetadata) {
         // Skip git commit/push animation for working tree diffs
-        if is_working_tree {
+        if metadata.hash == "working-tree" {
             // Just add a final pause for working tree mode
             self.steps.push(AnimationStep::Pause {
                 multiplier: PUSH_FINAL_PAUSE,
+                kind: PauseKind::Other,
             });
         } else {
             // Git commit
@@ -843,6 +1698,7 @@ impl AnimationEngine {
             self.add_terminal_command(&format!("git commit -m \"{}\"", commit_message));
             self.steps.push(AnimationStep::Pause {
                 multiplier: GIT_COMMIT_PAUSE,
+                kind: PauseKind::Other,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: format!("💾 [main {}] {}", &metadata.hash[..7], commit_message),
@@ -856,36 +1712,42 @@ impl AnimationEngine {
             });
             self.steps.push(AnimationStep::Pause {
                 multiplier: COMMIT_OUTPUT_PAUSE,
+                kind: PauseKind::Other,
             });
 
             // Git push
             self.add_terminal_command("git push origin main");
             self.steps.push(AnimationStep::Pause {
                 multiplier: GIT_PUSH_PAUSE,
+                kind: PauseKind::Other,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: "🚀 Launching code into the cloud...".to_string(),
             });
             self.steps.push(AnimationStep::Pause {
                 multiplier: PUSH_OUTPUT_PAUSE,
+                kind: PauseKind::Other,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: "📦 Compressing digital dreams: 100% (5/5)".to_string(),
             });
             self.steps.push(AnimationStep::Pause {
                 multiplier: PUSH_OUTPUT_PAUSE,
+                kind: PauseKind::Other,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: "✍️  Signing with invisible ink: done.".to_string(),
             });
             self.steps.push(AnimationStep::Pause {
                 multiplier: GIT_PUSH_PAUSE,
+                kind: PauseKind::Other,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: "📡 Beaming to origin/main via satellite...".to_string(),
             });
             self.steps.push(AnimationStep::Pause {
                 multiplier: PUSH_OUTPUT_PAUSE,
+                kind: PauseKind::Other,
             });
             self.steps.push(AnimationStep::TerminalOutput {
                 text: format!(
@@ -896,12 +1758,68 @@ impl AnimationEngine {
             });
             self.steps.push(AnimationStep::Pause {
                 multiplier: PUSH_FINAL_PAUSE,
+                kind: PauseKind::Other,
             });
         }
+    }
 
-        // Start with empty editor (no file opened yet)
-        self.buffer = EditorBuffer::new();
-        self.clear_checkpoints();
+    /// Summarize a collapsed file in the terminal instead of typing it out, and
+    /// play its narration (an overview of the change) over the summary
+    fn generate_steps_for_collapsed_file(&mut self, change: &FileChange) {
+        let (additions, deletions) = change.line_counts();
+        self.steps.push(AnimationStep::TerminalOutput {
+            text: format!(
+                "📦 {} +{} −{} (collapsed, press e to expand)",
+                change.path, additions, deletions
+            ),
+        });
+        self.steps.push(AnimationStep::Pause {
+            multiplier: OPEN_CMD_PAUSE,
+            kind: PauseKind::Other,
+        });
+
+        self.add_narration(&change.path);
+    }
+
+    /// Show a file as one `path | +N −M` line instead of typing its diff (`--stat-mode`)
+    fn generate_steps_for_stat_summary(&mut self, change: &FileChange) {
+        let (additions, deletions) = change.line_counts();
+        self.steps.push(AnimationStep::InsertLine {
+            line: 0,
+            content: format!("{} | +{} −{}", change.path, additions, deletions),
+            inline_changes: Vec::new(),
+        });
+        self.steps.push(AnimationStep::Pause {
+            multiplier: self.pauses.binary_summary,
+            kind: PauseKind::LineEnd,
+        });
+        self.add_narration(&change.path);
+    }
+
+    /// Play the voiced chunks narrating `path` one after another, waiting for each
+    fn add_narration(&mut self, path: &str) {
+        for chunk_id in self.narration_ids(path) {
+            self.steps.push(AnimationStep::StartAudio { chunk_id });
+            self.steps.push(AnimationStep::WaitForAudio { chunk_id });
+        }
+    }
+
+    /// Voiced chunks generated so far for `path`, in narration order
+    fn narration_ids(&self, path: &str) -> Vec<usize> {
+        let mut chunk_ids: Vec<usize> = self
+            .audio_player
+            .as_ref()
+            .map(|player| {
+                player
+                    .get_chunks_for_file(path)
+                    .into_iter()
+                    .filter(|c| c.has_audio)
+                    .map(|c| c.chunk_id)
+                    .collect()
+            })
+            .unwrap_or_default();
+        chunk_ids.sort_unstable();
+        chunk_ids
     }
 
     /// Generate animation steps for a file change
@@ -923,16 +1841,19 @@ impl AnimationEngine {
                 .into_iter()
                 .filter(|c| c.has_audio)
                 .collect();
-            eprintln!(
-                "[AUDIO DEBUG] File: {}, Hunks: {}, Audio chunks: {}",
+            log::debug!(
+                "File: {}, Hunks: {}, Audio chunks: {}",
                 change.path,
                 change.hunks.len(),
                 chunks.len()
             );
             for chunk in &chunks {
-                eprintln!(
-                    "  Chunk {}: hunk_indices={:?}, has_audio={}, duration={:.1}s",
-                    chunk.chunk_id, chunk.hunk_indices, chunk.has_audio, chunk.audio_duration_secs
+                log::debug!(
+                    "Chunk {}: hunk_indices={:?}, has_audio={}, duration={:.1}s",
+                    chunk.chunk_id,
+                    chunk.hunk_indices,
+                    chunk.has_audio,
+                    chunk.audio_duration_secs
                 );
             }
             chunks
@@ -940,8 +1861,22 @@ impl AnimationEngine {
             Vec::new()
         };
 
+        // The file's intro narrates it as a whole and finishes before typing starts
+        if let Some(intro) = audio_chunks.iter().find(|c| c.hunk_indices.is_empty()) {
+            self.steps.push(AnimationStep::StartAudio {
+                chunk_id: intro.chunk_id,
+            });
+            self.steps.push(AnimationStep::WaitForAudio {
+                chunk_id: intro.chunk_id,
+            });
+        }
+
         let mut current_chunk_id: Option<usize> = None;
 
+        let tokens = self
+            .type_by_token
+            .then(|| TokenMap::new(&change.path, change.new_content.as_deref().unwrap_or("")));
+
         // Process each hunk
         for (hunk_idx, hunk) in change.hunks.iter().enumerate() {
             // Match hunk to audio chunk by hunk index
@@ -950,13 +1885,13 @@ impl AnimationEngine {
                 .find(|chunk| chunk.hunk_indices.contains(&hunk_idx));
 
             if matching_chunk.is_some() {
-                eprintln!(
-                    "  Hunk {} matched to chunk {:?}",
+                log::debug!(
+                    "Hunk {} matched to chunk {:?}",
                     hunk_idx,
                     matching_chunk.as_ref().map(|c| c.chunk_id)
                 );
             } else {
-                eprintln!("  Hunk {} NO MATCH", hunk_idx);
+                log::debug!("Hunk {} has no narration chunk", hunk_idx);
             }
 
             // If we've entered a new chunk, start its audio
@@ -992,8 +1927,16 @@ impl AnimationEngine {
                 &old_lines,
             );
 
-            let (final_cursor_line, _final_buffer_line) =
-                self.generate_steps_for_hunk(hunk, current_cursor_line, target_line);
+            let (final_cursor_line, _final_buffer_line) = if change.is_binary {
+                self.generate_steps_for_binary_hunk(hunk, target_line)
+            } else {
+                self.generate_steps_for_hunk(
+                    hunk,
+                    current_cursor_line,
+                    target_line,
+                    tokens.as_ref(),
+                )
+            };
 
             current_cursor_line = final_cursor_line;
 
@@ -1014,7 +1957,8 @@ impl AnimationEngine {
 
             // Add pause between hunks
             self.steps.push(AnimationStep::Pause {
-                multiplier: HUNK_PAUSE,
+                multiplier: self.pauses.hunk,
+                kind: PauseKind::Hunk,
             });
         }
 
@@ -1079,7 +2023,7 @@ impl AnimationEngine {
         }
 
         // Generate movement steps
-        let pause_multiplier = (CURSOR_MOVE_PAUSE * base_speed_multiplier).max(0.01);
+        let pause_multiplier = (self.pauses.cursor_move * base_speed_multiplier).max(0.01);
 
         for line in positions {
             if line != from_line {
@@ -1091,6 +2035,7 @@ impl AnimationEngine {
                 self.steps.push(AnimationStep::MoveCursor { line, col });
                 self.steps.push(AnimationStep::Pause {
                     multiplier: pause_multiplier,
+                    kind: PauseKind::CursorMove,
                 });
             }
         }
@@ -1108,6 +2053,29 @@ impl AnimationEngine {
         }
     }
 
+    /// Show a binary file's summary lines whole, without typing them out
+    /// Returns (final_cursor_line, final_buffer_line)
+    fn generate_steps_for_binary_hunk(
+        &mut self,
+        hunk: &DiffHunk,
+        start_buffer_line: usize,
+    ) -> (usize, usize) {
+        let mut buffer_line = start_buffer_line;
+        for line_change in &hunk.lines {
+            self.steps.push(AnimationStep::InsertLine {
+                line: buffer_line,
+                content: line_change.content.clone(),
+                inline_changes: Vec::new(),
+            });
+            self.steps.push(AnimationStep::Pause {
+                multiplier: self.pauses.binary_summary,
+                kind: PauseKind::LineEnd,
+            });
+            buffer_line += 1;
+        }
+        (buffer_line.saturating_sub(1), buffer_line)
+    }
+
     /// Generate animation steps for a diff hunk
     /// Returns (final_cursor_line, final_buffer_line)
     fn generate_steps_for_hunk(
@@ -1115,6 +2083,7 @@ impl AnimationEngine {
         hunk: &DiffHunk,
         start_cursor_line: usize,
         start_buffer_line: usize,
+        tokens: Option<&TokenMap>,
     ) -> (usize, usize) {
         // buffer_line tracks the actual line number in the current buffer
         let mut buffer_line = start_buffer_line;
@@ -1123,12 +2092,24 @@ impl AnimationEngine {
         for line_change in &hunk.lines {
             match line_change.change_type {
                 LineChangeType::Deletion => {
+                    if self.show_deletions {
+                        self.steps
+                            .push(AnimationStep::MarkDeletion { line: buffer_line });
+                        self.steps.push(AnimationStep::Pause {
+                            multiplier: self.pauses.delete_mark,
+                            kind: PauseKind::Deletion,
+                        });
+                    }
                     // Delete the entire line at current buffer position
                     self.steps
                         .push(AnimationStep::DeleteLine { line: buffer_line });
-                    self.steps.push(AnimationStep::Pause {
-                        multiplier: DELETE_LINE_PAUSE,
-                    });
+                    // Without deletion playback the line just disappears
+                    if !self.only_additions {
+                        self.steps.push(AnimationStep::Pause {
+                            multiplier: self.pauses.delete_line,
+                            kind: PauseKind::Deletion,
+                        });
+                    }
                     cursor_line = buffer_line;
                     // After deletion, buffer_line stays the same
                     // (the next line moves up to this position)
@@ -1139,25 +2120,49 @@ impl AnimationEngine {
 
                     // Insert line with indentation already included
                     let indentation: String = content.chars().take(indentation_len).collect();
+                    let inline_changes = if self.word_diff {
+                        line_change.inline_changes.clone()
+                    } else {
+                        Vec::new()
+                    };
                     self.steps.push(AnimationStep::InsertLine {
                         line: buffer_line,
                         content: indentation,
+                        inline_changes,
                     });
 
-                    // Type each character after the indentation
-                    for (i, ch) in content.chars().skip(indentation_len).enumerate() {
-                        self.steps.push(AnimationStep::InsertChar {
-                            line: buffer_line,
-                            col: indentation_len + i,
-                            ch,
-                        });
+                    // Type each token (or character) after the indentation
+                    if let Some(tokens) = tokens {
+                        let spans = line_change
+                            .new_line_no
+                            .map(|n| tokens.line_spans(n, content))
+                            .unwrap_or_default();
+                        let mut col = indentation_len;
+                        for text in split_tokens(content, indentation_len, &spans) {
+                            let len = text.chars().count();
+                            self.steps.push(AnimationStep::InsertText {
+                                line: buffer_line,
+                                col,
+                                text,
+                            });
+                            col += len;
+                        }
+                    } else {
+                        for (i, ch) in content.chars().skip(indentation_len).enumerate() {
+                            self.steps.push(AnimationStep::InsertChar {
+                                line: buffer_line,
+                                col: indentation_len + i,
+                                ch,
+                            });
+                        }
                     }
 
                     cursor_line = buffer_line;
                     buffer_line += 1; // Move to next line after insertion
 
                     self.steps.push(AnimationStep::Pause {
-                        multiplier: INSERT_LINE_PAUSE,
+                        multiplier: self.pauses.insert_line,
+                        kind: PauseKind::LineEnd,
                     });
                 }
                 LineChangeType::Context => {
@@ -1174,7 +2179,8 @@ impl AnimationEngine {
                             col,
                         });
                         self.steps.push(AnimationStep::Pause {
-                            multiplier: CURSOR_MOVE_PAUSE,
+                            multiplier: self.pauses.cursor_move,
+                            kind: PauseKind::CursorMove,
                         });
                     }
                     cursor_line = buffer_line;
@@ -1210,13 +2216,16 @@ impl AnimationEngine {
         let executed = self.execute_batch_steps(now);
 
         if self.current_step >= self.steps.len() {
-            self.state = AnimationState::Finished;
+            self.finish();
         }
 
         executed
     }
 
     fn update_cursor_blink(&mut self) {
+        if !self.cursor_blink {
+            return;
+        }
         if self.cursor_blink_timer.elapsed() >= Duration::from_millis(500) {
             self.cursor_visible = !self.cursor_visible;
             self.cursor_blink_timer = Instant::now();
@@ -1243,6 +2252,9 @@ impl AnimationEngine {
             for finished_chunk_id in audio_player.poll_finished_chunks() {
                 // Track this chunk as finished
                 self.finished_audio_chunks.insert(finished_chunk_id);
+                self.emit(PlaybackEvent::ChunkEnd {
+                    chunk_id: finished_chunk_id,
+                });
 
                 if self.current_audio_chunk == Some(finished_chunk_id) {
                     self.audio_chunk_finished();
@@ -1306,21 +2318,39 @@ impl AnimationEngine {
                 let variation = rng.random_range(0.7..=1.3);
                 ((self.speed_ms as f64) * variation) as u64
             }
+            AnimationStep::InsertText { text, .. } => {
+                // Same time per character as typing it out, so narration stays in sync
+                let variation = rng.random_range(0.7..=1.3);
+                ((self.speed_ms as f64) * text.chars().count() as f64 * variation) as u64
+            }
             AnimationStep::DialogTypeChar { .. } => {
                 // Dialog typing is slower (2x speed with variation)
                 let variation = rng.random_range(0.7..=1.3);
                 ((self.speed_ms as f64) * 2.0 * variation) as u64
             }
-            AnimationStep::Pause { .. } => {
+            AnimationStep::Pause { .. } | AnimationStep::FileGap => {
                 // Pause timing is driven by `pause_until`; don't add extra delay
                 0
             }
+            AnimationStep::DeleteLine { .. } if self.only_additions => 0,
             _ => {
                 // Other steps use base speed
                 self.speed_ms
             }
         };
 
+        if matches!(
+            step,
+            AnimationStep::InsertChar { .. }
+                | AnimationStep::InsertText { .. }
+                | AnimationStep::TerminalTypeChar { .. }
+        ) && !self.replaying
+        {
+            if let Some(sound) = &mut self.keystroke_sound {
+                sound.click();
+            }
+        }
+
         match step {
             AnimationStep::InsertChar { line, col, ch } => {
                 self.active_pane = ActivePane::Editor;
@@ -1328,7 +2358,21 @@ impl AnimationEngine {
                 self.buffer.cursor_line = line;
                 self.buffer.cursor_col = col + 1;
             }
-            AnimationStep::InsertLine { line, content } => {
+            AnimationStep::InsertText { line, col, text } => {
+                self.active_pane = ActivePane::Editor;
+                let mut cursor = col;
+                for ch in text.chars() {
+                    self.buffer.insert_char(line, cursor, ch);
+                    cursor += 1;
+                }
+                self.buffer.cursor_line = line;
+                self.buffer.cursor_col = cursor;
+            }
+            AnimationStep::InsertLine {
+                line,
+                content,
+                inline_changes,
+            } => {
                 self.active_pane = ActivePane::Editor;
                 let content_len = content.chars().count();
                 self.buffer.insert_line(line, content);
@@ -1337,12 +2381,27 @@ impl AnimationEngine {
 
                 // Mark this line as an addition (for green highlighting)
                 self.buffer.added_lines.insert(line);
+                if !inline_changes.is_empty() {
+                    self.buffer.inline_changes.insert(line, inline_changes);
+                }
 
                 // Track line offset for old_highlights mapping
                 self.line_offset += 1;
             }
+            AnimationStep::MarkDeletion { line } => {
+                self.active_pane = ActivePane::Editor;
+                self.buffer.deleting_line = Some(line);
+                self.buffer.cursor_line = line;
+                self.buffer.cursor_col = self
+                    .buffer
+                    .lines
+                    .get(line)
+                    .map(|l| l.chars().take_while(|c| c.is_whitespace()).count())
+                    .unwrap_or(0);
+            }
             AnimationStep::DeleteLine { line } => {
                 self.active_pane = ActivePane::Editor;
+                self.buffer.deleting_line = None;
                 self.buffer.delete_line(line);
                 self.buffer.cursor_line = line;
                 // Set cursor to first non-whitespace position of the line that moved up
@@ -1367,6 +2426,21 @@ impl AnimationEngine {
                     self.buffer.added_lines.insert(old_line - 1);
                 }
 
+                // Same shift for word-diff ranges
+                self.buffer.inline_changes.remove(&line);
+                let ranges_to_update: Vec<usize> = self
+                    .buffer
+                    .inline_changes
+                    .keys()
+                    .filter(|&&l| l > line)
+                    .copied()
+                    .collect();
+                for old_line in ranges_to_update {
+                    if let Some(ranges) = self.buffer.inline_changes.remove(&old_line) {
+                        self.buffer.inline_changes.insert(old_line - 1, ranges);
+                    }
+                }
+
                 // Track line offset for old_highlights mapping
                 self.line_offset -= 1;
             }
@@ -1375,21 +2449,35 @@ impl AnimationEngine {
                 self.buffer.cursor_line = line;
                 self.buffer.cursor_col = col;
             }
-            AnimationStep::Pause { multiplier } => {
+            AnimationStep::Pause { multiplier, kind } => {
+                // Hunks play top to bottom, so the buffer line is the new file's line
+                let mut multiplier = multiplier;
+                if kind == PauseKind::LineEnd {
+                    if let Some(path) = self.current_file_path.clone() {
+                        let line = self.buffer.cursor_line + 1;
+                        self.emit(PlaybackEvent::LineTyped { path, line });
+                    }
+                    multiplier *= self.line_pause_scale;
+                }
                 let duration_ms = (self.speed_ms as f64 * multiplier) as u64;
                 self.pause_until = Some(Instant::now() + Duration::from_millis(duration_ms));
             }
+            AnimationStep::FileGap => {
+                let duration_ms = self
+                    .file_gap_ms
+                    .unwrap_or((self.speed_ms as f64 * OPEN_FILE_PAUSE) as u64);
+                self.pause_until = Some(Instant::now() + Duration::from_millis(duration_ms));
+            }
             AnimationStep::StartAudio { chunk_id } => {
                 // Start playing this audio chunk (non-blocking - animation continues)
-                if let Some(audio_player) = &self.audio_player {
-                    audio_player.trigger_chunk(chunk_id);
-                }
+                self.start_audio_chunk(chunk_id);
                 // Don't set current_audio_chunk yet - we're not waiting
                 self.next_step_delay = 0; // No delay, continue immediately
             }
             AnimationStep::WaitForAudio { chunk_id } => {
-                // Check if this chunk already finished (audio was faster than animation)
-                if self.finished_audio_chunks.contains(&chunk_id) {
+                // Check if this chunk already finished (audio was faster than animation),
+                // or was never started because narration is muted
+                if self.muted || self.finished_audio_chunks.contains(&chunk_id) {
                     self.next_step_delay = 0;
                     return; // Don't wait, continue immediately
                 }
@@ -1424,11 +2512,7 @@ impl AnimationEngine {
                 self.current_file_path = Some(path.clone());
                 self.buffer = EditorBuffer::from_content(&old_content);
 
-                // Trigger voiceover for file open
-                if let Some(audio_player) = &self.audio_player {
-                    audio_player
-                        .trigger_voiceover(crate::audio::VoiceoverTrigger::FileOpen(path.clone()));
-                }
+                self.emit(PlaybackEvent::FileOpen { path: path.clone() });
 
                 // Update typing speed based on file-specific rules
                 self.speed_ms = self.get_speed_for_file(&path);
@@ -1437,10 +2521,6 @@ impl AnimationEngine {
                 // This will clear language settings if not supported
                 self.highlighter.borrow_mut().set_language_from_path(&path);
 
-                // Pre-calculate highlights for both old and new content
-                self.buffer.old_highlights = self.highlighter.borrow_mut().highlight(&old_content);
-                self.buffer.new_highlights = self.highlighter.borrow_mut().highlight(&new_content);
-
                 // Store content lines for byte offset calculation
                 self.buffer.old_content_lines = if old_content.is_empty() {
                     vec![String::new()]
@@ -1457,8 +2537,14 @@ impl AnimationEngine {
                 self.buffer.old_content_line_offsets = Self::calculate_line_offsets(&old_content);
                 self.buffer.new_content_line_offsets = Self::calculate_line_offsets(&new_content);
 
-                // Initialize cached_highlights with old_highlights
-                self.buffer.cached_highlights = self.buffer.old_highlights.clone();
+                // Pre-calculate highlights for both old and new content, grouped by
+                // line so rendering doesn't rescan the file every frame
+                let old_spans = self.highlighter.borrow_mut().highlight(&old_content);
+                let new_spans = self.highlighter.borrow_mut().highlight(&new_content);
+                self.buffer.old_highlights =
+                    LineHighlights::new(&old_spans, &self.buffer.old_content_line_offsets);
+                self.buffer.new_highlights =
+                    LineHighlights::new(&new_spans, &self.buffer.new_content_line_offsets);
 
                 // Reset line offset
                 self.line_offset = 0;
@@ -1495,23 +2581,19 @@ impl AnimationEngine {
 
         self.handle_step_checkpoint(&step_clone);
 
-        // Update scroll to keep cursor centered
-        self.update_scroll();
+        // Update scroll to keep cursor centered (once at the end when seeking)
+        if !self.replaying {
+            self.update_scroll();
+        }
     }
 
-    fn calculate_line_display_height(&self, line: &str) -> usize {
-        if self.content_width == 0 {
+    fn calculate_line_display_height(&self, line: &str, line_number_width: Option<usize>) -> usize {
+        if !self.wrap || self.content_width == 0 {
             return 1;
         }
 
-        // Calculate text area width (excluding line numbers, padding, etc.)
-        let line_num_width = format!("{}", self.buffer.lines.len()).len().max(3);
-        let left_padding = 2;
-        let line_num_and_space = line_num_width + 1;
-        let separator = 2;
-        let right_padding = 2;
-        let fixed_width = left_padding + line_num_and_space + separator + right_padding;
-
+        // Wrapped rows continue past the gutter, with no padding on the right
+        let fixed_width = EDITOR_PADDING + gutter_width(line_number_width);
         let text_width = self.content_width.saturating_sub(fixed_width);
         if text_width == 0 {
             return 1;
@@ -1527,6 +2609,20 @@ impl AnimationEngine {
             return;
         }
 
+        // The gutter may widen at the new position (e.g. line 999 to 1000), which
+        // changes how lines wrap, so settle the offset once more in that case
+        let current = self.line_number_width(self.buffer.scroll_offset);
+        let mut offset = self.scroll_target(current);
+        let settled = self.line_number_width(offset);
+        if settled != current {
+            offset = self.scroll_target(settled);
+        }
+        self.buffer.scroll_offset = offset;
+    }
+
+    /// First line to show so the cursor sits mid-viewport, with lines wrapped
+    /// next to a gutter of `line_number_width` digits
+    fn scroll_target(&self, line_number_width: Option<usize>) -> usize {
         let cursor_line = self.buffer.cursor_line;
 
         // Calculate display line positions for each logical line
@@ -1535,7 +2631,7 @@ impl AnimationEngine {
 
         for line in &self.buffer.lines {
             display_line_positions.push(current_display_line);
-            current_display_line += self.calculate_line_display_height(line);
+            current_display_line += self.calculate_line_display_height(line, line_number_width);
         }
 
         let total_display_lines = current_display_line;
@@ -1563,7 +2659,7 @@ impl AnimationEngine {
             }
         }
 
-        self.buffer.scroll_offset = logical_offset;
+        logical_offset
     }
 
     /// Returns true if the animation has completed.
@@ -1587,6 +2683,7 @@ mod tests {
             content: content.to_string(),
             old_line_no: Some(1),
             new_line_no: Some(1),
+            inline_changes: Vec::new(),
         }
     }
 
@@ -1612,6 +2709,7 @@ mod tests {
             new_content: Some("fn demo() {}\n".to_string()),
             hunks: vec![make_hunk()],
             diff: String::new(),
+            language: Some("Rust"),
         }
     }
 
@@ -1645,6 +2743,170 @@ mod tests {
         }
     }
 
+    #[test]
+    fn change_boundaries_follow_pause_kind_even_when_pauses_match() {
+        let mut engine = AnimationEngine::new(1);
+        // Tuned so a typed line pauses exactly as long as a hunk boundary
+        let pauses = EditPauses {
+            insert_line: 50.0,
+            hunk: 50.0,
+            ..EditPauses::default()
+        };
+        engine.set_pauses(pauses);
+        let mut file = make_file("src/a.rs");
+        file.hunks[0].lines = vec![
+            make_line(LineChangeType::Addition, "fn one() {}\n"),
+            make_line(LineChangeType::Addition, "fn two() {}\n"),
+        ];
+        engine.load_commit(&make_commit(vec![file]));
+
+        let change_pauses = engine
+            .steps
+            .iter()
+            .filter(|s| matches!(s, AnimationStep::Pause { .. }))
+            .filter(|s| engine.is_boundary_step(s, StepMode::Change))
+            .count();
+        let line_end_pauses = engine
+            .steps
+            .iter()
+            .filter(|s| {
+                matches!(
+                    s,
+                    AnimationStep::Pause {
+                        kind: PauseKind::LineEnd,
+                        ..
+                    }
+                )
+            })
+            .count();
+        assert_eq!(change_pauses, 1);
+        assert_eq!(line_end_pauses, 2);
+    }
+
+    #[test]
+    fn show_deletions_strikes_through_line_before_removing_it() {
+        let mut engine = AnimationEngine::new(1);
+        engine.set_show_deletions(true);
+        let mut file = make_file("src/a.rs");
+        file.new_content = Some(String::new());
+        file.hunks[0].lines = vec![make_line(LineChangeType::Deletion, "fn demo() {}\n")];
+        engine.load_commit(&make_commit(vec![file]));
+
+        let mark = engine
+            .steps
+            .iter()
+            .position(|s| matches!(s, AnimationStep::MarkDeletion { line: 0 }))
+            .expect("expected a MarkDeletion step");
+        assert!(matches!(
+            engine.steps.get(mark + 2),
+            Some(AnimationStep::DeleteLine { line: 0 })
+        ));
+
+        engine.buffer = EditorBuffer::from_content("fn demo() {}");
+        engine.execute_step(AnimationStep::MarkDeletion { line: 0 });
+        assert_eq!(engine.buffer.deleting_line, Some(0));
+        engine.execute_step(AnimationStep::DeleteLine { line: 0 });
+        assert_eq!(engine.buffer.deleting_line, None);
+    }
+
+    #[test]
+    fn only_additions_removes_deleted_lines_without_pausing() {
+        let mut engine = AnimationEngine::new(1);
+        engine.set_only_additions(true);
+        let mut file = make_file("src/a.rs");
+        file.new_content = Some("fn added() {}\n".to_string());
+        file.hunks[0].lines = vec![
+            make_line(LineChangeType::Deletion, "fn demo() {}\n"),
+            make_line(LineChangeType::Addition, "fn added() {}"),
+        ];
+        engine.load_commit(&make_commit(vec![file]));
+
+        let delete = engine
+            .steps
+            .iter()
+            .position(|s| matches!(s, AnimationStep::DeleteLine { line: 0 }))
+            .expect("expected a DeleteLine step");
+        assert!(matches!(
+            engine.steps.get(delete + 1),
+            Some(AnimationStep::InsertLine { line: 0, .. })
+        ));
+        assert!(!engine
+            .steps
+            .iter()
+            .any(|s| matches!(s, AnimationStep::MarkDeletion { .. })));
+
+        engine.buffer = EditorBuffer::from_content("fn demo() {}");
+        engine.execute_step(AnimationStep::DeleteLine { line: 0 });
+        assert_eq!(engine.next_step_delay, 0);
+    }
+
+    #[test]
+    fn collapsed_files_are_summarized_until_expanded() {
+        let mut engine = AnimationEngine::new(1);
+        engine.set_collapse_threshold(Some(1));
+        let mut file = make_file("src/a.rs");
+        file.new_content = Some("fn added() {}\nfn more() {}\n".to_string());
+        file.hunks[0].lines = vec![
+            make_line(LineChangeType::Addition, "fn added() {}"),
+            make_line(LineChangeType::Addition, "fn more() {}"),
+        ];
+        engine.load_commit(&make_commit(vec![file]));
+
+        let types_lines = |engine: &AnimationEngine| {
+            engine
+                .steps
+                .iter()
+                .any(|s| matches!(s, AnimationStep::InsertLine { .. }))
+        };
+        assert!(!types_lines(&engine));
+        assert!(engine.steps.iter().any(|s| matches!(
+            s,
+            AnimationStep::TerminalOutput { text } if text.contains("+2 −0 (collapsed")
+        )));
+
+        // Nothing to expand until the file is open
+        assert!(!engine.expand_current_file());
+        assert!(engine.seek_to_file(0));
+        assert!(engine.expand_current_file());
+        assert!(types_lines(&engine));
+        assert_eq!(engine.current_file_index, 0);
+        assert!(!engine.expand_current_file());
+    }
+
+    #[test]
+    fn stat_mode_shows_one_summary_line_per_file() {
+        let mut engine = AnimationEngine::new(1);
+        engine.set_stat_mode(true);
+        let mut file = make_file("src/a.rs");
+        file.hunks[0].lines = vec![
+            make_line(LineChangeType::Deletion, "fn old() {}"),
+            make_line(LineChangeType::Addition, "fn added() {}"),
+            make_line(LineChangeType::Addition, "fn more() {}"),
+        ];
+        engine.load_commit(&make_commit(vec![file]));
+
+        let inserted: Vec<&str> = engine
+            .steps
+            .iter()
+            .filter_map(|s| match s {
+                AnimationStep::InsertLine { content, .. } => Some(content.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(inserted, ["src/a.rs | +2 −1"]);
+        assert!(!engine.steps.iter().any(|s| matches!(
+            s,
+            AnimationStep::InsertChar { .. } | AnimationStep::DeleteLine { .. }
+        )));
+
+        assert!(engine.seek_to_file(0));
+        assert!(engine.expand_current_file());
+        assert!(engine
+            .steps
+            .iter()
+            .any(|s| matches!(s, AnimationStep::DeleteLine { .. })));
+    }
+
     #[test]
     fn wait_step_unblocks_immediately_when_chunk_already_finished() {
         let mut engine = AnimationEngine::new(1);
@@ -1710,6 +2972,186 @@ mod tests {
         );
     }
 
+    #[test]
+    fn commit_intro_plays_before_files_and_outro_after() {
+        let mut engine = AnimationEngine::new(1);
+        let player = Arc::new(
+            crate::audio::AudioPlayer::new(VoiceoverConfig::default())
+                .expect("audio player should initialize when disabled"),
+        );
+        set_chunks(
+            &player,
+            vec![
+                make_chunk(0, COMMIT_INTRO_PATH, vec![]),
+                make_chunk(1, "src/a.rs", vec![0]),
+                make_chunk(2, COMMIT_OUTRO_PATH, vec![]),
+            ],
+        );
+        engine.set_audio_player(player);
+
+        let commit = make_commit(vec![make_file("src/a.rs")]);
+        engine.load_commit(&commit);
+
+        let position = |id: usize| {
+            engine
+                .steps
+                .iter()
+                .position(
+                    |s| matches!(s, AnimationStep::StartAudio { chunk_id } if *chunk_id == id),
+                )
+                .expect("expected a StartAudio step")
+        };
+        let reset = engine
+            .steps
+            .iter()
+            .position(|s| matches!(s, AnimationStep::ResetState))
+            .expect("expected a ResetState step");
+        let switch = engine
+            .steps
+            .iter()
+            .position(|s| matches!(s, AnimationStep::SwitchFile { .. }))
+            .expect("expected a SwitchFile step");
+        assert_eq!(position(0), reset + 1);
+        assert!(position(0) < switch && switch < position(1));
+        assert!(position(1) < position(2));
+    }
+
+    #[test]
+    fn file_intro_finishes_before_the_files_hunks_are_narrated() {
+        let mut engine = AnimationEngine::new(1);
+        let player = Arc::new(
+            crate::audio::AudioPlayer::new(VoiceoverConfig::default())
+                .expect("audio player should initialize when disabled"),
+        );
+        set_chunks(
+            &player,
+            vec![
+                make_chunk(0, "src/a.rs", vec![]),
+                make_chunk(1, "src/a.rs", vec![0]),
+            ],
+        );
+        engine.set_audio_player(player);
+
+        let commit = make_commit(vec![make_file("src/a.rs")]);
+        engine.load_commit(&commit);
+
+        let audio: Vec<&AnimationStep> = engine
+            .steps
+            .iter()
+            .filter(|s| {
+                matches!(
+                    s,
+                    AnimationStep::StartAudio { .. } | AnimationStep::WaitForAudio { .. }
+                )
+            })
+            .collect();
+        assert!(matches!(
+            audio[..],
+            [
+                AnimationStep::StartAudio { chunk_id: 0 },
+                AnimationStep::WaitForAudio { chunk_id: 0 },
+                AnimationStep::StartAudio { chunk_id: 1 },
+                AnimationStep::WaitForAudio { chunk_id: 1 },
+            ]
+        ));
+        let switch = engine
+            .steps
+            .iter()
+            .position(|s| matches!(s, AnimationStep::SwitchFile { .. }))
+            .expect("expected a SwitchFile step");
+        let intro = engine
+            .steps
+            .iter()
+            .position(|s| matches!(s, AnimationStep::StartAudio { chunk_id: 0 }))
+            .expect("expected the intro to start");
+        assert!(switch < intro);
+    }
+
+    #[test]
+    fn refresh_narration_only_rebuilds_files_not_opened_yet() {
+        let mut engine = AnimationEngine::new(1);
+        let player = Arc::new(
+            crate::audio::AudioPlayer::new(VoiceoverConfig::default())
+                .expect("audio player should initialize when disabled"),
+        );
+        engine.set_audio_player(Arc::clone(&player));
+
+        let commit = make_commit(vec![make_file("src/a.rs"), make_file("src/b.rs")]);
+        engine.load_commit(&commit);
+        let first_switch = engine
+            .steps
+            .iter()
+            .position(|s| matches!(s, AnimationStep::SwitchFile { file_index: 0, .. }))
+            .expect("expected a SwitchFile step for the first file");
+        engine.current_step = first_switch + 1;
+        let played = engine.steps[..engine.current_step].to_vec();
+
+        // Narration for both files arrives after the first one was opened
+        set_chunks(
+            &player,
+            vec![
+                make_chunk(0, "src/a.rs", vec![0]),
+                make_chunk(1, "src/b.rs", vec![0]),
+            ],
+        );
+        assert!(engine.refresh_narration());
+
+        let starts = |id: usize| {
+            engine
+                .steps
+                .iter()
+                .any(|s| matches!(s, AnimationStep::StartAudio { chunk_id } if *chunk_id == id))
+        };
+        assert!(!starts(0));
+        assert!(starts(1));
+        assert_eq!(
+            format!("{:?}", &engine.steps[..played.len()]),
+            format!("{:?}", played)
+        );
+    }
+
+    #[test]
+    fn refresh_narration_reaches_a_file_until_its_switch_runs() {
+        let mut engine = AnimationEngine::new(1);
+        let player = Arc::new(
+            crate::audio::AudioPlayer::new(VoiceoverConfig::default())
+                .expect("audio player should initialize when disabled"),
+        );
+        engine.set_audio_player(Arc::clone(&player));
+
+        let commit = make_commit(vec![make_file("src/a.rs"), make_file("src/b.rs")]);
+        engine.load_commit(&commit);
+        let switch = |engine: &AnimationEngine, index: usize| {
+            engine
+                .steps
+                .iter()
+                .position(|s| matches!(s, AnimationStep::SwitchFile { file_index, .. } if *file_index == index))
+                .expect("expected a SwitchFile step for the file")
+        };
+        // Playback is inside the first file's section, before it is opened
+        engine.current_step = switch(&engine, 0);
+        let played = engine.steps[..engine.current_step].to_vec();
+        let second_file = engine.steps[switch(&engine, 1)..].to_vec();
+
+        assert!(!engine.refresh_narration());
+
+        set_chunks(&player, vec![make_chunk(0, "src/a.rs", vec![0])]);
+        assert!(engine.refresh_narration());
+        assert!(engine
+            .steps
+            .iter()
+            .any(|s| matches!(s, AnimationStep::StartAudio { chunk_id: 0 })));
+        assert_eq!(
+            format!("{:?}", &engine.steps[..played.len()]),
+            format!("{:?}", played)
+        );
+        // The second file's narration didn't change, so its steps are untouched
+        assert_eq!(
+            format!("{:?}", &engine.steps[switch(&engine, 1)..]),
+            format!("{:?}", second_file)
+        );
+    }
+
     #[test]
     fn audio_steps_are_scoped_to_the_current_file() {
         let mut engine = AnimationEngine::new(1);
@@ -1760,6 +3202,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn files_play_in_the_chosen_order() {
+        let mut big = make_file("z.rs");
+        big.hunks[0].lines = vec![
+            make_line(LineChangeType::Addition, "a"),
+            make_line(LineChangeType::Deletion, "b"),
+        ];
+        let commit = make_commit(vec![make_file("src/b.rs"), big, make_file("src/a.rs")]);
+        assert_eq!(file_play_order(&commit, FileOrder::Flow), vec![1, 2, 0]);
+        assert_eq!(
+            file_play_order(&commit, FileOrder::ReverseFlow),
+            vec![0, 2, 1]
+        );
+        assert_eq!(file_play_order(&commit, FileOrder::Alpha), vec![2, 0, 1]);
+        assert_eq!(file_play_order(&commit, FileOrder::Size), vec![1, 2, 0]);
+
+        // With narration, flow follows the narrated order; unnarrated files go last
+        let mut engine = AnimationEngine::new(1);
+        let player = Arc::new(
+            crate::audio::AudioPlayer::new(VoiceoverConfig::default())
+                .expect("audio player should initialize when disabled"),
+        );
+        set_chunks(
+            &player,
+            vec![
+                make_chunk(0, "src/b.rs", vec![0]),
+                make_chunk(1, "src/a.rs", vec![0]),
+            ],
+        );
+        engine.set_audio_player(player);
+        engine.load_commit(&commit);
+        let opened: Vec<&str> = engine
+            .steps
+            .iter()
+            .filter_map(|s| match s {
+                AnimationStep::SwitchFile { path, .. } => Some(path.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(opened, ["src/b.rs", "src/a.rs", "z.rs"]);
+    }
+
     #[test]
     fn file_without_matching_chunks_emits_no_audio_steps() {
         let mut engine = AnimationEngine::new(1);
@@ -1779,10 +3263,10 @@ mod tests {
         for step in &engine.steps {
             match step {
                 AnimationStep::SwitchFile { path, .. } => current_file = Some(path.clone()),
-                AnimationStep::StartAudio { .. } | AnimationStep::WaitForAudio { .. } => {
-                    if current_file.as_deref() == Some("src/no-audio.rs") {
-                        no_audio_file_step_count += 1;
-                    }
+                AnimationStep::StartAudio { .. } | AnimationStep::WaitForAudio { .. }
+                    if current_file.as_deref() == Some("src/no-audio.rs") =>
+                {
+                    no_audio_file_step_count += 1;
                 }
                 _ => {}
             }
@@ -1790,4 +3274,305 @@ mod tests {
 
         assert_eq!(no_audio_file_step_count, 0);
     }
+
+    #[test]
+    fn seeking_rebuilds_the_same_state_as_playing_through() {
+        let mut file = make_file("src/a.rs");
+        file.new_content = Some("fn demo() {}\nfn added() {}\n".to_string());
+        file.hunks[0]
+            .lines
+            .push(make_line(LineChangeType::Addition, "fn added() {}"));
+        let commit = make_commit(vec![file]);
+
+        let mut played = AnimationEngine::new(1);
+        played.load_commit(&commit);
+        while played.manual_step(StepMode::Change) {}
+
+        let mut sought = AnimationEngine::new(1);
+        sought.load_commit(&commit);
+        assert!(sought.seek_to_fraction(0.5));
+        assert!(sought.seek_to_fraction(0.1));
+        assert!(sought.seek_to_fraction(1.0));
+
+        assert!(sought.is_finished());
+        assert_eq!(sought.progress(), 1.0);
+        assert_eq!(sought.buffer.lines, played.buffer.lines);
+        assert_eq!(sought.terminal_lines, played.terminal_lines);
+        assert_eq!(sought.current_file_path.as_deref(), Some("src/a.rs"));
+        assert_eq!(
+            sought.current_metadata().map(|m| m.hash.as_str()),
+            Some("1234567890abcdef")
+        );
+    }
+
+    #[test]
+    fn playing_a_commit_records_its_events_in_order() {
+        let mut file = make_file("src/a.rs");
+        file.new_content = Some("fn demo() {}\nfn added() {}\n".to_string());
+        file.hunks[0].new_lines = 2;
+        file.hunks[0]
+            .lines
+            .push(make_line(LineChangeType::Addition, "fn added() {}"));
+        let mut engine = AnimationEngine::new(1);
+        engine.set_record_events(true);
+        engine.load_commit(&make_commit(vec![file]));
+        for _ in 0..1000 {
+            if engine.is_finished() {
+                break;
+            }
+            engine.manual_step(StepMode::Line);
+        }
+
+        let commit = "1234567890abcdef".to_string();
+        assert_eq!(
+            engine.take_events(),
+            vec![
+                PlaybackEvent::CommitStart {
+                    commit: commit.clone()
+                },
+                PlaybackEvent::FileOpen {
+                    path: "src/a.rs".to_string()
+                },
+                PlaybackEvent::LineTyped {
+                    path: "src/a.rs".to_string(),
+                    line: 2
+                },
+                PlaybackEvent::CommitEnd { commit },
+            ]
+        );
+    }
+
+    #[test]
+    fn seeking_to_a_file_opens_it() {
+        let commit = make_commit(vec![make_file("src/a.rs"), make_file("src/b.rs")]);
+        let mut engine = AnimationEngine::new(1);
+        engine.load_commit(&commit);
+
+        assert!(engine.seek_to_file(1));
+        assert_eq!(engine.current_file_index, 1);
+        assert_eq!(engine.current_file_path.as_deref(), Some("src/b.rs"));
+        assert!(!engine.seek_to_file(5));
+    }
+
+    #[test]
+    fn skipping_files_follows_playback_order() {
+        let commit = make_commit(vec![make_file("src/b.rs"), make_file("src/a.rs")]);
+        let mut engine = AnimationEngine::new(1);
+        engine.load_commit(&commit);
+
+        // From the intro, the first file played is next and nothing is before it
+        assert!(!engine.skip_file(false));
+        assert!(engine.skip_file(true));
+        assert_eq!(engine.current_file_path.as_deref(), Some("src/a.rs"));
+        assert!(engine.skip_file(true));
+        assert_eq!(engine.current_file_path.as_deref(), Some("src/b.rs"));
+        assert!(!engine.skip_file(true));
+        assert!(engine.skip_file(false));
+        assert_eq!(engine.current_file_index, 1);
+    }
+
+    #[test]
+    fn seeking_into_a_chunk_window_restarts_its_audio() {
+        let mut engine = AnimationEngine::new(1);
+        let player = Arc::new(
+            crate::audio::AudioPlayer::new(VoiceoverConfig::default())
+                .expect("audio player should initialize when disabled"),
+        );
+        set_chunks(&player, vec![make_chunk(42, "src/a.rs", vec![0])]);
+        engine.set_audio_player(player);
+        engine.load_commit(&make_commit(vec![make_file("src/a.rs")]));
+
+        let start = engine
+            .steps
+            .iter()
+            .position(|s| matches!(s, AnimationStep::StartAudio { .. }))
+            .expect("expected a StartAudio step");
+        let fraction = (start + 1) as f32 / engine.steps.len() as f32;
+
+        assert!(engine.seek_to_fraction(fraction));
+        assert_eq!(engine.take_started_audio_chunks(), vec![42]);
+
+        // Before the window: nothing restarts
+        assert!(engine.seek_to_fraction(0.0));
+        assert!(engine.take_started_audio_chunks().is_empty());
+    }
+
+    #[test]
+    fn replaying_restarts_only_the_chunk_covering_the_position() {
+        let mut engine = AnimationEngine::new(1);
+        let player = Arc::new(
+            crate::audio::AudioPlayer::new(VoiceoverConfig::default())
+                .expect("audio player should initialize when disabled"),
+        );
+        set_chunks(&player, vec![make_chunk(42, "src/a.rs", vec![0])]);
+        engine.set_audio_player(player);
+        engine.load_commit(&make_commit(vec![make_file("src/a.rs")]));
+        assert!(!engine.replay_audio_chunk());
+
+        let start = engine
+            .steps
+            .iter()
+            .position(|s| matches!(s, AnimationStep::StartAudio { .. }))
+            .expect("expected a StartAudio step");
+        let fraction = (start + 1) as f32 / engine.steps.len() as f32;
+        assert!(engine.seek_to_fraction(fraction));
+        engine.take_started_audio_chunks();
+
+        assert_eq!(engine.active_audio_chunk(), Some(42));
+        assert!(engine.replay_audio_chunk());
+        assert_eq!(engine.take_started_audio_chunks(), vec![42]);
+    }
+
+    #[test]
+    fn wrapped_line_height_leaves_out_the_gutter_only_when_shown() {
+        let mut engine = AnimationEngine::new(1);
+        engine.set_content_width(20);
+        engine.buffer = EditorBuffer::from_content(&"x".repeat(18));
+        let line = engine.buffer.lines[0].clone();
+
+        assert_eq!(engine.line_number_width(0), Some(3));
+        assert_eq!(gutter_width(engine.line_number_width(0)), 6);
+        assert_eq!(
+            engine.calculate_line_display_height(&line, engine.line_number_width(0)),
+            2
+        );
+
+        engine.set_line_numbers(false);
+        assert_eq!(engine.line_number_width(0), None);
+        assert_eq!(
+            engine.calculate_line_display_height(&line, engine.line_number_width(0)),
+            1
+        );
+    }
+
+    #[test]
+    fn editor_window_counts_wrapped_rows_toward_the_viewport() {
+        let mut engine = AnimationEngine::new(1);
+        engine.set_viewport_height(4);
+        engine.set_content_width(20);
+        engine.set_line_numbers(false);
+        let long = "x".repeat(40);
+        engine.buffer = EditorBuffer::from_content(&format!("a\n{}\nb\nc\nd", long));
+
+        // 18 columns of text: the long line takes three rows
+        let window = engine.editor_window();
+        assert_eq!((window.first_line, window.end_line), (0, 2));
+        assert!(window.wrap);
+
+        engine.set_wrap(false);
+        let window = engine.editor_window();
+        assert_eq!((window.first_line, window.end_line), (0, 4));
+    }
+
+    #[test]
+    fn paging_up_past_wrapped_lines_shows_the_scrolled_window() {
+        let content: String = (0..40)
+            .map(|i| format!("{} {}\n", i, "x".repeat(30)))
+            .collect();
+        let mut engine = AnimationEngine::new(1);
+        engine.set_viewport_height(10);
+        engine.set_content_width(30);
+        engine.buffer = EditorBuffer::from_content(&content);
+        engine.buffer.cursor_line = 35;
+        engine.update_scroll();
+        let following = engine.editor_window();
+        assert!((following.first_line..following.end_line).contains(&35));
+
+        // The window stays where the user paged to, even with the cursor below it
+        engine.pause();
+        engine.scroll_page(false);
+        let paged = engine.editor_window();
+        assert_eq!(paged.first_line, following.first_line - 9);
+        assert!(paged.end_line <= 35);
+
+        // Paging down stops at the last screenful of wrapped rows
+        for _ in 0..10 {
+            engine.scroll_page(true);
+        }
+        assert_eq!(engine.editor_window().end_line, 40);
+        assert_eq!(engine.buffer.scroll_offset, 35);
+    }
+
+    #[test]
+    fn paging_while_paused_snaps_back_to_the_cursor_on_resume() {
+        let content: String = (0..100).map(|i| format!("line {}\n", i)).collect();
+        let mut engine = AnimationEngine::new(1);
+        engine.set_viewport_height(20);
+        engine.buffer = EditorBuffer::from_content(&content);
+        engine.buffer.cursor_line = 80;
+        engine.update_scroll();
+        let following = engine.buffer.scroll_offset;
+
+        engine.pause();
+        engine.scroll_page(false);
+        assert_eq!(engine.buffer.scroll_offset, following - 19);
+        engine.scroll_page(true);
+        engine.scroll_page(true);
+        assert_eq!(engine.buffer.scroll_offset, 80);
+
+        engine.resume();
+        assert_eq!(engine.buffer.scroll_offset, following);
+    }
+
+    #[test]
+    fn muted_narration_is_neither_started_nor_waited_on() {
+        let mut engine = AnimationEngine::new(1);
+        let player = Arc::new(
+            crate::audio::AudioPlayer::new(VoiceoverConfig::default())
+                .expect("audio player should initialize when disabled"),
+        );
+        set_chunks(&player, vec![make_chunk(42, "src/a.rs", vec![0])]);
+        engine.set_audio_player(player);
+        engine.load_commit(&make_commit(vec![make_file("src/a.rs")]));
+        let start = engine
+            .steps
+            .iter()
+            .position(|s| matches!(s, AnimationStep::StartAudio { .. }))
+            .expect("expected a StartAudio step");
+        let fraction = (start + 1) as f32 / engine.steps.len() as f32;
+
+        assert!(engine.toggle_mute());
+        assert!(engine.seek_to_fraction(fraction));
+        assert!(engine.take_started_audio_chunks().is_empty());
+        engine.execute_step(AnimationStep::WaitForAudio { chunk_id: 42 });
+        assert_eq!(engine.current_audio_chunk, None);
+
+        assert!(!engine.toggle_mute());
+        assert!(engine.seek_to_fraction(fraction));
+        assert_eq!(engine.take_started_audio_chunks(), vec![42]);
+    }
+
+    #[test]
+    fn live_speed_changes_apply_to_rules_and_stay_clamped() {
+        let mut engine = AnimationEngine::new(40);
+        engine.set_speed_rules(vec![SpeedRule::parse("*.md:10").unwrap()]);
+        assert_eq!(engine.adjust_speed(true), 20);
+        assert_eq!(engine.get_speed_for_file("README.md"), 5);
+
+        for _ in 0..10 {
+            engine.adjust_speed(false);
+        }
+        assert_eq!(engine.current_speed(), 320);
+        assert_eq!(engine.get_speed_for_file("src/a.rs"), 320);
+    }
+
+    #[test]
+    fn token_typing_keeps_highlighted_spans_whole() {
+        let content = "fn main() {\n    let greeting = \"hello world\";\n}\n";
+        let tokens = TokenMap::new("src/main.rs", content);
+        let line = "    let greeting = \"hello world\";";
+        let spans = tokens.line_spans(2, line);
+        let split = split_tokens(line, 4, &spans);
+        assert_eq!(split.concat(), line[4..]);
+        assert!(split.contains(&"let".to_string()));
+        assert!(split.contains(&"\"hello world\"".to_string()));
+
+        // Without highlighting, words, spaces and symbols split apart
+        assert_eq!(
+            split_tokens("  a_b(c, 12)", 2, &[]),
+            vec!["a_b", "(", "c", ",", " ", "12", ")"]
+        );
+        // Content that doesn't match the line falls back to no spans
+        assert!(tokens.line_spans(2, "something else").is_empty());
+    }
 }
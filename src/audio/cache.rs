@@ -0,0 +1,251 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::llm::AnimationTiming;
+use super::types::{DiffChunk, TtsFormat, VoiceoverConfig, VoiceoverProvider};
+use crate::cli::FileOrder;
+use crate::config::EditPauses;
+
+/// Directory holding cached chunks for a commit under `config::cache_dir()`, e.g.
+/// `<cache_dir>/<commit_hash>/<provider>-<voice>-<key_hash>/`, where the key hash
+/// covers every setting that changes the generated narration, audio or chunk timing.
+/// Returns None for the working tree, whose content isn't pinned to a hash.
+pub fn chunk_dir(
+    config: &VoiceoverConfig,
+    commit_hash: &str,
+    timing: AnimationTiming,
+    file_order: FileOrder,
+) -> Option<PathBuf> {
+    if commit_hash.is_empty() || commit_hash == "working-tree" {
+        return None;
+    }
+
+    // Piper voices are picked by model file rather than voice id
+    let voice = match config.provider {
+        VoiceoverProvider::Piper => config
+            .piper_model
+            .as_deref()
+            .and_then(|model| Path::new(model).file_stem()?.to_str()),
+        _ => config.voice_id.as_deref(),
+    };
+    let key = CacheKey {
+        provider: config.provider.as_str(),
+        voice,
+        model_id: config.model_id.as_deref(),
+        narration_speed: config.narration_speed,
+        speech_wpm: config.speech_wpm,
+        tts_format: config.tts_format,
+        tts_sample_rate: config.tts_sample_rate,
+        llm_base_url: config.llm_base_url.as_deref(),
+        llm_model: config.llm_model.as_deref(),
+        ordering_model: config.ordering_model.as_deref(),
+        grouping_model: config.grouping_model.as_deref(),
+        narration_model: config.narration_model.as_deref(),
+        ordering_temperature: config.ordering_temperature,
+        grouping_temperature: config.grouping_temperature,
+        narration_temperature: config.narration_temperature,
+        use_llm_explanations: config.use_llm_explanations,
+        narration_prompt: config.narration_prompt.as_deref(),
+        narrate_author: config.narrate_author,
+        commit_intro: config.commit_intro,
+        file_intros: config.file_intros,
+        language: config.foreign_language(),
+        max_files: config.max_files,
+        speed_ms: timing.speed_ms,
+        show_deletions: timing.show_deletions,
+        only_additions: timing.only_additions,
+        collapse_threshold: timing.collapse_threshold,
+        line_pause_scale: timing.line_pause_scale,
+        pauses: timing.pauses,
+        // Chunk ids follow narration order, which playback follows in turn
+        file_order: match file_order {
+            FileOrder::Flow => "flow",
+            FileOrder::Alpha => "alpha",
+            FileOrder::ReverseFlow => "reverse",
+            FileOrder::Size => "size",
+        },
+    };
+    let json = serde_json::to_vec(&key).ok()?;
+    let name = format!(
+        "{}-{}-{:016x}",
+        config.provider.as_str(),
+        path_safe(voice.unwrap_or("default")),
+        crate::config::stable_hash(&json)
+    );
+    crate::config::cache_dir().map(|dir| dir.join(commit_hash).join(name))
+}
+
+/// Everything that shapes cached chunks, hashed into the cache directory name.
+#[derive(Serialize)]
+struct CacheKey<'a> {
+    provider: &'a str,
+    voice: Option<&'a str>,
+    model_id: Option<&'a str>,
+    narration_speed: f32,
+    speech_wpm: Option<f32>,
+    tts_format: Option<TtsFormat>,
+    tts_sample_rate: Option<u32>,
+    llm_base_url: Option<&'a str>,
+    llm_model: Option<&'a str>,
+    ordering_model: Option<&'a str>,
+    grouping_model: Option<&'a str>,
+    narration_model: Option<&'a str>,
+    ordering_temperature: Option<f32>,
+    grouping_temperature: Option<f32>,
+    narration_temperature: Option<f32>,
+    use_llm_explanations: bool,
+    narration_prompt: Option<&'a str>,
+    narrate_author: bool,
+    commit_intro: bool,
+    file_intros: bool,
+    language: Option<String>,
+    max_files: Option<usize>,
+    speed_ms: u64,
+    show_deletions: bool,
+    only_additions: bool,
+    collapse_threshold: Option<usize>,
+    line_pause_scale: f64,
+    pauses: EditPauses,
+    file_order: &'static str,
+}
+
+fn path_safe(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Load previously generated chunks, ordered by chunk id.
+/// Returns None when nothing usable is cached.
+pub fn load(dir: &Path) -> Option<Vec<DiffChunk>> {
+    let mut chunks = Vec::new();
+
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+
+        let json = fs::read_to_string(&path).ok()?;
+        let mut chunk: DiffChunk = serde_json::from_str(&json).ok()?;
+        if chunk.has_audio {
+            chunk.audio_data = Some(fs::read(path.with_extension("mp3")).ok()?);
+        }
+        chunks.push(chunk);
+    }
+
+    if chunks.is_empty() {
+        return None;
+    }
+
+    chunks.sort_by_key(|c| c.chunk_id);
+    Some(chunks)
+}
+
+/// Persist chunks as `<chunk_id>.json` (explanation + timing) and `<chunk_id>.mp3` (audio).
+pub fn store(dir: &Path, chunks: &[DiffChunk]) -> Result<()> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create cache directory: {}", dir.display()))?;
+
+    for chunk in chunks {
+        if let Some(audio) = &chunk.audio_data {
+            fs::write(dir.join(format!("{}.mp3", chunk.chunk_id)), audio)
+                .context("Failed to write cached audio")?;
+        }
+        let json = serde_json::to_string(chunk).context("Failed to serialize chunk")?;
+        fs::write(dir.join(format!("{}.json", chunk.chunk_id)), json)
+            .context("Failed to write cached chunk")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_then_load_round_trips_chunks() {
+        let dir = std::env::temp_dir().join(format!("torvax_cache_{}", std::process::id()));
+        let chunks = vec![
+            DiffChunk {
+                chunk_id: 1,
+                file_path: "src/b.rs".to_string(),
+                hunk_indices: vec![2],
+                explanation: "second".to_string(),
+                audio_data: None,
+                has_audio: false,
+                audio_duration_secs: 0.0,
+            },
+            DiffChunk {
+                chunk_id: 0,
+                file_path: "src/a.rs".to_string(),
+                hunk_indices: vec![0, 1],
+                explanation: "first".to_string(),
+                audio_data: Some(vec![1, 2, 3]),
+                has_audio: true,
+                audio_duration_secs: 1.5,
+            },
+        ];
+
+        store(&dir, &chunks).unwrap();
+        let loaded = load(&dir).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].chunk_id, 0);
+        assert_eq!(loaded[0].audio_data.as_deref(), Some(&[1, 2, 3][..]));
+        assert_eq!(loaded[0].hunk_indices, vec![0, 1]);
+        assert_eq!(loaded[1].explanation, "second");
+        assert!(loaded[1].audio_data.is_none());
+    }
+
+    #[test]
+    fn working_tree_is_never_cached() {
+        let config = VoiceoverConfig::default();
+        let timing = AnimationTiming {
+            speed_ms: 30,
+            show_deletions: false,
+            only_additions: false,
+            collapse_threshold: None,
+            line_pause_scale: 1.0,
+            pauses: EditPauses::default(),
+        };
+        assert!(chunk_dir(&config, "working-tree", timing, FileOrder::Flow).is_none());
+        assert!(chunk_dir(&config, "abc123", timing, FileOrder::Flow).is_some());
+    }
+
+    #[test]
+    fn llm_settings_change_the_cache_dir() {
+        let config = VoiceoverConfig::default();
+        let timing = AnimationTiming {
+            speed_ms: 30,
+            show_deletions: false,
+            only_additions: false,
+            collapse_threshold: None,
+            line_pause_scale: 1.0,
+            pauses: EditPauses::default(),
+        };
+        let dir = |config: &VoiceoverConfig| chunk_dir(config, "abc123", timing, FileOrder::Flow);
+
+        let mut other_model = config.clone();
+        other_model.narration_model = Some("llama3".to_string());
+        let mut other_temperature = config.clone();
+        other_temperature.grouping_temperature = Some(0.2);
+        let mut other_endpoint = config.clone();
+        other_endpoint.llm_base_url = Some("http://localhost:11434/v1".to_string());
+
+        assert_eq!(dir(&config), dir(&config.clone()));
+        assert_ne!(dir(&config), dir(&other_model));
+        assert_ne!(dir(&config), dir(&other_temperature));
+        assert_ne!(dir(&config), dir(&other_endpoint));
+    }
+}
@@ -1,29 +1,54 @@
-use super::llm::{calculate_animation_duration, words_for_duration};
-use super::types::{DiffChunk, ProjectContext, VoiceoverConfig};
+use super::llm::{
+    calculate_animation_duration, chat_client, chat_model, temperature, words_for_duration,
+    AnimationTiming, LlmStage,
+};
+use super::retry;
+use super::types::{CommitContext, DiffChunk, ProjectContext, VoiceoverConfig};
+use crate::git::FileStatus;
 use anyhow::{Context, Result};
-use async_openai::{
-    config::OpenAIConfig,
-    types::{
-        ChatCompletionRequestMessage, ChatCompletionRequestUserMessageArgs,
-        CreateChatCompletionRequestArgs,
-    },
-    Client,
+use async_openai::types::{
+    ChatCompletionRequestMessage, ChatCompletionRequestUserMessageArgs,
+    CreateChatCompletionRequestArgs,
 };
 
+/// Pronunciation rule of the default prompt; only meaningful for English narration
+const ENGLISH_SPEECH_RULES: &str =
+    "- OPTIMIZE FOR SPEECH: Say 'Node' not 'Node.js', 'React' not 'React.js', 'TypeScript' not 'TS'\n";
+
+/// Appended to the narration prompt when deletions are not played
+const ONLY_ADDITIONS_RULE: &str =
+    "\n\nOnly the added lines are shown on screen; deleted lines vanish without comment. \
+    Describe only what the additions introduce and do not talk about removed code.";
+
+/// Narration prompt used when no `narration_prompt` is configured
+const DEFAULT_NARRATION_PROMPT: &str = "You are narrating live code changes for a developer teaching stream.\n\n\
+    PROJECT: {project}\n\
+    COMMIT: \"{commit}\"\n\
+    {byline}\
+    FILE: {file}\n\
+    {language_line}\n\
+    CODE CHANGES:\n{diff}\n\n\
+    Write a {target_words}-word narration explaining these changes.\n\
+    This narration will be spoken by text-to-speech while the code is being typed on screen.\n\
+    The typing animation for this section lasts {animation_secs} seconds, so the narration MUST fill that time.\n\n\
+    RULES:\n\
+    - Explain WHAT changed, WHY it matters for this project, and HOW it works\n\
+    - Be semantically rich: describe the purpose and design decisions, not just surface changes\n\
+    {speech_rules}\
+    - No symbols, no file extensions, no code syntax. Write how developers actually talk.\n\n\
+    Respond with ONLY the narration text.";
+
 /// Split a file diff into semantic chunks, each with an LLM explanation sized to match
 /// the animation duration for that chunk.
 pub async fn split_diff_into_chunks(
     config: &VoiceoverConfig,
     project_context: &ProjectContext,
-    commit_message: &str,
+    commit: &CommitContext,
     filename: &str,
     diff: &str,
-    speed_ms: u64,
+    timing: AnimationTiming,
 ) -> Result<Vec<DiffChunk>> {
-    let api_key = config
-        .openai_api_key
-        .as_ref()
-        .context("OpenAI API key not configured")?;
+    let client = chat_client(config)?;
 
     // Parse diff into hunk groups
     let (hunks, hunk_summaries) = parse_hunks(diff);
@@ -32,10 +57,9 @@ pub async fn split_diff_into_chunks(
         vec![(0..hunks.len()).collect()]
     } else {
         llm_group_hunks(
-            api_key,
             config,
             project_context,
-            commit_message,
+            &commit.message,
             filename,
             &hunk_summaries,
             &hunks,
@@ -43,8 +67,6 @@ pub async fn split_diff_into_chunks(
         .await?
     };
 
-    let cfg = OpenAIConfig::new().with_api_key(api_key);
-    let client = Client::with_config(cfg);
     let mut chunks = Vec::new();
 
     for (idx, hunk_indices) in chunk_groups.iter().enumerate() {
@@ -54,52 +76,56 @@ pub async fn split_diff_into_chunks(
             .copied()
             .collect();
 
-        let animation_secs = calculate_animation_duration(&chunk_lines, speed_ms);
-        let target_words = words_for_duration(animation_secs);
+        let animation_secs = calculate_animation_duration(&chunk_lines, timing);
+        let target_words = words_for_duration(animation_secs, config.words_per_sec());
         let chunk_diff = chunk_lines.join("\n");
 
-        let prompt = format!(
-            "You are narrating live code changes for a developer teaching stream.\n\n\
-            PROJECT: {} - {}\n\
-            COMMIT: \"{}\"\n\
-            FILE: {}\n\n\
-            CODE CHANGES:\n{}\n\n\
-            Write a {}-word narration explaining these changes.\n\
-            This narration will be spoken by text-to-speech while the code is being typed on screen.\n\
-            The typing animation for this section lasts {:.0} seconds, so the narration MUST fill that time.\n\n\
-            RULES:\n\
-            - Explain WHAT changed, WHY it matters for this project, and HOW it works\n\
-            - Be semantically rich: describe the purpose and design decisions, not just surface changes\n\
-            - OPTIMIZE FOR SPEECH: Say 'Node' not 'Node.js', 'React' not 'React.js', 'TypeScript' not 'TS'\n\
-            - No symbols, no file extensions, no code syntax. Write how developers actually talk.\n\n\
-            Respond with ONLY the narration text.",
-            project_context.repo_name,
-            project_context.description,
-            commit_message,
-            filename,
-            chunk_diff,
-            target_words,
-            animation_secs
+        let (author, date) = authorship(config, commit);
+        let language = commit.language(filename);
+        let mut prompt = narration_prompt(
+            config,
+            &[
+                (
+                    "project",
+                    format!(
+                        "{} - {}",
+                        project_context.repo_name, project_context.description
+                    ),
+                ),
+                ("commit", commit.message.clone()),
+                ("byline", byline(&author, &date)),
+                ("author", author.clone()),
+                ("date", date.clone()),
+                ("file", filename.to_string()),
+                ("language", language.unwrap_or_default().to_string()),
+                ("language_line", language_line(language)),
+                ("diff", chunk_diff),
+                ("target_words", target_words.to_string()),
+                ("animation_secs", format!("{:.0}", animation_secs)),
+            ],
         );
+        if timing.only_additions {
+            prompt.push_str(ONLY_ADDITIONS_RULE);
+        }
 
         tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
 
         let request = CreateChatCompletionRequestArgs::default()
-            .model("gpt-5.2")
+            .model(chat_model(config, LlmStage::Narration))
             .messages(vec![ChatCompletionRequestMessage::User(
                 ChatCompletionRequestUserMessageArgs::default()
                     .content(prompt)
                     .build()?,
             )])
-            .temperature(0.7)
+            .temperature(temperature(config, LlmStage::Narration))
             .max_completion_tokens((target_words * 2).max(200) as u32)
             .build()?;
 
-        let response = client
-            .chat()
-            .create(request)
-            .await
-            .context("Failed to generate explanation")?;
+        let response = retry::with_backoff("Explanation request", || async {
+            Ok(client.chat().create(request.clone()).await?)
+        })
+        .await
+        .context("Failed to generate explanation")?;
 
         let explanation = response
             .choices
@@ -109,8 +135,7 @@ pub async fn split_diff_into_chunks(
             .trim()
             .to_string();
 
-        let actual_words = explanation.split_whitespace().count();
-        let audio_secs = (actual_words as f32) / 2.5;
+        let audio_secs = config.speech_secs(&explanation);
 
         chunks.push(DiffChunk {
             chunk_id: idx,
@@ -126,8 +151,307 @@ pub async fn split_diff_into_chunks(
     Ok(chunks)
 }
 
+/// One-sentence spoken introduction played as a file opens, naming the file and
+/// what it does in the project.
+pub async fn file_intro(
+    config: &VoiceoverConfig,
+    project_context: &ProjectContext,
+    commit: &CommitContext,
+    filename: &str,
+    diff: &str,
+) -> Result<String> {
+    let prompt = intro_prompt(config, project_context, commit, filename, diff);
+    spoken_reply(config, prompt, 100, "File intro").await
+}
+
+/// Opening line of a commit intro, e.g. `Commit abc1234 by Alice: Add retries.`
+/// The author is left out when the config keeps authorship out of narration, and
+/// the working tree is introduced by its message alone (`Staged changes.`).
+pub fn commit_lead(config: &VoiceoverConfig, commit: &CommitContext, commit_hash: &str) -> String {
+    let subject = commit.message.lines().next().unwrap_or("").trim();
+    let subject = subject.trim_end_matches('.');
+    let short_hash = &commit_hash[..7.min(commit_hash.len())];
+    if commit_hash == "working-tree" {
+        format!("{}.", subject)
+    } else if config.narrate_author {
+        format!("Commit {} by {}: {}.", short_hash, commit.author, subject)
+    } else {
+        format!("Commit {}: {}.", short_hash, subject)
+    }
+}
+
+/// Spoken introduction played before a commit's first file: the commit lead
+/// followed by a sentence on what the change does as a whole.
+pub async fn commit_intro(
+    config: &VoiceoverConfig,
+    project_context: &ProjectContext,
+    commit: &CommitContext,
+    commit_hash: &str,
+    files: &[(String, String, FileStatus)],
+) -> Result<String> {
+    let instructions = format!(
+        "The walkthrough of this commit is about to begin. Write a spoken intro of at most 40 words. \
+        Start with \"{}\" and follow with one sentence on what the change does overall, \
+        like \"This change adds retries to the upload client.\"",
+        commit_lead(config, commit, commit_hash)
+    );
+    let prompt = commit_prompt(config, project_context, commit, files, &instructions);
+    spoken_reply(config, prompt, 150, "Commit intro").await
+}
+
+/// Short spoken wrap-up played after a commit's last file.
+pub async fn commit_outro(
+    config: &VoiceoverConfig,
+    project_context: &ProjectContext,
+    commit: &CommitContext,
+    files: &[(String, String, FileStatus)],
+) -> Result<String> {
+    let instructions =
+        "The walkthrough of this commit just ended. Write one or two short spoken sentences \
+        (at most 30 words) summing up what the commit changed, like \
+        \"And that's it: uploads now retry on failure and report their progress.\"";
+    let prompt = commit_prompt(config, project_context, commit, files, instructions);
+    spoken_reply(config, prompt, 100, "Commit outro").await
+}
+
 // --- helpers -----------------------------------------------------------------
 
+/// Ask for a short piece of narration and return it trimmed. `label` names the
+/// request in retry logs and errors.
+async fn spoken_reply(
+    config: &VoiceoverConfig,
+    prompt: String,
+    max_tokens: u32,
+    label: &str,
+) -> Result<String> {
+    let client = chat_client(config)?;
+    let request = CreateChatCompletionRequestArgs::default()
+        .model(chat_model(config, LlmStage::Narration))
+        .messages(vec![ChatCompletionRequestMessage::User(
+            ChatCompletionRequestUserMessageArgs::default()
+                .content(prompt)
+                .build()?,
+        )])
+        .temperature(temperature(config, LlmStage::Narration))
+        .max_completion_tokens(max_tokens)
+        .build()?;
+
+    let response = retry::with_backoff(&format!("{} request", label), || async {
+        Ok(client.chat().create(request.clone()).await?)
+    })
+    .await
+    .with_context(|| format!("Failed to generate {}", label.to_lowercase()))?;
+
+    let reply = response
+        .choices
+        .first()
+        .and_then(|c| c.message.content.as_ref())
+        .with_context(|| format!("No content in {} response", label.to_lowercase()))?
+        .trim()
+        .to_string();
+    Ok(reply)
+}
+
+/// Prompt for narration about a whole commit (intro or outro), listing its files.
+fn commit_prompt(
+    config: &VoiceoverConfig,
+    project_context: &ProjectContext,
+    commit: &CommitContext,
+    files: &[(String, String, FileStatus)],
+    instructions: &str,
+) -> String {
+    let file_list: Vec<String> = files
+        .iter()
+        .map(|(name, diff, status)| {
+            format!(
+                "- {} ({}, {} diff lines)",
+                name,
+                status.as_str(),
+                diff.lines().count()
+            )
+        })
+        .collect();
+    let (author, date) = authorship(config, commit);
+    let mut prompt = format!(
+        "You are narrating live code changes for a developer teaching stream.\n\n\
+        PROJECT: {} - {}\n\
+        COMMIT: \"{}\"\n\
+        {}\
+        FILES (A added, M modified, D deleted, R renamed):\n{}\n\n\
+        {}\n\
+        No symbols, no file extensions, no code syntax.\n\n\
+        Respond with ONLY the narration.",
+        project_context.repo_name,
+        &project_context
+            .description
+            .chars()
+            .take(300)
+            .collect::<String>(),
+        commit.message,
+        byline(&author, &date),
+        file_list.join("\n"),
+        instructions
+    );
+    if let Some(code) = config.foreign_language() {
+        prompt.push_str(&format!(
+            "\n\nWrite the narration in {}, translating the opening as well.",
+            language_name(&code)
+        ));
+    }
+    prompt
+}
+
+fn intro_prompt(
+    config: &VoiceoverConfig,
+    project_context: &ProjectContext,
+    commit: &CommitContext,
+    filename: &str,
+    diff: &str,
+) -> String {
+    // The start of the diff is enough to tell what the file is for
+    let excerpt: String = diff.lines().take(40).collect::<Vec<_>>().join("\n");
+    let (author, date) = authorship(config, commit);
+    let mut prompt = format!(
+        "You are narrating live code changes for a developer teaching stream.\n\n\
+        PROJECT: {} - {}\n\
+        COMMIT: \"{}\"\n\
+        {}\
+        FILE: {}\n\n\
+        CODE CHANGES (excerpt):\n{}\n\n\
+        The editor is about to open this file. Write ONE short spoken sentence (at most 20 words) \
+        introducing it, like \"Now opening the router, which maps requests to handlers.\"\n\
+        No symbols, no file extensions, no code syntax.\n\n\
+        Respond with ONLY the sentence.",
+        project_context.repo_name,
+        &project_context
+            .description
+            .chars()
+            .take(300)
+            .collect::<String>(),
+        commit.message,
+        byline(&author, &date),
+        filename,
+        excerpt
+    );
+    if let Some(code) = config.foreign_language() {
+        prompt.push_str(&format!(
+            "\n\nWrite the sentence in {}.",
+            language_name(&code)
+        ));
+    }
+    prompt
+}
+
+/// Commit author and relative date for the prompt, both empty when the
+/// config keeps authorship out of narration.
+fn authorship(config: &VoiceoverConfig, commit: &CommitContext) -> (String, String) {
+    if config.narrate_author {
+        (commit.author.clone(), commit.relative_date())
+    } else {
+        (String::new(), String::new())
+    }
+}
+
+/// Prompt line naming who made the commit and when, or nothing without an author.
+fn byline(author: &str, date: &str) -> String {
+    if author.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "AUTHOR: {}, committed {} (you may mention who made the change and when)\n",
+            author, date
+        )
+    }
+}
+
+/// Prompt line naming the file's language, so constructs are called by their
+/// proper names ("this Rust trait"), or nothing when it wasn't recognized.
+fn language_line(language: Option<&str>) -> String {
+    match language {
+        Some(language) => format!("LANGUAGE: {}\n", language),
+        None => String::new(),
+    }
+}
+
+/// Fill the configured (or default) narration prompt. For narration in another
+/// language the English pronunciation rules are dropped and the LLM is told which
+/// language to answer in.
+fn narration_prompt(config: &VoiceoverConfig, values: &[(&str, String)]) -> String {
+    let language = config.foreign_language();
+    let speech_rules = match language {
+        Some(_) => String::new(),
+        None => ENGLISH_SPEECH_RULES.to_string(),
+    };
+    let template = config
+        .narration_prompt
+        .as_deref()
+        .unwrap_or(DEFAULT_NARRATION_PROMPT);
+    let mut values = values.to_vec();
+    values.push(("speech_rules", speech_rules));
+    let mut prompt = fill_template(template, &values);
+
+    if let Some(code) = language {
+        prompt.push_str(&format!(
+            "\n\nWrite the narration in {}.",
+            language_name(&code)
+        ));
+    }
+    prompt
+}
+
+/// English name of a language code, for the prompt (the code itself if unknown)
+fn language_name(code: &str) -> &str {
+    match code {
+        "es" => "Spanish",
+        "fr" => "French",
+        "de" => "German",
+        "it" => "Italian",
+        "pt" => "Portuguese",
+        "nl" => "Dutch",
+        "pl" => "Polish",
+        "ru" => "Russian",
+        "ja" => "Japanese",
+        "ko" => "Korean",
+        "zh" => "Chinese",
+        "hi" => "Hindi",
+        "ar" => "Arabic",
+        "tr" => "Turkish",
+        _ => code,
+    }
+}
+
+/// Substitute `{name}` placeholders in a single pass, so braces inside the
+/// substituted values (e.g. code in the diff) are never expanded again.
+/// Unknown placeholders are left as written.
+fn fill_template(template: &str, values: &[(&str, String)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after.find('}').and_then(|close| {
+            let name = &after[..close];
+            values
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| (value, close))
+        });
+        match value {
+            Some((value, close)) => {
+                out.push_str(value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
 fn parse_hunks(diff: &str) -> (Vec<Vec<&str>>, Vec<String>) {
     let mut hunks: Vec<Vec<&str>> = Vec::new();
     let mut current: Vec<&str> = Vec::new();
@@ -181,8 +505,7 @@ fn parse_hunks(diff: &str) -> (Vec<Vec<&str>>, Vec<String>) {
 }
 
 async fn llm_group_hunks(
-    api_key: &str,
-    _config: &VoiceoverConfig,
+    config: &VoiceoverConfig,
     project_context: &ProjectContext,
     commit_message: &str,
     filename: &str,
@@ -209,24 +532,23 @@ async fn llm_group_hunks(
         hunk_summaries.join("\n")
     );
 
-    let cfg = OpenAIConfig::new().with_api_key(api_key);
-    let client = Client::with_config(cfg);
+    let client = chat_client(config)?;
     let request = CreateChatCompletionRequestArgs::default()
-        .model("gpt-5.2")
+        .model(chat_model(config, LlmStage::Grouping))
         .messages(vec![ChatCompletionRequestMessage::User(
             ChatCompletionRequestUserMessageArgs::default()
                 .content(prompt)
                 .build()?,
         )])
-        .temperature(0.3)
+        .temperature(temperature(config, LlmStage::Grouping))
         .max_completion_tokens(256u32)
         .build()?;
 
-    let response = client
-        .chat()
-        .create(request)
-        .await
-        .context("Failed to get hunk groupings")?;
+    let response = retry::with_backoff("Hunk grouping request", || async {
+        Ok(client.chat().create(request.clone()).await?)
+    })
+    .await
+    .context("Failed to get hunk groupings")?;
 
     let content = response
         .choices
@@ -262,3 +584,113 @@ async fn llm_group_hunks(
         Err(_) => Ok(vec![(0..hunks.len()).collect()]),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commit_lead_names_hash_author_and_subject() {
+        let commit = CommitContext {
+            message: "Add upload retries.\n\nLonger body".to_string(),
+            author: "Alice".to_string(),
+            date: Default::default(),
+            languages: Default::default(),
+        };
+        let mut config = VoiceoverConfig::default();
+        assert_eq!(
+            commit_lead(&config, &commit, "abc1234def"),
+            "Commit abc1234 by Alice: Add upload retries."
+        );
+        config.narrate_author = false;
+        assert_eq!(
+            commit_lead(&config, &commit, "abc1234def"),
+            "Commit abc1234: Add upload retries."
+        );
+        assert_eq!(
+            commit_lead(&config, &commit, "working-tree"),
+            "Add upload retries."
+        );
+    }
+
+    #[test]
+    fn fill_template_substitutes_known_placeholders_once() {
+        let filled = fill_template(
+            "{file}: {diff} ({unknown})",
+            &[
+                ("file", "src/a.rs".to_string()),
+                ("diff", "+fn f() { {file} }".to_string()),
+            ],
+        );
+        assert_eq!(filled, "src/a.rs: +fn f() { {file} } ({unknown})");
+    }
+
+    #[test]
+    fn default_prompt_uses_every_placeholder() {
+        for name in [
+            "project",
+            "commit",
+            "file",
+            "diff",
+            "target_words",
+            "animation_secs",
+        ] {
+            assert!(DEFAULT_NARRATION_PROMPT.contains(&format!("{{{}}}", name)));
+        }
+    }
+
+    #[test]
+    fn authorship_is_left_out_when_disabled() {
+        let commit = CommitContext {
+            message: "Fix login".to_string(),
+            author: "Alice".to_string(),
+            date: chrono::Utc::now(),
+            languages: Default::default(),
+        };
+        let (author, date) = authorship(&VoiceoverConfig::default(), &commit);
+        assert_eq!(
+            byline(&author, &date),
+            "AUTHOR: Alice, committed today (you may mention who made the change and when)\n"
+        );
+
+        let private = VoiceoverConfig {
+            narrate_author: false,
+            ..VoiceoverConfig::default()
+        };
+        let (author, date) = authorship(&private, &commit);
+        assert_eq!((author.as_str(), date.as_str()), ("", ""));
+        assert_eq!(byline(&author, &date), "");
+    }
+
+    #[test]
+    fn language_line_names_the_file_language() {
+        let commit = CommitContext {
+            message: "Add parser".to_string(),
+            author: String::new(),
+            date: chrono::Utc::now(),
+            languages: [("src/lib.rs".to_string(), "Rust")].into_iter().collect(),
+        };
+        assert_eq!(
+            language_line(commit.language("src/lib.rs")),
+            "LANGUAGE: Rust\n"
+        );
+        assert_eq!(language_line(commit.language("NOTES")), "");
+    }
+
+    #[test]
+    fn foreign_language_prompt_drops_english_rules_and_names_the_language() {
+        let values = [("diff", "+x".to_string())];
+        let english = narration_prompt(&VoiceoverConfig::default(), &values);
+        assert!(english.contains("Say 'Node' not 'Node.js'"));
+        assert!(!english.contains("Write the narration in"));
+
+        let config = VoiceoverConfig {
+            language: Some("es-MX".to_string()),
+            ..VoiceoverConfig::default()
+        };
+        let spanish = narration_prompt(&config, &values);
+        assert!(!spanish.contains("Node.js"));
+        assert!(!spanish.contains("{speech_rules}"));
+        assert!(spanish.ends_with("Write the narration in Spanish."));
+    }
+}
@@ -0,0 +1,74 @@
+use std::io::Cursor;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Source};
+
+// Short click bundled into the binary so no sound files need installing
+const CLICK_WAV: &[u8] = include_bytes!("keystroke.wav");
+
+// Clicks closer together than this blur into a buzz at fast typing speeds
+const MIN_CLICK_INTERVAL: Duration = Duration::from_millis(25);
+
+// Kept well under narration so it stays in the background
+const CLICK_VOLUME: f32 = 0.35;
+
+/// Plays a soft typewriter click per keystroke, mixed over any narration.
+pub struct KeystrokeSound {
+    // Only set when there is no narration output to share
+    _stream: Option<OutputStream>,
+    handle: OutputStreamHandle,
+    last_click: Option<Instant>,
+}
+
+impl KeystrokeSound {
+    /// Click through the narration's output, mixed over whatever it plays.
+    pub fn with_output(handle: OutputStreamHandle) -> Self {
+        Self {
+            _stream: None,
+            handle,
+            last_click: None,
+        }
+    }
+
+    /// Click through an output of its own, for playback without narration.
+    pub fn new() -> Result<Self> {
+        let (stream, handle) = OutputStream::try_default()
+            .context("Failed to create audio output stream for keystroke sound")?;
+        Ok(Self {
+            _stream: Some(stream),
+            ..Self::with_output(handle)
+        })
+    }
+
+    /// Play one click unless another just played.
+    pub fn click(&mut self) {
+        let now = Instant::now();
+        if self
+            .last_click
+            .is_some_and(|last| now.duration_since(last) < MIN_CLICK_INTERVAL)
+        {
+            return;
+        }
+        self.last_click = Some(now);
+
+        let Ok(source) = Decoder::new(Cursor::new(CLICK_WAV)) else {
+            return;
+        };
+        let _ = self
+            .handle
+            .play_raw(source.amplify(CLICK_VOLUME).convert_samples());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_click_is_a_short_sound() {
+        let source = Decoder::new(Cursor::new(CLICK_WAV)).unwrap();
+        let length = source.total_duration().unwrap();
+        assert!(length > Duration::ZERO && length < MIN_CLICK_INTERVAL);
+    }
+}
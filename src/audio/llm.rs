@@ -1,5 +1,7 @@
+use super::retry;
 use super::types::{ProjectContext, VoiceoverConfig};
-use crate::git::FileStatus;
+use crate::config::EditPauses;
+use crate::git::{is_binary_summary, FileStatus};
 use anyhow::{Context, Result};
 use async_openai::{
     config::OpenAIConfig,
@@ -10,6 +12,62 @@ use async_openai::{
     Client,
 };
 
+const DEFAULT_LLM_MODEL: &str = "gpt-5.2";
+
+/// Chat client for the configured LLM backend: OpenAI by default, or any
+/// OpenAI-compatible endpoint (e.g. Ollama) when `llm_base_url` is set.
+pub fn chat_client(config: &VoiceoverConfig) -> Result<Client<OpenAIConfig>> {
+    let mut cfg = OpenAIConfig::new();
+    if let Some(base_url) = &config.llm_base_url {
+        cfg = cfg.with_api_base(base_url.trim_end_matches('/'));
+    }
+    match &config.openai_api_key {
+        Some(key) => cfg = cfg.with_api_key(key),
+        // Local endpoints accept any key
+        None if config.llm_base_url.is_some() => cfg = cfg.with_api_key("local"),
+        None => anyhow::bail!("OpenAI API key not configured"),
+    }
+    Ok(Client::with_config(cfg).with_http_client(retry::http_client(config)?))
+}
+
+/// Steps of narration that call the chat model. Each can use its own model
+/// and temperature, e.g. a cheaper model for the short ordering and grouping replies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LlmStage {
+    /// Describing the project from its README and manifests
+    Context,
+    /// Ordering a commit's files by development flow
+    Ordering,
+    /// Grouping a file's hunks into narrated chunks
+    Grouping,
+    /// Writing the spoken explanations and file intros
+    Narration,
+}
+
+/// Chat model name for a stage: its own override, then `llm_model`, then the default
+pub fn chat_model(config: &VoiceoverConfig, stage: LlmStage) -> &str {
+    let stage_model = match stage {
+        LlmStage::Context => None,
+        LlmStage::Ordering => config.ordering_model.as_deref(),
+        LlmStage::Grouping => config.grouping_model.as_deref(),
+        LlmStage::Narration => config.narration_model.as_deref(),
+    };
+    stage_model
+        .or(config.llm_model.as_deref())
+        .unwrap_or(DEFAULT_LLM_MODEL)
+}
+
+/// Sampling temperature for a stage, clamped to the 0.0-2.0 range the API accepts
+pub fn temperature(config: &VoiceoverConfig, stage: LlmStage) -> f32 {
+    let temperature = match stage {
+        LlmStage::Context => 0.5,
+        LlmStage::Ordering => config.ordering_temperature.unwrap_or(0.2),
+        LlmStage::Grouping => config.grouping_temperature.unwrap_or(0.3),
+        LlmStage::Narration => config.narration_temperature.unwrap_or(0.7),
+    };
+    temperature.clamp(0.0, 2.0)
+}
+
 /// Build a ProjectContext from the local repo (repo_name filled, description empty until LLM runs)
 pub fn extract_project_context() -> ProjectContext {
     let repo_name = extract_repo_name().unwrap_or_else(|| {
@@ -26,10 +84,7 @@ pub fn extract_project_context() -> ProjectContext {
 
 /// Generate a TTS-friendly project description via GPT
 pub async fn generate_project_context_with_llm(config: &VoiceoverConfig) -> Result<String> {
-    let api_key = config
-        .openai_api_key
-        .as_ref()
-        .context("OpenAI API key not configured")?;
+    let client = chat_client(config)?;
 
     let key_files = [
         ("Cargo.toml", 5000),
@@ -74,25 +129,22 @@ pub async fn generate_project_context_with_llm(config: &VoiceoverConfig) -> Resu
         context_files.join("\n\n---\n\n")
     );
 
-    let cfg = OpenAIConfig::new().with_api_key(api_key);
-    let client = Client::with_config(cfg);
-
     let request = CreateChatCompletionRequestArgs::default()
-        .model("gpt-5.2")
+        .model(chat_model(config, LlmStage::Context))
         .messages(vec![ChatCompletionRequestMessage::User(
             ChatCompletionRequestUserMessageArgs::default()
                 .content(prompt)
                 .build()?,
         )])
-        .temperature(0.5)
+        .temperature(temperature(config, LlmStage::Context))
         .max_completion_tokens(2048u32)
         .build()?;
 
-    let response = client
-        .chat()
-        .create(request)
-        .await
-        .context("Failed to call OpenAI API")?;
+    let response = retry::with_backoff("Project description request", || async {
+        Ok(client.chat().create(request.clone()).await?)
+    })
+    .await
+    .context("Failed to call OpenAI API")?;
 
     response
         .choices
@@ -102,6 +154,80 @@ pub async fn generate_project_context_with_llm(config: &VoiceoverConfig) -> Resu
         .map(|s| s.trim().to_string())
 }
 
+/// Plain description built from the commit itself, used when the LLM project
+/// description fails so narration can still go ahead.
+pub fn fallback_project_description(
+    repo_name: &str,
+    commit_message: &str,
+    file_changes: &[(String, String, FileStatus)],
+) -> String {
+    let count = |status: FileStatus| file_changes.iter().filter(|(_, _, s)| *s == status).count();
+    let mut kinds = Vec::new();
+    for (status, label) in [
+        (FileStatus::Added, "added"),
+        (FileStatus::Modified, "modified"),
+        (FileStatus::Deleted, "deleted"),
+        (FileStatus::Renamed, "renamed"),
+    ] {
+        let n = count(status);
+        if n > 0 {
+            kinds.push(format!("{} {}", n, label));
+        }
+    }
+
+    let diff_lines = file_changes.iter().flat_map(|(_, diff, _)| diff.lines());
+    let (mut added, mut removed) = (0, 0);
+    for line in diff_lines {
+        if line.starts_with('+') && !line.starts_with("+++") {
+            added += 1;
+        } else if line.starts_with('-') && !line.starts_with("---") {
+            removed += 1;
+        }
+    }
+
+    let mut description = format!("{} is a software project.", repo_name);
+    let subject = commit_message.lines().next().unwrap_or("").trim();
+    if !subject.is_empty() {
+        description.push_str(&format!(" This commit is titled: {}.", subject));
+    }
+    description.push_str(&format!(
+        " It changes {} file{}",
+        file_changes.len(),
+        if file_changes.len() == 1 { "" } else { "s" }
+    ));
+    if !kinds.is_empty() {
+        description.push_str(&format!(" ({})", kinds.join(", ")));
+    }
+    description.push_str(&format!(
+        ", adding {} lines and removing {}.",
+        added, removed
+    ));
+    if let Some(dir) = busiest_directory(file_changes) {
+        description.push_str(&format!(" Most of the work is in the {} directory.", dir));
+    }
+    description
+}
+
+/// Top-level directory holding the most changed files, if any file is in one
+fn busiest_directory(file_changes: &[(String, String, FileStatus)]) -> Option<&str> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for (path, _, _) in file_changes {
+        let Some((dir, _)) = path.split_once('/') else {
+            continue;
+        };
+        match counts.iter_mut().find(|(d, _)| *d == dir) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((dir, 1)),
+        }
+    }
+    // Ties go to the directory seen first
+    counts
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, n)| *n)
+        .map(|(dir, _)| dir)
+}
+
 /// Extract repo name from .git/config remote URL
 fn extract_repo_name() -> Option<String> {
     let config = std::fs::read_to_string(".git/config").ok()?;
@@ -114,15 +240,70 @@ fn extract_repo_name() -> Option<String> {
     None
 }
 
-/// Calculate animation duration from diff lines, mirroring animation.rs timing constants.
-/// speed_ms: base typing delay per character in milliseconds.
-pub fn calculate_animation_duration(diff_lines: &[&str], speed_ms: u64) -> f32 {
-    const INSERT_LINE_PAUSE: f64 = 6.7;
-    const DELETE_LINE_PAUSE: f64 = 10.0;
-    const HUNK_PAUSE: f64 = 50.0;
-    const CURSOR_MOVE_PAUSE: f64 = 0.5;
+// Lower bound for the per-character delay derived from a --duration budget
+const MIN_DURATION_SPEED_MS: u64 = 2;
+
+/// Playback settings that determine how long a diff takes to animate
+#[derive(Debug, Clone, Copy)]
+pub struct AnimationTiming {
+    /// Base typing delay per character in milliseconds
+    pub speed_ms: u64,
+    /// Deleted lines are shown struck through for a beat before removal
+    pub show_deletions: bool,
+    /// Deleted lines are removed instantly and left out of the narration
+    pub only_additions: bool,
+    /// Files changing more lines than this are collapsed and narrated from an excerpt
+    pub collapse_threshold: Option<usize>,
+    /// Scale of the pause after each typed line (1.0, longer in presentation mode)
+    pub line_pause_scale: f64,
+    /// Pauses around typed edits, as played by the animation engine
+    pub pauses: EditPauses,
+}
+
+/// Calculate animation duration from diff lines, with the pauses the animation engine plays.
+pub fn calculate_animation_duration(diff_lines: &[&str], timing: AnimationTiming) -> f32 {
+    let total_ms = animation_cost(
+        diff_lines,
+        timing.show_deletions,
+        timing.only_additions,
+        timing.line_pause_scale,
+        &timing.pauses,
+    ) * timing.speed_ms as f64;
+    (total_ms / 1000.0).max(5.0) as f32
+}
+
+/// Inverse of `calculate_animation_duration`: the typing delay that makes the given
+/// diff lines animate in roughly `target_secs`.
+pub fn speed_for_duration(
+    diff_lines: &[&str],
+    target_secs: f64,
+    show_deletions: bool,
+    only_additions: bool,
+    line_pause_scale: f64,
+    pauses: &EditPauses,
+) -> u64 {
+    let cost = animation_cost(
+        diff_lines,
+        show_deletions,
+        only_additions,
+        line_pause_scale,
+        pauses,
+    );
+    if cost <= 0.0 {
+        return MIN_DURATION_SPEED_MS;
+    }
+    ((target_secs * 1000.0 / cost).round() as u64).max(MIN_DURATION_SPEED_MS)
+}
 
-    let speed = speed_ms as f64;
+/// Animation time in milliseconds per 1ms of typing delay (duration scales linearly with speed).
+fn animation_cost(
+    diff_lines: &[&str],
+    show_deletions: bool,
+    only_additions: bool,
+    line_pause_scale: f64,
+    pauses: &EditPauses,
+) -> f64 {
+    let speed = 1.0;
     let mut total_ms: f64 = 0.0;
     let mut in_hunk = false;
     let mut hunk_count = 0;
@@ -130,9 +311,9 @@ pub fn calculate_animation_duration(diff_lines: &[&str], speed_ms: u64) -> f32 {
     for line in diff_lines {
         if line.starts_with("@@") {
             if hunk_count > 0 {
-                total_ms += HUNK_PAUSE * speed;
+                total_ms += pauses.hunk * speed;
             }
-            total_ms += CURSOR_MOVE_PAUSE * speed * 5.0;
+            total_ms += pauses.cursor_move * speed * 5.0;
             hunk_count += 1;
             in_hunk = true;
             continue;
@@ -140,21 +321,28 @@ pub fn calculate_animation_duration(diff_lines: &[&str], speed_ms: u64) -> f32 {
         if !in_hunk {
             continue;
         }
-        if line.starts_with('+') && !line.starts_with("+++") {
+        if line.strip_prefix('+').is_some_and(is_binary_summary) {
+            // Binary files show one summary line at once instead of typing it
+            total_ms += pauses.binary_summary * line_pause_scale * speed;
+        } else if line.starts_with('+') && !line.starts_with("+++") {
             let char_count = line.len().saturating_sub(1);
             total_ms += (char_count as f64) * speed;
-            total_ms += INSERT_LINE_PAUSE * speed;
-        } else if line.starts_with('-') && !line.starts_with("---") {
-            total_ms += DELETE_LINE_PAUSE * speed;
+            total_ms += pauses.insert_line * line_pause_scale * speed;
+        } else if line.starts_with('-') && !line.starts_with("---") && !only_additions {
+            total_ms += pauses.delete_line * speed;
+            if show_deletions {
+                total_ms += pauses.delete_mark * speed;
+            }
         }
     }
 
-    (total_ms / 1000.0).max(5.0) as f32
+    total_ms
 }
 
-/// Target word count so narration outlasts the animation (2× buffer, clamp 40–400).
-pub fn words_for_duration(animation_secs: f32) -> usize {
-    ((animation_secs * 2.5 * 2.0) as usize).clamp(40, 400)
+/// Target word count so narration spoken at `words_per_sec` outlasts the animation
+/// (2× buffer, clamp 40–400).
+pub fn words_for_duration(animation_secs: f32, words_per_sec: f32) -> usize {
+    ((animation_secs * words_per_sec * 2.0) as usize).clamp(40, 400)
 }
 
 /// Order files by logical development flow using GPT. Falls back to original order on error.
@@ -167,9 +355,9 @@ pub async fn order_files_by_development_flow(
     if files.len() <= 1 {
         return files.to_vec();
     }
-    let api_key = match config.openai_api_key.as_ref() {
-        Some(k) => k,
-        None => return files.to_vec(),
+    let client = match chat_client(config) {
+        Ok(client) => client,
+        Err(_) => return files.to_vec(),
     };
 
     let file_list: Vec<String> = files
@@ -208,10 +396,8 @@ pub async fn order_files_by_development_flow(
         file_list.join("\n")
     );
 
-    let cfg = OpenAIConfig::new().with_api_key(api_key);
-    let client = Client::with_config(cfg);
     let request = match CreateChatCompletionRequestArgs::default()
-        .model("gpt-5.2")
+        .model(chat_model(config, LlmStage::Ordering))
         .messages(vec![ChatCompletionRequestMessage::User(
             match ChatCompletionRequestUserMessageArgs::default()
                 .content(prompt)
@@ -221,7 +407,7 @@ pub async fn order_files_by_development_flow(
                 Err(_) => return files.to_vec(),
             },
         )])
-        .temperature(0.2)
+        .temperature(temperature(config, LlmStage::Ordering))
         .max_completion_tokens(128u32)
         .build()
     {
@@ -229,7 +415,11 @@ pub async fn order_files_by_development_flow(
         Err(_) => return files.to_vec(),
     };
 
-    match client.chat().create(request).await {
+    let response = retry::with_backoff("File ordering request", || async {
+        Ok(client.chat().create(request.clone()).await?)
+    })
+    .await;
+    match response {
         Ok(response) => {
             if let Some(content) = response
                 .choices
@@ -257,3 +447,79 @@ pub async fn order_files_by_development_flow(
         Err(_) => files.to_vec(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stages_fall_back_to_the_shared_model_and_default_temperatures() {
+        let config = VoiceoverConfig {
+            llm_model: Some("big".to_string()),
+            ordering_model: Some("mini".to_string()),
+            narration_temperature: Some(3.5),
+            ..Default::default()
+        };
+        assert_eq!(chat_model(&config, LlmStage::Ordering), "mini");
+        assert_eq!(chat_model(&config, LlmStage::Grouping), "big");
+        assert_eq!(
+            chat_model(&VoiceoverConfig::default(), LlmStage::Narration),
+            DEFAULT_LLM_MODEL
+        );
+        assert_eq!(temperature(&config, LlmStage::Grouping), 0.3);
+        assert_eq!(temperature(&config, LlmStage::Narration), 2.0);
+    }
+
+    #[test]
+    fn fallback_description_summarizes_the_commit() {
+        let changes = vec![
+            (
+                "src/parser.rs".to_string(),
+                "--- a/src/parser.rs\n+++ b/src/parser.rs\n-old\n+new\n+more".to_string(),
+                FileStatus::Modified,
+            ),
+            (
+                "src/lexer.rs".to_string(),
+                "+fn lex() {}".to_string(),
+                FileStatus::Added,
+            ),
+            ("README.md".to_string(), String::new(), FileStatus::Modified),
+        ];
+        assert_eq!(
+            fallback_project_description("torvax", "Rewrite the parser\n\nDetails", &changes),
+            "torvax is a software project. This commit is titled: Rewrite the parser. \
+             It changes 3 files (1 added, 2 modified), adding 3 lines and removing 1. \
+             Most of the work is in the src directory."
+        );
+    }
+
+    #[test]
+    fn presenting_lengthens_the_animation_narration_is_sized_to() {
+        let diff: Vec<String> = std::iter::once("@@ -0,0 +1,60 @@".to_string())
+            .chain((0..60).map(|i| format!("+let value_{} = {};", i, i)))
+            .collect();
+        let lines: Vec<&str> = diff.iter().map(String::as_str).collect();
+        let timing = AnimationTiming {
+            speed_ms: 30,
+            show_deletions: false,
+            only_additions: false,
+            collapse_threshold: None,
+            line_pause_scale: 1.0,
+            pauses: EditPauses::default(),
+        };
+        let normal = calculate_animation_duration(&lines, timing);
+        let presenting = calculate_animation_duration(
+            &lines,
+            AnimationTiming {
+                line_pause_scale: 3.0,
+                ..timing
+            },
+        );
+        // 60 lines × 6.7 × 30ms × 2 extra pauses
+        assert!((presenting - normal - 24.12).abs() < 0.01);
+        assert!(
+            speed_for_duration(&lines, 60.0, false, false, 3.0, &timing.pauses)
+                < speed_for_duration(&lines, 60.0, false, false, 1.0, &timing.pauses)
+        );
+    }
+}
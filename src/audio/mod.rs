@@ -1,64 +1,103 @@
+pub(crate) mod cache;
 pub(crate) mod chunker;
+pub(crate) mod keystroke;
 pub(crate) mod llm;
+pub(crate) mod retry;
 pub(crate) mod tts;
 pub mod types;
 
 pub use types::{
-    DiffChunk, VoiceoverConfig, VoiceoverProvider, VoiceoverSegment, VoiceoverTrigger,
+    CommitContext, DiffChunk, VoiceoverConfig, VoiceoverProvider, COMMIT_INTRO_PATH,
+    COMMIT_OUTRO_PATH, MAX_VOLUME,
 };
 
 use anyhow::{Context, Result};
-use rodio::{Decoder, OutputStream, Sink};
-use std::collections::VecDeque;
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 
+use crate::cli::FileOrder;
 use crate::git::FileStatus;
+use tts::TtsProvider;
+
+// Path, diff and status of each file handed to the narrator
+type FileChanges = Vec<(String, String, FileStatus)>;
+
+/// Status message, progress (0.0-1.0) and estimated finish time while narration is generated
+pub type GenerationProgress = (String, f32, Option<Instant>);
 
 /// Handles pre-generated audio chunks and synced playback during animation
 pub struct AudioPlayer {
     config: VoiceoverConfig,
     _stream: Option<OutputStream>,
+    // Shared with the keystroke sound so clicks mix into the same output
+    stream_handle: Option<OutputStreamHandle>,
     sink: Option<Arc<Mutex<Sink>>>,
-    segment_queue: Arc<Mutex<VecDeque<VoiceoverSegment>>>,
     chunks: Arc<Mutex<std::collections::HashMap<usize, DiffChunk>>>,
     chunk_finished_tx: Sender<usize>,
     chunk_finished_rx: Arc<Mutex<Receiver<usize>>>,
+    // Bumped by stop_chunks so interrupted chunks don't report completion
+    playback_generation: Arc<AtomicUsize>,
+    // Without an output device chunks are timed silently; this holds that clock
+    silent_paused: Arc<AtomicBool>,
 }
 
 impl AudioPlayer {
+    /// Create a player for the config. Without a usable audio device narration is
+    /// still generated and timed, just not heard, so subtitles
+    /// and `--audio-out` work on headless machines.
     pub fn new(config: VoiceoverConfig) -> Result<Self> {
-        let (chunk_finished_tx, chunk_finished_rx) = channel();
-
-        if !config.enabled {
-            return Ok(Self {
-                config,
-                _stream: None,
-                sink: None,
-                segment_queue: Arc::new(Mutex::new(VecDeque::new())),
-                chunks: Arc::new(Mutex::new(std::collections::HashMap::new())),
-                chunk_finished_tx,
-                chunk_finished_rx: Arc::new(Mutex::new(chunk_finished_rx)),
-            });
-        }
+        let output = if config.enabled {
+            if config.provider == VoiceoverProvider::Piper {
+                tts::check_piper(&config)?;
+            }
+            match open_output(config.volume) {
+                Ok(output) => Some(output),
+                Err(e) => {
+                    log::warn!(
+                        "No audio output device ({:#}); narration is still generated for \
+                         subtitles and --audio-out but won't be heard",
+                        e
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        Ok(Self::with_output(config, output))
+    }
 
-        eprintln!("[AUDIO INIT] Creating OutputStream...");
-        let (_stream, stream_handle) = OutputStream::try_default()
-            .context("Failed to create audio output stream during AudioPlayer::new()")?;
-        eprintln!("[AUDIO INIT] OutputStream created successfully");
-        let sink = Sink::try_new(&stream_handle).context("Failed to create audio sink")?;
-        sink.play();
+    /// Player on an already opened output, or timing chunks silently without one.
+    fn with_output(
+        config: VoiceoverConfig,
+        output: Option<(OutputStream, OutputStreamHandle, Sink)>,
+    ) -> Self {
+        let (chunk_finished_tx, chunk_finished_rx) = channel();
+        let (_stream, (stream_handle, sink)) = match output {
+            Some((stream, handle, sink)) => (Some(stream), (Some(handle), Some(sink))),
+            None => (None, (None, None)),
+        };
 
-        Ok(Self {
+        Self {
             config,
-            _stream: Some(_stream),
-            sink: Some(Arc::new(Mutex::new(sink))),
-            segment_queue: Arc::new(Mutex::new(VecDeque::new())),
+            _stream,
+            stream_handle,
+            sink: sink.map(|sink| Arc::new(Mutex::new(sink))),
             chunks: Arc::new(Mutex::new(std::collections::HashMap::new())),
             chunk_finished_tx,
             chunk_finished_rx: Arc::new(Mutex::new(chunk_finished_rx)),
-        })
+            playback_generation: Arc::new(AtomicUsize::new(0)),
+            silent_paused: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Handle to the narration output, for mixing other sounds into it
+    pub fn output_handle(&self) -> Option<OutputStreamHandle> {
+        self.stream_handle.clone()
     }
 
     /// Drain finished chunk IDs (non-blocking)
@@ -85,26 +124,32 @@ impl AudioPlayer {
             .unwrap_or_default()
     }
 
+    /// Number of chunks generated so far
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.lock().map(|g| g.len()).unwrap_or_default()
+    }
+
     /// Start playing a pre-generated audio chunk (non-blocking)
     pub fn trigger_chunk(&self, chunk_id: usize) {
-        if !self.config.enabled || self.sink.is_none() {
+        if !self.config.enabled {
             return;
         }
-        eprintln!("[AUDIO] trigger_chunk({})", chunk_id);
+        log::debug!("trigger_chunk({})", chunk_id);
         let chunks = self.chunks.clone();
         let sink = self.sink.clone();
         let tx = self.chunk_finished_tx.clone();
+        let speed = self.config.narration_speed;
+        let generation = self.playback_generation.clone();
+        let started_generation = generation.load(Ordering::SeqCst);
+        let silent_paused = self.silent_paused.clone();
 
         thread::spawn(move || {
             let chunk = chunks.lock().ok().and_then(|g| g.get(&chunk_id).cloned());
             if let Some(chunk) = chunk {
-                eprintln!(
-                    "[AUDIO] Chunk {} found, has_audio={}",
-                    chunk_id, chunk.has_audio
-                );
+                log::debug!("Chunk {} found, has_audio={}", chunk_id, chunk.has_audio);
                 if let Some(audio_data) = chunk.audio_data {
-                    eprintln!(
-                        "[AUDIO] Chunk {} starting playback ({} bytes)",
+                    log::debug!(
+                        "Chunk {} starting playback ({} bytes)",
                         chunk_id,
                         audio_data.len()
                     );
@@ -116,7 +161,9 @@ impl AudioPlayer {
                             let Ok(source) = Decoder::new(cursor) else {
                                 return;
                             };
-                            guard.append(source);
+                            // Completion is detected from the sink draining below,
+                            // so it tracks the sped-up playback automatically
+                            guard.append(source.speed(speed));
                             guard.play();
                         } // Lock released
 
@@ -129,19 +176,64 @@ impl AudioPlayer {
                         while !sink_arc.lock().map(|guard| guard.empty()).unwrap_or(true) {
                             thread::sleep(std::time::Duration::from_millis(50));
                         }
+                    } else {
+                        // No device: let the chunk's length pass, holding while paused
+                        let step = std::time::Duration::from_millis(50);
+                        let mut remaining = std::time::Duration::from_secs_f32(
+                            chunk.audio_duration_secs / speed.max(f32::EPSILON),
+                        );
+                        while !remaining.is_zero()
+                            && generation.load(Ordering::SeqCst) == started_generation
+                        {
+                            thread::sleep(step);
+                            if !silent_paused.load(Ordering::SeqCst) {
+                                remaining = remaining.saturating_sub(step);
+                            }
+                        }
+                    }
 
-                        eprintln!("[AUDIO] Chunk {} finished playback", chunk_id);
-                        let _ = tx.send(chunk_id);
+                    if generation.load(Ordering::SeqCst) != started_generation {
+                        log::debug!("Chunk {} interrupted", chunk_id);
+                        return;
                     }
+                    log::debug!("Chunk {} finished playback", chunk_id);
+                    let _ = tx.send(chunk_id);
                 } else {
-                    eprintln!("[AUDIO] Chunk {} has no audio_data", chunk_id);
+                    log::debug!("Chunk {} has no audio_data", chunk_id);
                 }
             } else {
-                eprintln!("[AUDIO] Chunk {} not found in chunks map", chunk_id);
+                log::debug!("Chunk {} not found in chunks map", chunk_id);
             }
         });
     }
 
+    /// Stop any narration in progress without reporting the chunks as finished (used when seeking).
+    pub fn stop_chunks(&self) {
+        self.playback_generation.fetch_add(1, Ordering::SeqCst);
+        if let Some(arc) = &self.sink {
+            if let Ok(sink) = arc.lock() {
+                sink.clear();
+            }
+        }
+    }
+
+    /// Set narration loudness, clamped to 0.0-1.5 (above 1.0 may clip).
+    pub fn set_volume(&self, volume: f32) {
+        if let Some(arc) = &self.sink {
+            if let Ok(sink) = arc.lock() {
+                sink.set_volume(volume.clamp(0.0, MAX_VOLUME));
+            }
+        }
+    }
+
+    /// Current narration loudness (the configured value when audio is disabled).
+    pub fn volume(&self) -> f32 {
+        self.sink
+            .as_ref()
+            .and_then(|arc| arc.lock().ok().map(|sink| sink.volume()))
+            .unwrap_or(self.config.volume)
+    }
+
     /// Access the voiceover config (for use outside the player).
     pub fn voiceover_config(&self) -> &VoiceoverConfig {
         &self.config
@@ -152,38 +244,8 @@ impl AudioPlayer {
         self.chunks.clone()
     }
 
-    /// Trigger a queued voiceover segment (e.g. on file open)
-    pub fn trigger_voiceover(&self, trigger_type: VoiceoverTrigger) {
-        if !self.config.enabled || self.sink.is_none() {
-            return;
-        }
-        let queue = self.segment_queue.clone();
-        let sink = self.sink.clone();
-
-        thread::spawn(move || {
-            let segment = queue.lock().ok().and_then(|mut q| {
-                q.iter()
-                    .position(|s| s.trigger_type == trigger_type)
-                    .map(|i| q.remove(i).unwrap())
-            });
-
-            if let Some(seg) = segment {
-                if let Some(audio_data) = seg.audio_data {
-                    if let Some(sink_arc) = sink {
-                        if let Ok(guard) = sink_arc.lock() {
-                            let cursor = std::io::Cursor::new(audio_data);
-                            if let Ok(source) = Decoder::new(cursor) {
-                                guard.append(source);
-                                guard.play();
-                            }
-                        }
-                    }
-                }
-            }
-        });
-    }
-
     pub fn pause(&self) {
+        self.silent_paused.store(true, Ordering::SeqCst);
         if let Some(arc) = &self.sink {
             if let Ok(sink) = arc.lock() {
                 sink.pause();
@@ -192,6 +254,7 @@ impl AudioPlayer {
     }
 
     pub fn resume(&self) {
+        self.silent_paused.store(false, Ordering::SeqCst);
         if let Some(arc) = &self.sink {
             if let Ok(sink) = arc.lock() {
                 sink.play();
@@ -200,42 +263,91 @@ impl AudioPlayer {
     }
 }
 
+/// Default audio output, its handle and a playing sink on it at `volume`.
+fn open_output(volume: f32) -> Result<(OutputStream, OutputStreamHandle, Sink)> {
+    log::debug!("Creating OutputStream...");
+    let (stream, stream_handle) =
+        OutputStream::try_default().context("Failed to create audio output stream")?;
+    log::debug!("OutputStream created successfully");
+    let sink = Sink::try_new(&stream_handle).context("Failed to create audio sink")?;
+    sink.set_volume(volume.clamp(0.0, MAX_VOLUME));
+    sink.play();
+    Ok((stream, stream_handle, sink))
+}
+
 /// Pre-generate all audio chunks with progress reporting.
+/// Chunks are published as soon as each is synthesized, so setting
+/// `cancel` stops generation early but keeps whatever narration was finished.
+#[allow(clippy::too_many_arguments)]
 pub fn generate_audio_chunks_with_progress(
     config: VoiceoverConfig,
     chunks_map: Arc<Mutex<std::collections::HashMap<usize, DiffChunk>>>,
-    message: String,
+    commit_hash: String,
+    commit: CommitContext,
     file_changes: Vec<(String, String, FileStatus)>,
-    speed_ms: u64,
-    progress: Arc<Mutex<(String, f32)>>,
+    file_order: FileOrder,
+    timing: llm::AnimationTiming,
+    progress: Arc<Mutex<GenerationProgress>>,
+    cancel: Arc<AtomicBool>,
 ) -> Vec<DiffChunk> {
     let _ = progress
         .lock()
-        .map(|mut p| *p = ("Analyzing repository...".to_string(), 0.0));
+        .map(|mut p| *p = ("Analyzing repository...".to_string(), 0.0, None));
+    let tts = tts::provider(&config);
     generate_audio_chunks_impl(
-        config,
+        &config,
+        tts.as_ref(),
         chunks_map,
-        message,
+        commit_hash,
+        commit,
         file_changes,
-        speed_ms,
+        file_order,
+        timing,
         Some(progress),
+        cancel,
     )
 }
 
+/// Publish generated chunks for playback unless generation was cancelled.
+/// The flag is checked under the lock so a cancelling caller can clear the map afterwards.
+fn publish_chunks(
+    chunks_map: &Mutex<std::collections::HashMap<usize, DiffChunk>>,
+    chunks: &[DiffChunk],
+    cancel: &AtomicBool,
+) {
+    if let Ok(mut guard) = chunks_map.lock() {
+        if cancel.load(Ordering::SeqCst) {
+            return;
+        }
+        for chunk in chunks {
+            guard.insert(chunk.chunk_id, chunk.clone());
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn generate_audio_chunks_impl(
-    config: VoiceoverConfig,
+    config: &VoiceoverConfig,
+    tts: &dyn TtsProvider,
     chunks_map: Arc<Mutex<std::collections::HashMap<usize, DiffChunk>>>,
-    message: String,
+    commit_hash: String,
+    commit: CommitContext,
     file_changes: Vec<(String, String, FileStatus)>,
-    speed_ms: u64,
-    progress: Option<Arc<Mutex<(String, f32)>>>,
+    file_order: FileOrder,
+    timing: llm::AnimationTiming,
+    progress: Option<Arc<Mutex<GenerationProgress>>>,
+    cancel: Arc<AtomicBool>,
 ) -> Vec<DiffChunk> {
-    eprintln!(
-        "[AUDIO GEN] Starting audio generation, {} file changes",
+    log::debug!(
+        "Starting audio generation, {} file changes",
         file_changes.len()
     );
-    if !config.enabled || config.api_key.is_none() {
-        eprintln!("[AUDIO GEN] Audio disabled or no API key, returning empty");
+    if !config.enabled || !config.has_tts_key() {
+        log::debug!("Audio disabled or no API key, returning empty");
+        return Vec::new();
+    }
+    if config.max_files == Some(0) {
+        log::debug!("max_files is 0, skipping narration");
         return Vec::new();
     }
 
@@ -244,73 +356,76 @@ fn generate_audio_chunks_impl(
         guard.clear();
     }
 
-    eprintln!("[AUDIO GEN] Creating tokio runtime...");
+    let cache_dir = cache::chunk_dir(config, &commit_hash, timing, file_order);
+    if config.cache {
+        if let Some(cached) = cache_dir.as_deref().and_then(cache::load) {
+            log::debug!("Loaded {} chunks from cache", cached.len());
+            publish_chunks(&chunks_map, &cached, &cancel);
+            if let Some(ref p) = progress {
+                let _ = p
+                    .lock()
+                    .map(|mut s| *s = ("Loaded narration from cache".to_string(), 1.0, None));
+            }
+            return cached;
+        }
+    }
+
+    log::debug!("Creating tokio runtime...");
     let rt = match tokio::runtime::Runtime::new() {
         Ok(rt) => {
-            eprintln!("[AUDIO GEN] Tokio runtime created");
+            log::debug!("Tokio runtime created");
             rt
         }
         Err(e) => {
-            eprintln!("[AUDIO GEN] Failed to create tokio runtime: {:?}", e);
+            log::warn!("Failed to create tokio runtime: {:?}", e);
             return Vec::new();
         }
     };
 
-    eprintln!("[AUDIO GEN] Entering async block...");
+    log::debug!("Entering async block...");
     rt.block_on(async {
-        eprintln!("[AUDIO GEN] Inside async block, starting project context generation...");
+        log::debug!("Inside async block, starting project context generation...");
         if let Some(ref p) = progress {
-            let _ = p
-                .lock()
-                .map(|mut s| *s = ("Generating project context with GPT...".to_string(), 0.05));
+            let _ = p.lock().map(|mut s| {
+                *s = (
+                    "Generating project context with GPT...".to_string(),
+                    0.05,
+                    None,
+                )
+            });
         }
 
-        eprintln!("[AUDIO GEN] Calling extract_project_context...");
+        log::debug!("Calling extract_project_context...");
         let mut project_context = llm::extract_project_context();
-        eprintln!(
-            "[AUDIO GEN] Project context extracted: {}",
-            project_context.repo_name
-        );
+        log::debug!("Project context extracted: {}", project_context.repo_name);
 
-        if config.use_llm_explanations && config.openai_api_key.is_some() {
-            eprintln!("[AUDIO GEN] Calling LLM to generate project description...");
-            match llm::generate_project_context_with_llm(&config).await {
+        if config.use_llm_explanations && config.has_llm_access() {
+            log::debug!("Calling LLM to generate project description...");
+            match llm::generate_project_context_with_llm(config).await {
                 Ok(desc) => {
-                    eprintln!("[AUDIO GEN] LLM project description received");
+                    log::debug!("LLM project description received");
                     project_context.description = desc;
                 }
                 Err(e) => {
-                    eprintln!("[AUDIO GEN] LLM project description failed: {:?}", e);
-                    return Vec::new();
+                    log::warn!(
+                        "LLM project description failed, describing the commit instead: {:?}",
+                        e
+                    );
+                    project_context.description = llm::fallback_project_description(
+                        &project_context.repo_name,
+                        &commit.message,
+                        &file_changes,
+                    );
                 }
             }
         } else {
-            eprintln!("[AUDIO GEN] LLM disabled or no API key");
+            log::debug!("LLM disabled or no API key");
             return Vec::new();
         }
 
         let important_files: Vec<(String, String, FileStatus)> = file_changes
             .into_iter()
-            .filter(|(name, _, _)| {
-                // Exclude lock files
-                !name.contains("package-lock.json")
-                    && !name.contains("yarn.lock")
-                    && !name.contains("pnpm-lock.yaml")
-                    && !name.ends_with(".lock")
-                    // Exclude JSON config/data files
-                    && !name.ends_with(".json")
-                    // Exclude Xcode project files
-                    && !name.ends_with(".xcodeproj")
-                    && !name.ends_with(".pbxproj")
-                    && !name.ends_with(".xcworkspace")
-                    // Exclude IDE/editor config
-                    && !name.contains(".vscode/")
-                    && !name.contains(".idea/")
-                    // Exclude build artifacts
-                    && !name.contains("/dist/")
-                    && !name.contains("/build/")
-                    && !name.contains("/target/")
-            })
+            .filter(|(name, _, _)| is_narrated_file(name))
             .collect();
 
         if let Some(ref p) = progress {
@@ -321,36 +436,106 @@ fn generate_audio_chunks_impl(
                         important_files.len()
                     ),
                     0.1,
+                    None,
                 )
             });
         }
 
-        let ordered = llm::order_files_by_development_flow(
-            &config,
+        let mut ordered = order_files(
+            config,
             &project_context,
-            &message,
-            &important_files,
+            &commit.message,
+            important_files,
+            file_order,
         )
         .await;
+        if let Some(max) = config.max_files {
+            ordered.truncate(max);
+        }
 
         let mut all_chunks: Vec<DiffChunk> = Vec::new();
         let mut global_id = 0usize;
         let total_files = ordered.len();
+        let timeouts = AtomicUsize::new(0);
+        let files_started = Instant::now();
+        // Set when an explanation or intro failed, so the partial result isn't cached
+        let mut incomplete = false;
+
+        if config.commit_intro && !ordered.is_empty() {
+            if let Some(ref p) = progress {
+                let _ = p.lock().map(|mut s| {
+                    *s = (
+                        with_timeouts("Writing commit intro...".to_string(), &timeouts, config),
+                        0.12,
+                        None,
+                    )
+                });
+            }
+            let sentence = match chunker::commit_intro(
+                config,
+                &project_context,
+                &commit,
+                &commit_hash,
+                &ordered,
+            )
+            .await
+            {
+                Ok(sentence) => sentence,
+                Err(e) => {
+                    log::warn!("Commit intro failed, using the commit subject: {:#}", e);
+                    count_timeout(&e, &timeouts);
+                    incomplete = true;
+                    chunker::commit_lead(config, &commit, &commit_hash)
+                }
+            };
+            let mut intro = vec![narration_chunk(COMMIT_INTRO_PATH, sentence)];
+            let voiced = voice_chunks(
+                config,
+                tts,
+                &mut intro,
+                global_id,
+                &cancel,
+                &timeouts,
+                |_| {},
+                |chunk| publish_chunks(&chunks_map, std::slice::from_ref(chunk), &cancel),
+            )
+            .await;
+            let Some(next_id) = voiced else {
+                log::debug!("Cancelled");
+                return Vec::new();
+            };
+            global_id = next_id;
+            all_chunks.extend(intro);
+        }
 
         for (i, (filename, diff, _)) in ordered.iter().enumerate() {
+            if cancel.load(Ordering::SeqCst) {
+                log::debug!("Cancelled");
+                return Vec::new();
+            }
+
             // Progress: 15% to 95% based on file processing
             let file_progress = 0.15 + (0.80 * (i as f32 / total_files.max(1) as f32));
+            // A finish time rather than a duration, so the countdown keeps moving
+            // while a single slow file is narrated
+            let eta = remaining_time(files_started.elapsed(), i, total_files)
+                .map(|left| Instant::now() + left);
 
             if let Some(ref p) = progress {
                 let _ = p.lock().map(|mut s| {
                     *s = (
-                        format!(
-                            "Processing file {}/{}: {}",
-                            i + 1,
-                            total_files,
-                            filename.rsplit('/').next().unwrap_or(filename)
+                        with_timeouts(
+                            format!(
+                                "Processing file {}/{}: {}",
+                                i + 1,
+                                total_files,
+                                filename.rsplit('/').next().unwrap_or(filename)
+                            ),
+                            &timeouts,
+                            config,
                         ),
                         file_progress,
+                        eta,
                     )
                 });
             }
@@ -358,67 +543,425 @@ fn generate_audio_chunks_impl(
                 tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
             }
 
-            if let Ok(mut file_chunks) = chunker::split_diff_into_chunks(
-                &config,
+            let file_chunks = chunker::split_diff_into_chunks(
+                config,
                 &project_context,
-                &message,
+                &commit,
                 filename,
                 diff,
-                speed_ms,
+                timing,
             )
-            .await
-            {
-                for chunk in &mut file_chunks {
-                    chunk.chunk_id = global_id;
-                    global_id += 1;
+            .await;
+            let mut file_chunks = match file_chunks {
+                Ok(chunks) => chunks,
+                Err(e) => {
+                    log::warn!("Skipping narration for {}: {:#}", filename, e);
+                    count_timeout(&e, &timeouts);
+                    incomplete = true;
+                    continue;
+                }
+            };
 
-                    let word_count = chunk.explanation.split_whitespace().count();
+            // The intro narrates the whole file, so it plays as the file opens
+            if config.file_intros {
+                match chunker::file_intro(config, &project_context, &commit, filename, diff).await {
+                    Ok(sentence) => file_chunks.insert(0, narration_chunk(filename, sentence)),
+                    Err(e) => {
+                        log::warn!("No intro for {}: {:#}", filename, e);
+                        count_timeout(&e, &timeouts);
+                        incomplete = true;
+                    }
+                }
+            }
 
+            let voiced = voice_chunks(
+                config,
+                tts,
+                &mut file_chunks,
+                global_id,
+                &cancel,
+                &timeouts,
+                |chunk| {
                     if let Some(ref p) = progress {
                         let _ = p.lock().map(|mut s| {
                             *s = (
-                                format!(
-                                    "Synthesizing audio {}/{}: {} (chunk {})",
-                                    i + 1,
-                                    total_files,
-                                    filename.rsplit('/').next().unwrap_or(filename),
-                                    chunk.chunk_id + 1
+                                with_timeouts(
+                                    format!(
+                                        "Synthesizing audio {}/{}: {} (chunk {})",
+                                        i + 1,
+                                        total_files,
+                                        filename.rsplit('/').next().unwrap_or(filename),
+                                        chunk.chunk_id + 1
+                                    ),
+                                    &timeouts,
+                                    config,
                                 ),
                                 file_progress,
+                                eta,
                             )
                         });
                     }
+                },
+                // Published one by one so an interrupted run still narrates what finished
+                |chunk| publish_chunks(&chunks_map, std::slice::from_ref(chunk), &cancel),
+            )
+            .await;
+            let Some(next_id) = voiced else {
+                log::debug!("Cancelled");
+                return Vec::new();
+            };
+            global_id = next_id;
+            all_chunks.extend(file_chunks);
+        }
 
-                    tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
-
-                    if let Ok(audio_data) =
-                        tts::synthesize_speech_from_text(&config, &chunk.explanation).await
-                    {
-                        // Use estimated duration based on word count instead of decoding
-                        // to avoid potential audio device conflicts
-                        chunk.audio_duration_secs = (word_count as f32) / 2.5;
-                        chunk.audio_data = Some(audio_data);
-                        chunk.has_audio = true;
+        if config.commit_intro && !ordered.is_empty() {
+            if let Some(ref p) = progress {
+                let _ = p.lock().map(|mut s| {
+                    *s = (
+                        with_timeouts("Writing commit outro...".to_string(), &timeouts, config),
+                        0.95,
+                        None,
+                    )
+                });
+            }
+            match chunker::commit_outro(config, &project_context, &commit, &ordered).await {
+                Ok(sentence) => {
+                    let mut outro = vec![narration_chunk(COMMIT_OUTRO_PATH, sentence)];
+                    let voiced = voice_chunks(
+                        config,
+                        tts,
+                        &mut outro,
+                        global_id,
+                        &cancel,
+                        &timeouts,
+                        |_| {},
+                        |chunk| publish_chunks(&chunks_map, std::slice::from_ref(chunk), &cancel),
+                    )
+                    .await;
+                    if voiced.is_none() {
+                        log::debug!("Cancelled");
+                        return Vec::new();
                     }
+                    all_chunks.extend(outro);
+                }
+                Err(e) => {
+                    log::warn!("No commit outro: {:#}", e);
+                    count_timeout(&e, &timeouts);
+                    incomplete = true;
                 }
-                all_chunks.extend(file_chunks);
             }
         }
 
-        if let Ok(mut guard) = chunks_map.lock() {
-            for chunk in &all_chunks {
-                guard.insert(chunk.chunk_id, chunk.clone());
+        // A brief outage shouldn't come back as missing narration on every later run
+        let complete = !incomplete && all_chunks.iter().all(|c| c.has_audio);
+        if let Some(dir) = cache_dir.filter(|_| complete && !all_chunks.is_empty()) {
+            if let Err(e) = cache::store(&dir, &all_chunks) {
+                log::warn!("Failed to cache chunks: {:?}", e);
             }
         }
 
         if let Some(ref p) = progress {
-            let _ = p.lock().map(|mut s| *s = ("Complete!".to_string(), 1.0));
+            let _ = p
+                .lock()
+                .map(|mut s| *s = ("Complete!".to_string(), 1.0, None));
         }
 
         all_chunks
     })
 }
 
+/// Time left to narrate `total` files after `done` took `elapsed`, assuming the
+/// rest take as long on average. `None` until a file has finished.
+fn remaining_time(elapsed: Duration, done: usize, total: usize) -> Option<Duration> {
+    if done == 0 {
+        return None;
+    }
+    Some(elapsed / done as u32 * total.saturating_sub(done) as u32)
+}
+
+/// Tally a failed request in `timeouts` if it timed out.
+fn count_timeout(err: &anyhow::Error, timeouts: &AtomicUsize) {
+    if retry::is_timeout(err) {
+        timeouts.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Progress status with a note on timed-out requests, kept on every later update
+/// so a provider that stopped responding doesn't go unnoticed.
+fn with_timeouts(status: String, timeouts: &AtomicUsize, config: &VoiceoverConfig) -> String {
+    match timeouts.load(Ordering::SeqCst) {
+        0 => status,
+        n => format!(
+            "{} ({} request{} timed out after {}s)",
+            status,
+            n,
+            if n == 1 { "" } else { "s" },
+            config.request_timeout().as_secs()
+        ),
+    }
+}
+
+/// Number a file's chunks from `next_id` on and synthesize each explanation,
+/// calling `on_chunk` before each one and `on_voiced` once it is done. Chunks
+/// whose synthesis fails stay silent, and those that timed out are counted in
+/// `timeouts`.
+/// Returns the next free id, or None if generation was cancelled.
+#[allow(clippy::too_many_arguments)]
+async fn voice_chunks(
+    config: &VoiceoverConfig,
+    tts: &dyn TtsProvider,
+    chunks: &mut [DiffChunk],
+    mut next_id: usize,
+    cancel: &AtomicBool,
+    timeouts: &AtomicUsize,
+    mut on_chunk: impl FnMut(&DiffChunk),
+    mut on_voiced: impl FnMut(&DiffChunk),
+) -> Option<usize> {
+    for chunk in chunks {
+        chunk.chunk_id = next_id;
+        next_id += 1;
+        on_chunk(chunk);
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
+        if cancel.load(Ordering::SeqCst) {
+            return None;
+        }
+
+        if let Err(e) = voice_chunk(config, tts, chunk).await {
+            log::warn!(
+                "No audio for chunk {} of {}: {:#}",
+                chunk.chunk_id,
+                chunk.file_path,
+                e
+            );
+            count_timeout(&e, timeouts);
+        }
+        on_voiced(chunk);
+    }
+    Some(next_id)
+}
+
+/// Synthesize a chunk's explanation and keep the audio along with its length,
+/// measured from the audio where the format allows and otherwise estimated from
+/// the speaking rate.
+async fn voice_chunk(
+    config: &VoiceoverConfig,
+    tts: &dyn TtsProvider,
+    chunk: &mut DiffChunk,
+) -> Result<()> {
+    let audio =
+        retry::with_backoff("Speech synthesis", || tts.synthesize(&chunk.explanation)).await?;
+    chunk.audio_duration_secs =
+        audio_duration_secs(&audio).unwrap_or_else(|| config.speech_secs(&chunk.explanation));
+    chunk.audio_data = Some(audio);
+    chunk.has_audio = true;
+    Ok(())
+}
+
+/// Length of encoded audio, when its format records one (WAV does, MP3 streams don't).
+fn audio_duration_secs(audio: &[u8]) -> Option<f32> {
+    let source = Decoder::new(std::io::Cursor::new(audio.to_vec())).ok()?;
+    source.total_duration().map(|d| d.as_secs_f32())
+}
+
+/// Unvoiced chunk narrating `path` as a whole rather than any of its hunks, as
+/// file intros and the commit intro and outro do.
+fn narration_chunk(path: &str, explanation: String) -> DiffChunk {
+    DiffChunk {
+        chunk_id: 0,
+        file_path: path.to_string(),
+        hunk_indices: Vec::new(),
+        explanation,
+        audio_data: None,
+        has_audio: false,
+        audio_duration_secs: 0.0,
+    }
+}
+
+/// Arrange files for narration. `file_changes` arrive in playback order, which
+/// `alpha` and `size` keep; the flow orders ask the LLM.
+async fn order_files(
+    config: &VoiceoverConfig,
+    project_context: &types::ProjectContext,
+    commit_message: &str,
+    files: Vec<(String, String, FileStatus)>,
+    file_order: FileOrder,
+) -> Vec<(String, String, FileStatus)> {
+    match file_order {
+        FileOrder::Alpha | FileOrder::Size => files,
+        FileOrder::Flow | FileOrder::ReverseFlow => {
+            let mut ordered = llm::order_files_by_development_flow(
+                config,
+                project_context,
+                commit_message,
+                &files,
+            )
+            .await;
+            if file_order == FileOrder::ReverseFlow {
+                ordered.reverse();
+            }
+            ordered
+        }
+    }
+}
+
+/// Whether a file is worth narrating (lock files, data/config files, IDE
+/// settings and build artifacts are skipped).
+fn is_narrated_file(name: &str) -> bool {
+    // Exclude lock files
+    !name.contains("package-lock.json")
+        && !name.contains("yarn.lock")
+        && !name.contains("pnpm-lock.yaml")
+        && !name.ends_with(".lock")
+        // Exclude JSON config/data files
+        && !name.ends_with(".json")
+        // Exclude Xcode project files
+        && !name.ends_with(".xcodeproj")
+        && !name.ends_with(".pbxproj")
+        && !name.ends_with(".xcworkspace")
+        // Exclude IDE/editor config
+        && !name.contains(".vscode/")
+        && !name.contains(".idea/")
+        // Exclude build artifacts
+        && !name.contains("/dist/")
+        && !name.contains("/build/")
+        && !name.contains("/target/")
+}
+
+/// Order a commit's files by development flow for reading, with a single LLM
+/// request and nothing explained or voiced. The project is described from the
+/// commit rather than by the LLM. Files that wouldn't be narrated (lock files,
+/// build output, ...) are left out; they come back second, in their given order.
+pub fn suggest_reading_order(
+    config: &VoiceoverConfig,
+    commit: &CommitContext,
+    file_changes: FileChanges,
+    file_order: FileOrder,
+) -> Result<(FileChanges, FileChanges)> {
+    let mut project_context = llm::extract_project_context();
+    project_context.description = llm::fallback_project_description(
+        &project_context.repo_name,
+        &commit.message,
+        &file_changes,
+    );
+    let (narrated, skipped) = file_changes
+        .into_iter()
+        .partition(|(name, _, _)| is_narrated_file(name));
+
+    let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
+    let ordered = rt.block_on(order_files(
+        config,
+        &project_context,
+        &commit.message,
+        narrated,
+        file_order,
+    ));
+    Ok((ordered, skipped))
+}
+
+/// Generate the per-chunk explanations for a commit without synthesizing speech
+/// (used for written reports). Chunks come back in narration order, with the file
+/// intros and the commit intro and outro when the config narrates them; files
+/// whose explanation fails are skipped.
+pub fn explain_commit(
+    config: &VoiceoverConfig,
+    commit: &CommitContext,
+    commit_hash: &str,
+    file_changes: Vec<(String, String, FileStatus)>,
+    file_order: FileOrder,
+    timing: llm::AnimationTiming,
+) -> Result<Vec<DiffChunk>> {
+    let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
+    rt.block_on(async {
+        let mut project_context = llm::extract_project_context();
+        project_context.description = match llm::generate_project_context_with_llm(config).await {
+            Ok(desc) => desc,
+            Err(e) => {
+                log::warn!(
+                    "LLM project description failed, describing the commit instead: {:?}",
+                    e
+                );
+                llm::fallback_project_description(
+                    &project_context.repo_name,
+                    &commit.message,
+                    &file_changes,
+                )
+            }
+        };
+
+        let important_files: Vec<(String, String, FileStatus)> = file_changes
+            .into_iter()
+            .filter(|(name, _, _)| is_narrated_file(name))
+            .collect();
+        let mut ordered = order_files(
+            config,
+            &project_context,
+            &commit.message,
+            important_files,
+            file_order,
+        )
+        .await;
+        if let Some(max) = config.max_files {
+            ordered.truncate(max);
+        }
+
+        let mut all_chunks = Vec::new();
+        if config.commit_intro && !ordered.is_empty() {
+            let sentence = match chunker::commit_intro(
+                config,
+                &project_context,
+                commit,
+                commit_hash,
+                &ordered,
+            )
+            .await
+            {
+                Ok(sentence) => sentence,
+                Err(e) => {
+                    log::warn!("Commit intro failed, using the commit subject: {:#}", e);
+                    chunker::commit_lead(config, commit, commit_hash)
+                }
+            };
+            all_chunks.push(narration_chunk(COMMIT_INTRO_PATH, sentence));
+        }
+        for (filename, diff, _) in &ordered {
+            let chunks = match chunker::split_diff_into_chunks(
+                config,
+                &project_context,
+                commit,
+                filename,
+                diff,
+                timing,
+            )
+            .await
+            {
+                Ok(chunks) => chunks,
+                Err(e) => {
+                    log::warn!("Skipping {}: {:#}", filename, e);
+                    continue;
+                }
+            };
+            if config.file_intros {
+                match chunker::file_intro(config, &project_context, commit, filename, diff).await {
+                    Ok(sentence) => all_chunks.push(narration_chunk(filename, sentence)),
+                    Err(e) => log::warn!("No intro for {}: {:#}", filename, e),
+                }
+            }
+            all_chunks.extend(chunks);
+        }
+        if config.commit_intro && !ordered.is_empty() {
+            match chunker::commit_outro(config, &project_context, commit, &ordered).await {
+                Ok(sentence) => all_chunks.push(narration_chunk(COMMIT_OUTRO_PATH, sentence)),
+                Err(e) => log::warn!("No commit outro: {:#}", e),
+            }
+        }
+        for (id, chunk) in all_chunks.iter_mut().enumerate() {
+            chunk.chunk_id = id;
+        }
+        Ok(all_chunks)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -435,6 +978,153 @@ mod tests {
         }
     }
 
+    const MOCK_SAMPLE_RATE: u32 = 8000;
+
+    /// Speaks half a second of silence per word, or fails on text containing "fail".
+    struct MockTts;
+
+    impl TtsProvider for MockTts {
+        fn synthesize<'a>(&'a self, text: &'a str) -> tts::SpeechFuture<'a> {
+            Box::pin(async move {
+                anyhow::ensure!(!text.contains("fail"), "mock synthesis failed");
+                let secs = text.split_whitespace().count() as f32 * 0.5;
+                Ok(silent_wav((secs * MOCK_SAMPLE_RATE as f32) as u32))
+            })
+        }
+    }
+
+    /// 16-bit mono PCM WAV of `samples` zero samples.
+    fn silent_wav(samples: u32) -> Vec<u8> {
+        let data_len = samples * 2;
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+        wav.extend_from_slice(&MOCK_SAMPLE_RATE.to_le_bytes());
+        wav.extend_from_slice(&(MOCK_SAMPLE_RATE * 2).to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        wav.resize(wav.len() + data_len as usize, 0);
+        wav
+    }
+
+    fn unvoiced(file: &str, explanation: &str) -> DiffChunk {
+        DiffChunk {
+            chunk_id: 0,
+            file_path: file.to_string(),
+            hunk_indices: vec![0],
+            explanation: explanation.to_string(),
+            audio_data: None,
+            has_audio: false,
+            audio_duration_secs: 0.0,
+        }
+    }
+
+    #[test]
+    fn voiced_chunks_are_numbered_across_files_and_timed_from_their_audio() {
+        let config = VoiceoverConfig::default();
+        let cancel = AtomicBool::new(false);
+        let timeouts = AtomicUsize::new(0);
+        let mut first = vec![
+            unvoiced("src/a.rs", "one two three four"),
+            unvoiced("src/a.rs", "please fail here"),
+        ];
+        let mut second = vec![unvoiced("src/b.rs", "five six")];
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let mut seen = Vec::new();
+        let mut voiced = Vec::new();
+        let next = rt
+            .block_on(voice_chunks(
+                &config,
+                &MockTts,
+                &mut first,
+                0,
+                &cancel,
+                &timeouts,
+                |c| seen.push(c.chunk_id),
+                |c| voiced.push((c.chunk_id, c.has_audio)),
+            ))
+            .unwrap();
+        let last = rt
+            .block_on(voice_chunks(
+                &config,
+                &MockTts,
+                &mut second,
+                next,
+                &cancel,
+                &timeouts,
+                |c| seen.push(c.chunk_id),
+                |c| voiced.push((c.chunk_id, c.has_audio)),
+            ))
+            .unwrap();
+
+        assert_eq!(seen, vec![0, 1, 2]);
+        // Each chunk is handed over once synthesized, silent ones included
+        assert_eq!(voiced, vec![(0, true), (1, false), (2, true)]);
+        assert_eq!(last, 3);
+        assert!(first[0].has_audio);
+        assert!((first[0].audio_duration_secs - 2.0).abs() < 1e-3);
+        assert!((second[0].audio_duration_secs - 1.0).abs() < 1e-3);
+        // A failed synthesis leaves the chunk silent without stopping the others
+        assert!(!first[1].has_audio && first[1].audio_data.is_none());
+
+        // Audio without a recorded length falls back to the speaking-rate estimate
+        assert_eq!(audio_duration_secs(&[1, 2, 3]), None);
+
+        cancel.store(true, Ordering::SeqCst);
+        let mut rest = vec![unvoiced("src/c.rs", "seven")];
+        assert_eq!(
+            rt.block_on(voice_chunks(
+                &config,
+                &MockTts,
+                &mut rest,
+                last,
+                &cancel,
+                &timeouts,
+                |_| {},
+                |_| {}
+            )),
+            None
+        );
+        assert!(!rest[0].has_audio);
+    }
+
+    #[test]
+    fn remaining_time_extrapolates_the_average_file() {
+        assert_eq!(remaining_time(Duration::from_secs(5), 0, 4), None);
+        assert_eq!(
+            remaining_time(Duration::from_secs(30), 3, 7),
+            Some(Duration::from_secs(40))
+        );
+    }
+
+    #[test]
+    fn chunks_finish_on_time_without_an_audio_device() {
+        let config = VoiceoverConfig {
+            enabled: true,
+            ..VoiceoverConfig::default()
+        };
+        let player = AudioPlayer::with_output(config, None);
+        let mut short = chunk(7, "src/a.rs");
+        short.audio_duration_secs = 0.1;
+        player.chunks_handle().lock().unwrap().insert(7, short);
+
+        player.trigger_chunk(7);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let mut finished = Vec::new();
+        while finished.is_empty() && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            finished = player.poll_finished_chunks();
+        }
+        assert_eq!(finished, vec![7]);
+    }
+
     #[test]
     fn get_chunks_for_file_returns_only_matching_path() {
         let player = AudioPlayer::new(VoiceoverConfig::default())
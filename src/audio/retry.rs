@@ -0,0 +1,153 @@
+use anyhow::{Context, Result};
+use async_openai::error::OpenAIError;
+use std::future::Future;
+use std::time::Duration;
+
+use super::types::VoiceoverConfig;
+
+const MAX_ATTEMPTS: u32 = 3;
+const BASE_DELAY_MS: u64 = 500;
+
+/// Non-success HTTP response from a TTS provider
+#[derive(Debug)]
+pub struct HttpStatusError {
+    pub provider: &'static str,
+    pub status: reqwest::StatusCode,
+    pub body: String,
+}
+
+impl std::fmt::Display for HttpStatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} API error ({}): {}",
+            self.provider, self.status, self.body
+        )
+    }
+}
+
+impl std::error::Error for HttpStatusError {}
+
+/// HTTP client for provider requests, giving up after the configured timeout
+/// so a hung request can't stall narration indefinitely.
+pub fn http_client(config: &VoiceoverConfig) -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .timeout(config.request_timeout())
+        .build()
+        .context("Failed to create HTTP client")
+}
+
+/// Whether a request failed because it hit the timeout
+pub fn is_timeout(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            return e.is_timeout();
+        }
+        matches!(cause.downcast_ref::<OpenAIError>(), Some(OpenAIError::Reqwest(e)) if e.is_timeout())
+    })
+}
+
+/// Run `op` up to 3 times, backing off exponentially (0.5s, 1s) between attempts
+/// when it fails with a rate limit, server error or network error.
+/// The final error is returned with the operation label attached.
+pub async fn with_backoff<T, F, Fut>(label: &str, mut op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < MAX_ATTEMPTS && is_retryable(&e) => {
+                let delay = BASE_DELAY_MS * 2u64.pow(attempt - 1);
+                log::debug!(
+                    "{} failed (attempt {}/{}), retrying in {}ms: {:#}",
+                    label,
+                    attempt,
+                    MAX_ATTEMPTS,
+                    delay,
+                    e
+                );
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("{} failed after {} attempt(s)", label, attempt))
+            }
+        }
+    }
+}
+
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+fn is_retryable_reqwest(e: &reqwest::Error) -> bool {
+    e.is_timeout() || e.is_connect() || e.status().is_some_and(is_retryable_status)
+}
+
+fn is_retryable(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        if let Some(e) = cause.downcast_ref::<HttpStatusError>() {
+            return is_retryable_status(e.status);
+        }
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            return is_retryable_reqwest(e);
+        }
+        match cause.downcast_ref::<OpenAIError>() {
+            Some(OpenAIError::Reqwest(e)) => is_retryable_reqwest(e),
+            Some(OpenAIError::ApiError(e)) => {
+                e.code.as_deref() == Some("rate_limit_exceeded")
+                    || e.r#type.as_deref() == Some("server_error")
+            }
+            _ => false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn status_error(status: u16) -> anyhow::Error {
+        HttpStatusError {
+            provider: "Test",
+            status: reqwest::StatusCode::from_u16(status).unwrap(),
+            body: String::new(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn retries_server_errors_then_succeeds() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let calls = Cell::new(0);
+        let result = rt.block_on(with_backoff("test", || {
+            calls.set(calls.get() + 1);
+            let n = calls.get();
+            async move {
+                if n < 3 {
+                    Err(status_error(503))
+                } else {
+                    Ok(n)
+                }
+            }
+        }));
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn client_errors_are_not_retried() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let calls = Cell::new(0);
+        let result: Result<()> = rt.block_on(with_backoff("test", || {
+            calls.set(calls.get() + 1);
+            async { Err(status_error(401)) }
+        }));
+        assert!(result.is_err());
+        assert_eq!(calls.get(), 1);
+    }
+}
@@ -1,13 +1,89 @@
-use super::types::{VoiceoverConfig, VoiceoverProvider};
+use super::retry::{http_client, HttpStatusError};
+use super::types::{TtsFormat, VoiceoverConfig, VoiceoverProvider};
 use anyhow::{Context, Result};
 use base64::{engine::general_purpose, Engine as _};
+use std::future::Future;
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use std::pin::Pin;
+use std::process::{Command, Stdio};
 
-/// Dispatch TTS to the configured provider
-pub async fn synthesize_speech_from_text(config: &VoiceoverConfig, text: &str) -> Result<Vec<u8>> {
+/// Audio bytes for a piece of narration, as returned by a `TtsProvider`
+pub type SpeechFuture<'a> = Pin<Box<dyn Future<Output = Result<Vec<u8>>> + Send + 'a>>;
+
+/// Turns narration text into encoded audio (MP3 or WAV bytes that rodio can decode).
+pub trait TtsProvider: Send + Sync {
+    fn synthesize<'a>(&'a self, text: &'a str) -> SpeechFuture<'a>;
+}
+
+macro_rules! http_providers {
+    ($($(#[$doc:meta])* $name:ident => $synthesize:ident,)*) => {$(
+        $(#[$doc])*
+        pub struct $name<'a>(pub &'a VoiceoverConfig);
+
+        impl TtsProvider for $name<'_> {
+            fn synthesize<'a>(&'a self, text: &'a str) -> SpeechFuture<'a> {
+                Box::pin($synthesize(self.0, text))
+            }
+        }
+    )*};
+}
+
+http_providers! {
+    /// ElevenLabs text-to-speech
+    ElevenLabs => synthesize_elevenlabs,
+    /// Inworld text-to-speech
+    Inworld => synthesize_inworld,
+    /// OpenAI speech endpoint
+    OpenAi => synthesize_openai,
+    /// Google Cloud Text-to-Speech
+    GoogleTts => synthesize_google,
+    /// Azure Speech service
+    Azure => synthesize_azure,
+    /// Local `piper` binary, for narration that never leaves the machine
+    Piper => synthesize_piper,
+}
+
+/// The provider selected in `config`
+pub fn provider(config: &VoiceoverConfig) -> Box<dyn TtsProvider + '_> {
     match config.provider {
-        VoiceoverProvider::ElevenLabs => synthesize_elevenlabs(config, text).await,
-        VoiceoverProvider::Inworld => synthesize_inworld(config, text).await,
+        VoiceoverProvider::ElevenLabs => Box::new(ElevenLabs(config)),
+        VoiceoverProvider::Inworld => Box::new(Inworld(config)),
+        VoiceoverProvider::OpenAI => Box::new(OpenAi(config)),
+        VoiceoverProvider::GoogleTts => Box::new(GoogleTts(config)),
+        VoiceoverProvider::Azure => Box::new(Azure(config)),
+        VoiceoverProvider::Piper => Box::new(Piper(config)),
+    }
+}
+
+/// Make sure the Piper binary runs and its voice model exists, so a bad setup
+/// is reported once up front instead of failing every chunk.
+pub fn check_piper(config: &VoiceoverConfig) -> Result<()> {
+    let model = config.piper_model.as_deref().context(
+        "Piper narration needs a voice model (piper_model), e.g. en_US-lessac-medium.onnx",
+    )?;
+    if !Path::new(model).is_file() {
+        anyhow::bail!("Piper voice model not found: {}", model);
     }
+    let bin = piper_bin(config);
+    match Command::new(bin)
+        .arg("--help")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+    {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == ErrorKind::NotFound => anyhow::bail!(
+            "Piper binary '{}' not found; install piper or set piper_bin",
+            bin
+        ),
+        Err(e) => Err(e).with_context(|| format!("Failed to run Piper binary '{}'", bin)),
+    }
+}
+
+fn piper_bin(config: &VoiceoverConfig) -> &str {
+    config.piper_bin.as_deref().unwrap_or("piper")
 }
 
 async fn synthesize_elevenlabs(config: &VoiceoverConfig, text: &str) -> Result<Vec<u8>> {
@@ -21,9 +97,10 @@ async fn synthesize_elevenlabs(config: &VoiceoverConfig, text: &str) -> Result<V
 
     let url = format!("https://api.elevenlabs.io/v1/text-to-speech/{}", voice_id);
 
-    let client = reqwest::Client::new();
+    let client = http_client(config)?;
     let response = client
         .post(&url)
+        .query(&[("output_format", elevenlabs_output_format(config))])
         .header("xi-api-key", api_key)
         .header("Content-Type", "application/json")
         .json(&serde_json::json!({
@@ -38,10 +115,43 @@ async fn synthesize_elevenlabs(config: &VoiceoverConfig, text: &str) -> Result<V
         .await
         .context("Failed to send request to ElevenLabs API")?;
 
-    if !response.status().is_success() {
-        let error_text = response.text().await.unwrap_or_default();
-        anyhow::bail!("ElevenLabs API error: {}", error_text);
-    }
+    let response = check_status(response, "ElevenLabs").await?;
+
+    response
+        .bytes()
+        .await
+        .context("Failed to read audio response")
+        .map(|b| b.to_vec())
+}
+
+async fn synthesize_openai(config: &VoiceoverConfig, text: &str) -> Result<Vec<u8>> {
+    let api_key = config
+        .openai_api_key
+        .as_ref()
+        .context("OpenAI API key not configured")?;
+
+    let voice_id = config.voice_id.as_deref().unwrap_or("alloy");
+    let model_id = config.model_id.as_deref().unwrap_or("tts-1");
+
+    let client = http_client(config)?;
+    let response = client
+        .post("https://api.openai.com/v1/audio/speech")
+        .bearer_auth(api_key)
+        .header("Content-Type", "application/json")
+        .json(&serde_json::json!({
+            "model": model_id,
+            "voice": voice_id,
+            "input": text,
+            "response_format": match config.tts_format.unwrap_or_default() {
+                TtsFormat::Mp3 => "mp3",
+                TtsFormat::Wav => "wav",
+            },
+        }))
+        .send()
+        .await
+        .context("Failed to send request to OpenAI speech API")?;
+
+    let response = check_status(response, "OpenAI speech").await?;
 
     response
         .bytes()
@@ -56,10 +166,14 @@ async fn synthesize_inworld(config: &VoiceoverConfig, text: &str) -> Result<Vec<
         .as_ref()
         .context("Inworld API key not configured (Basic auth base64)")?;
 
-    let voice_id = config.voice_id.as_deref().unwrap_or("Simon");
+    let language = config.foreign_language();
+    let voice_id = config
+        .voice_id
+        .as_deref()
+        .unwrap_or_else(|| inworld_default_voice(language.as_deref()));
     let model_id = config.model_id.as_deref().unwrap_or("inworld-tts-1.5-max");
 
-    let client = reqwest::Client::new();
+    let client = http_client(config)?;
     let response = client
         .post("https://api.inworld.ai/tts/v1/voice")
         .header("Authorization", format!("Basic {}", api_key))
@@ -68,16 +182,13 @@ async fn synthesize_inworld(config: &VoiceoverConfig, text: &str) -> Result<Vec<
             "text": text,
             "voiceId": voice_id,
             "modelId": model_id,
+            "audioConfig": audio_config(config),
         }))
         .send()
         .await
         .context("Failed to send request to Inworld API")?;
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        anyhow::bail!("Inworld API error ({}): {}", status, error_text);
-    }
+    let response = check_status(response, "Inworld").await?;
 
     let response_json: serde_json::Value = response
         .json()
@@ -92,3 +203,349 @@ async fn synthesize_inworld(config: &VoiceoverConfig, text: &str) -> Result<Vec<
         .decode(audio_base64)
         .context("Failed to decode base64 audio from Inworld")
 }
+
+async fn synthesize_google(config: &VoiceoverConfig, text: &str) -> Result<Vec<u8>> {
+    let credential = config
+        .google_api_key
+        .as_ref()
+        .context("Google Cloud API key not configured")?;
+
+    let language = config.foreign_language();
+    let voice_id = config
+        .voice_id
+        .as_deref()
+        .unwrap_or_else(|| google_default_voice(language.as_deref()));
+
+    let client = http_client(config)?;
+    let request = client
+        .post("https://texttospeech.googleapis.com/v1/text:synthesize")
+        .header("Content-Type", "application/json");
+    // OAuth access tokens go in the Authorization header, API keys in the query string
+    let request = if credential.starts_with("ya29.") {
+        request.bearer_auth(credential)
+    } else {
+        request.query(&[("key", credential)])
+    };
+    let response = request
+        .json(&serde_json::json!({
+            "input": { "text": text },
+            "voice": {
+                "languageCode": voice_language_code(voice_id),
+                "name": voice_id,
+            },
+            "audioConfig": audio_config(config),
+        }))
+        .send()
+        .await
+        .context("Failed to send request to Google Cloud TTS API")?;
+
+    let response = check_status(response, "Google Cloud TTS").await?;
+
+    let response_json: serde_json::Value = response
+        .json()
+        .await
+        .context("Failed to parse Google Cloud TTS response")?;
+
+    let audio_base64 = response_json["audioContent"]
+        .as_str()
+        .context("Failed to extract audioContent from Google Cloud TTS response")?;
+
+    general_purpose::STANDARD
+        .decode(audio_base64)
+        .context("Failed to decode base64 audio from Google Cloud TTS")
+}
+
+async fn synthesize_azure(config: &VoiceoverConfig, text: &str) -> Result<Vec<u8>> {
+    let api_key = config
+        .azure_api_key
+        .as_ref()
+        .context("Azure Speech API key not configured")?;
+    let region = config
+        .azure_region
+        .as_deref()
+        .context("Azure Speech region not configured (azure_region)")?;
+
+    let language = config.foreign_language();
+    let voice_id = config
+        .voice_id
+        .as_deref()
+        .unwrap_or_else(|| azure_default_voice(language.as_deref()));
+
+    let ssml = format!(
+        "<speak version=\"1.0\" xml:lang=\"{}\"><voice name=\"{}\">{}</voice></speak>",
+        voice_language_code(voice_id),
+        xml_escape(voice_id),
+        xml_escape(text)
+    );
+
+    let url = format!(
+        "https://{}.tts.speech.microsoft.com/cognitiveservices/v1",
+        region
+    );
+
+    let client = http_client(config)?;
+    let response = client
+        .post(&url)
+        .header("Ocp-Apim-Subscription-Key", api_key)
+        .header("Content-Type", "application/ssml+xml")
+        .header("X-Microsoft-OutputFormat", azure_output_format(config))
+        .header("User-Agent", "torvax")
+        .body(ssml)
+        .send()
+        .await
+        .context("Failed to send request to Azure Speech API")?;
+
+    let response = check_status(response, "Azure Speech").await?;
+
+    response
+        .bytes()
+        .await
+        .context("Failed to read audio response")
+        .map(|b| b.to_vec())
+}
+
+async fn synthesize_piper(config: &VoiceoverConfig, text: &str) -> Result<Vec<u8>> {
+    let bin = piper_bin(config).to_string();
+    let model = config
+        .piper_model
+        .clone()
+        .context("Piper voice model not configured (piper_model)")?;
+    // Piper speaks each input line as its own utterance
+    let text = format!(
+        "{}\n",
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    );
+
+    tokio::task::spawn_blocking(move || run_piper(&bin, &model, &text))
+        .await
+        .context("Piper task failed")?
+}
+
+/// Feed `text` to piper on stdin and collect the WAV it writes to stdout.
+fn run_piper(bin: &str, model: &str, text: &str) -> Result<Vec<u8>> {
+    let mut child = Command::new(bin)
+        .args(["--model", model, "--output_file", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run Piper binary '{}'", bin))?;
+
+    child
+        .stdin
+        .take()
+        .context("Failed to open Piper stdin")?
+        .write_all(text.as_bytes())
+        .context("Failed to send text to Piper")?;
+    let output = child
+        .wait_with_output()
+        .context("Failed to read Piper output")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Piper exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    if output.stdout.is_empty() {
+        anyhow::bail!("Piper produced no audio");
+    }
+    Ok(output.stdout)
+}
+
+/// ElevenLabs `output_format`, e.g. `mp3_44100_128` or `wav_24000`. MP3 comes at
+/// 22.05 or 44.1 kHz only.
+fn elevenlabs_output_format(config: &VoiceoverConfig) -> String {
+    match (
+        config.tts_format.unwrap_or_default(),
+        config.tts_sample_rate,
+    ) {
+        (TtsFormat::Mp3, Some(rate)) if rate <= 22050 => "mp3_22050_32".to_string(),
+        (TtsFormat::Mp3, _) => "mp3_44100_128".to_string(),
+        (TtsFormat::Wav, rate) => format!("wav_{}", rate.unwrap_or(44100)),
+    }
+}
+
+/// `audioConfig` shared by the Inworld and Google request bodies. LINEAR16 comes
+/// back with a WAV header.
+fn audio_config(config: &VoiceoverConfig) -> serde_json::Value {
+    let encoding = match config.tts_format.unwrap_or_default() {
+        TtsFormat::Mp3 => "MP3",
+        TtsFormat::Wav => "LINEAR16",
+    };
+    let mut audio_config = serde_json::json!({ "audioEncoding": encoding });
+    if let Some(rate) = config.tts_sample_rate {
+        audio_config["sampleRateHertz"] = rate.into();
+    }
+    audio_config
+}
+
+/// Azure `X-Microsoft-OutputFormat`, at the nearest rate Azure offers at or above
+/// the requested one (24 kHz when unset).
+fn azure_output_format(config: &VoiceoverConfig) -> &'static str {
+    let rate = config.tts_sample_rate.unwrap_or(24000);
+    match config.tts_format.unwrap_or_default() {
+        TtsFormat::Mp3 => match rate {
+            0..=16000 => "audio-16khz-32kbitrate-mono-mp3",
+            16001..=24000 => "audio-24khz-48kbitrate-mono-mp3",
+            _ => "audio-48khz-96kbitrate-mono-mp3",
+        },
+        TtsFormat::Wav => match rate {
+            0..=8000 => "riff-8khz-16bit-mono-pcm",
+            8001..=16000 => "riff-16khz-16bit-mono-pcm",
+            16001..=22050 => "riff-22050hz-16bit-mono-pcm",
+            22051..=24000 => "riff-24khz-16bit-mono-pcm",
+            24001..=44100 => "riff-44100hz-16bit-mono-pcm",
+            _ => "riff-48khz-16bit-mono-pcm",
+        },
+    }
+}
+
+/// Inworld voice for the narration language (voices are tied to a language).
+/// OpenAI and ElevenLabs defaults are multilingual and need no mapping.
+fn inworld_default_voice(language: Option<&str>) -> &'static str {
+    match language {
+        Some("es") => "Diego",
+        Some("fr") => "Alain",
+        Some("de") => "Josef",
+        Some("it") => "Gianni",
+        Some("pt") => "Heitor",
+        Some("ja") => "Satoshi",
+        Some("ko") => "Hyunwoo",
+        Some("zh") => "Yichen",
+        _ => "Simon",
+    }
+}
+
+/// Google voice for the narration language; its name also sets the language code.
+fn google_default_voice(language: Option<&str>) -> &'static str {
+    match language {
+        Some("es") => "es-ES-Standard-A",
+        Some("fr") => "fr-FR-Standard-A",
+        Some("de") => "de-DE-Standard-A",
+        Some("it") => "it-IT-Standard-A",
+        Some("pt") => "pt-BR-Standard-A",
+        Some("ja") => "ja-JP-Standard-A",
+        Some("ko") => "ko-KR-Standard-A",
+        Some("zh") => "cmn-CN-Standard-A",
+        _ => "en-US-Neural2-F",
+    }
+}
+
+/// Azure neural voice for the narration language; its name also sets the SSML language.
+fn azure_default_voice(language: Option<&str>) -> &'static str {
+    match language {
+        Some("es") => "es-ES-ElviraNeural",
+        Some("fr") => "fr-FR-DeniseNeural",
+        Some("de") => "de-DE-KatjaNeural",
+        Some("it") => "it-IT-ElsaNeural",
+        Some("pt") => "pt-BR-FranciscaNeural",
+        Some("ja") => "ja-JP-NanamiNeural",
+        Some("ko") => "ko-KR-SunHiNeural",
+        Some("zh") => "zh-CN-XiaoxiaoNeural",
+        _ => "en-US-JennyNeural",
+    }
+}
+
+/// Escape text for use inside SSML.
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Language code embedded in a Google or Azure voice name, e.g. `en-US` for `en-US-Neural2-F`.
+fn voice_language_code(voice_id: &str) -> String {
+    voice_id
+        .splitn(3, '-')
+        .take(2)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Turn a non-success response into an `HttpStatusError` so callers can decide to retry.
+async fn check_status(
+    response: reqwest::Response,
+    provider: &'static str,
+) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(HttpStatusError {
+        provider,
+        status,
+        body,
+    }
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_code_comes_from_voice_name() {
+        assert_eq!(voice_language_code("en-US-Neural2-F"), "en-US");
+        assert_eq!(voice_language_code("cmn-CN-Wavenet-A"), "cmn-CN");
+        assert_eq!(voice_language_code("en-US-JennyNeural"), "en-US");
+    }
+
+    #[test]
+    fn piper_setup_problems_are_reported_up_front() {
+        let missing_model = VoiceoverConfig {
+            provider: VoiceoverProvider::Piper,
+            ..Default::default()
+        };
+        assert!(check_piper(&missing_model)
+            .unwrap_err()
+            .to_string()
+            .contains("piper_model"));
+
+        let model = std::env::temp_dir().join("torvax-test-voice.onnx");
+        std::fs::write(&model, b"").unwrap();
+        let missing_bin = VoiceoverConfig {
+            piper_bin: Some("torvax-no-such-piper".to_string()),
+            piper_model: Some(model.to_string_lossy().to_string()),
+            ..missing_model
+        };
+        let err = check_piper(&missing_bin).unwrap_err().to_string();
+        std::fs::remove_file(&model).unwrap();
+        assert!(err.contains("not found"), "{}", err);
+    }
+
+    #[test]
+    fn output_formats_follow_format_and_sample_rate() {
+        let mut config = VoiceoverConfig::default();
+        assert_eq!(elevenlabs_output_format(&config), "mp3_44100_128");
+        assert_eq!(
+            azure_output_format(&config),
+            "audio-24khz-48kbitrate-mono-mp3"
+        );
+        assert_eq!(
+            audio_config(&config),
+            serde_json::json!({ "audioEncoding": "MP3" })
+        );
+
+        config.tts_format = Some(TtsFormat::Wav);
+        config.tts_sample_rate = Some(22050);
+        assert_eq!(elevenlabs_output_format(&config), "wav_22050");
+        assert_eq!(azure_output_format(&config), "riff-22050hz-16bit-mono-pcm");
+        assert_eq!(
+            audio_config(&config),
+            serde_json::json!({ "audioEncoding": "LINEAR16", "sampleRateHertz": 22050 })
+        );
+    }
+
+    #[test]
+    fn ssml_text_is_escaped() {
+        assert_eq!(
+            xml_escape(r#"a < b && c > "d" 'e'"#),
+            "a &lt; b &amp;&amp; c &gt; &quot;d&quot; &apos;e&apos;"
+        );
+    }
+}
@@ -1,4 +1,8 @@
+use chrono::{DateTime, Datelike, Local, Utc, Weekday};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::git::CommitMetadata;
 
 /// Configuration for voiceover providers
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,10 +15,105 @@ pub struct VoiceoverConfig {
     pub voice_id: Option<String>,
     pub model_id: Option<String>,
     pub openai_api_key: Option<String>,
+    /// Google Cloud API key, or an OAuth access token (`ya29.…`) sent as a bearer token
+    pub google_api_key: Option<String>,
+    /// Azure Speech resource key, sent as `Ocp-Apim-Subscription-Key`
+    pub azure_api_key: Option<String>,
+    /// Azure region of the Speech resource, e.g. `westeurope`
+    pub azure_region: Option<String>,
+    /// Local `piper` executable for offline speech (`piper` on PATH when unset)
+    pub piper_bin: Option<String>,
+    /// Piper voice model file, e.g. `en_US-lessac-medium.onnx`
+    pub piper_model: Option<String>,
+    /// OpenAI-compatible endpoint for explanations (e.g. Ollama at http://localhost:11434/v1)
+    pub llm_base_url: Option<String>,
+    /// Chat model used for explanations (default: gpt-5.2)
+    pub llm_model: Option<String>,
+    /// Chat model for ordering files by development flow (llm_model when unset)
+    pub ordering_model: Option<String>,
+    /// Chat model for grouping hunks into chunks (llm_model when unset)
+    pub grouping_model: Option<String>,
+    /// Chat model for the spoken explanations and file intros (llm_model when unset)
+    pub narration_model: Option<String>,
+    /// Sampling temperature for ordering files, 0.0-2.0 (default 0.2)
+    pub ordering_temperature: Option<f32>,
+    /// Sampling temperature for grouping hunks, 0.0-2.0 (default 0.3)
+    pub grouping_temperature: Option<f32>,
+    /// Sampling temperature for explanations; higher is more creative, 0.0-2.0 (default 0.7)
+    pub narration_temperature: Option<f32>,
     #[serde(default)]
     pub use_llm_explanations: bool,
+    /// Custom narration prompt with {project}, {commit}, {author}, {date}, {file}, {language},
+    /// {diff}, {target_words} and {animation_secs} placeholders (default prompt when unset)
+    pub narration_prompt: Option<String>,
+    /// Mention the commit author and when the commit was made in narration
+    #[serde(default = "default_narrate_author")]
+    pub narrate_author: bool,
+    /// Speak an intro before a commit's files and a short outro after them
+    #[serde(default)]
+    pub commit_intro: bool,
+    /// Speak a one-sentence introduction as each file opens
+    #[serde(default = "default_file_intros")]
+    pub file_intros: bool,
+    /// Narration language as an ISO 639-1 code, e.g. `es` (English when unset)
+    pub language: Option<String>,
+    /// Narrate only the first N files in development-flow order (all when unset, none when 0)
+    pub max_files: Option<usize>,
+    /// Reuse narration cached under ~/.cache/torvax instead of regenerating it
+    #[serde(default = "default_cache")]
+    pub cache: bool,
+    /// Playback rate multiplier for narration (1.0 = as synthesized)
+    #[serde(default = "default_narration_speed")]
+    pub narration_speed: f32,
+    /// Speaking rate of the voice in words per minute, used to size narration to the
+    /// animation (provider default when unset)
+    pub speech_wpm: Option<f32>,
+    /// Audio encoding requested from the TTS provider (mp3 when unset; Piper always writes WAV)
+    pub tts_format: Option<TtsFormat>,
+    /// Sample rate in Hz requested from the TTS provider, e.g. 24000 (provider default when
+    /// unset; OpenAI and Piper pick their own)
+    pub tts_sample_rate: Option<u32>,
+    /// Narration loudness (0.0-1.5); above 1.0 amplifies and may clip
+    #[serde(default = "default_volume")]
+    pub volume: f32,
+    /// Seconds before a TTS or LLM request is given up on
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
 }
 
+fn default_cache() -> bool {
+    true
+}
+
+fn default_narrate_author() -> bool {
+    true
+}
+
+fn default_file_intros() -> bool {
+    true
+}
+
+fn default_narration_speed() -> f32 {
+    1.0
+}
+
+fn default_volume() -> f32 {
+    1.0
+}
+
+fn default_request_timeout_secs() -> u64 {
+    60
+}
+
+/// Chunk path of the spoken intro played before a commit's first file
+pub const COMMIT_INTRO_PATH: &str = "<commit intro>";
+
+/// Chunk path of the spoken outro played after a commit's last file
+pub const COMMIT_OUTRO_PATH: &str = "<commit outro>";
+
+/// Loudest narration volume accepted; values above 1.0 may clip
+pub const MAX_VOLUME: f32 = 1.5;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
 pub enum VoiceoverProvider {
     #[serde(rename = "elevenlabs")]
@@ -22,6 +121,50 @@ pub enum VoiceoverProvider {
     #[default]
     #[serde(rename = "inworld")]
     Inworld,
+    #[serde(rename = "openai")]
+    OpenAI,
+    #[serde(rename = "google")]
+    GoogleTts,
+    #[serde(rename = "azure")]
+    Azure,
+    #[serde(rename = "piper")]
+    Piper,
+}
+
+/// Encoding of synthesized narration. Both decode with rodio; WAV records its length,
+/// so narration timing is measured rather than estimated. Ogg isn't offered: providers
+/// return Ogg Opus, which rodio can't play.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TtsFormat {
+    #[default]
+    Mp3,
+    Wav,
+}
+
+impl VoiceoverProvider {
+    /// Typical speaking rate of the provider's voices in words per minute
+    pub fn default_speech_wpm(&self) -> f32 {
+        match self {
+            VoiceoverProvider::ElevenLabs => 165.0,
+            VoiceoverProvider::Piper => 160.0,
+            VoiceoverProvider::OpenAI => 155.0,
+            VoiceoverProvider::Inworld
+            | VoiceoverProvider::GoogleTts
+            | VoiceoverProvider::Azure => 150.0,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            VoiceoverProvider::ElevenLabs => "elevenlabs",
+            VoiceoverProvider::Inworld => "inworld",
+            VoiceoverProvider::OpenAI => "openai",
+            VoiceoverProvider::GoogleTts => "google",
+            VoiceoverProvider::Azure => "azure",
+            VoiceoverProvider::Piper => "piper",
+        }
+    }
 }
 
 impl Default for VoiceoverConfig {
@@ -33,9 +176,139 @@ impl Default for VoiceoverConfig {
             voice_id: None,
             model_id: None,
             openai_api_key: None,
+            google_api_key: None,
+            azure_api_key: None,
+            azure_region: None,
+            piper_bin: None,
+            piper_model: None,
+            llm_base_url: None,
+            llm_model: None,
+            ordering_model: None,
+            grouping_model: None,
+            narration_model: None,
+            ordering_temperature: None,
+            grouping_temperature: None,
+            narration_temperature: None,
             use_llm_explanations: false,
+            narration_prompt: None,
+            narrate_author: default_narrate_author(),
+            commit_intro: false,
+            file_intros: default_file_intros(),
+            language: None,
+            max_files: None,
+            cache: default_cache(),
+            narration_speed: default_narration_speed(),
+            speech_wpm: None,
+            tts_format: None,
+            tts_sample_rate: None,
+            volume: default_volume(),
+            request_timeout_secs: default_request_timeout_secs(),
+        }
+    }
+}
+
+impl VoiceoverConfig {
+    /// Whether the key needed by the selected TTS provider is present
+    pub fn has_tts_key(&self) -> bool {
+        match self.provider {
+            VoiceoverProvider::OpenAI => self.openai_api_key.is_some(),
+            VoiceoverProvider::GoogleTts => self.google_api_key.is_some(),
+            VoiceoverProvider::Azure => self.azure_api_key.is_some(),
+            // Runs locally, so the voice model stands in for a key
+            VoiceoverProvider::Piper => self.piper_model.is_some(),
+            VoiceoverProvider::ElevenLabs | VoiceoverProvider::Inworld => self.api_key.is_some(),
+        }
+    }
+
+    /// Primary subtag of a non-English narration language, lowercased (`es` for `es-MX`).
+    /// None when narrating in English.
+    pub fn foreign_language(&self) -> Option<String> {
+        let code = self.language.as_deref()?.trim();
+        let primary = code.split(['-', '_']).next().unwrap_or(code).to_lowercase();
+        (!primary.is_empty() && primary != "en").then_some(primary)
+    }
+
+    /// How long a single TTS or LLM request may take (at least a second)
+    pub fn request_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.request_timeout_secs.max(1))
+    }
+
+    /// Words spoken per second by the configured voice
+    pub fn words_per_sec(&self) -> f32 {
+        self.speech_wpm
+            .filter(|wpm| *wpm > 0.0)
+            .unwrap_or_else(|| self.provider.default_speech_wpm())
+            / 60.0
+    }
+
+    /// Seconds it takes the configured voice to speak `text`
+    pub fn speech_secs(&self, text: &str) -> f32 {
+        text.split_whitespace().count() as f32 / self.words_per_sec()
+    }
+
+    /// Whether explanations can be generated (OpenAI key, or a local endpoint that needs none)
+    pub fn has_llm_access(&self) -> bool {
+        self.openai_api_key.is_some() || self.llm_base_url.is_some()
+    }
+}
+
+/// The commit being narrated, as described to the LLM
+#[derive(Debug, Clone)]
+pub struct CommitContext {
+    pub message: String,
+    pub author: String,
+    pub date: DateTime<Utc>,
+    /// Detected language of each changed file, keyed by path
+    pub languages: HashMap<String, &'static str>,
+}
+
+impl CommitContext {
+    pub fn new(metadata: &CommitMetadata) -> Self {
+        Self {
+            message: metadata.message.clone(),
+            author: metadata.author.clone(),
+            date: metadata.date,
+            languages: metadata
+                .changes
+                .iter()
+                .filter_map(|c| Some((c.path.clone(), c.language?)))
+                .collect(),
         }
     }
+
+    /// Language `path` is written in, when it was recognized
+    pub fn language(&self, path: &str) -> Option<&'static str> {
+        self.languages.get(path).copied()
+    }
+
+    /// When the commit was made, relative to now in local time (e.g. `last Tuesday`)
+    pub fn relative_date(&self) -> String {
+        let today = Local::now().date_naive();
+        let day = self.date.with_timezone(&Local).date_naive();
+        relative_day((today - day).num_days(), day.weekday())
+    }
+}
+
+/// Spoken form of a date `days_ago` days back that fell on `weekday`
+fn relative_day(days_ago: i64, weekday: Weekday) -> String {
+    let weekday = match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    };
+    match days_ago {
+        i64::MIN..=0 => "today".to_string(),
+        1 => "yesterday".to_string(),
+        2..=6 => format!("on {}", weekday),
+        7..=13 => format!("last {}", weekday),
+        14..=59 => format!("{} weeks ago", days_ago / 7),
+        60..=729 => format!("{} months ago", days_ago / 30),
+        _ => format!("{} years ago", days_ago / 365),
+    }
 }
 
 /// Project context used to give LLM repository awareness
@@ -46,26 +319,51 @@ pub struct ProjectContext {
 }
 
 /// A single voiceover chunk covering a portion of a file diff
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiffChunk {
     pub chunk_id: usize,
     pub file_path: String,
     pub hunk_indices: Vec<usize>,
     pub explanation: String,
+    #[serde(skip)]
     pub audio_data: Option<Vec<u8>>,
     pub has_audio: bool,
     pub audio_duration_secs: f32,
 }
 
-/// A queued voiceover segment (for file-open triggers)
-#[derive(Debug, Clone)]
-pub struct VoiceoverSegment {
-    pub audio_data: Option<Vec<u8>>,
-    pub trigger_type: VoiceoverTrigger,
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_day_reads_like_speech() {
+        assert_eq!(relative_day(0, Weekday::Fri), "today");
+        assert_eq!(relative_day(1, Weekday::Thu), "yesterday");
+        assert_eq!(relative_day(3, Weekday::Tue), "on Tuesday");
+        assert_eq!(relative_day(10, Weekday::Tue), "last Tuesday");
+        assert_eq!(relative_day(21, Weekday::Fri), "3 weeks ago");
+        assert_eq!(relative_day(200, Weekday::Fri), "6 months ago");
+        assert_eq!(relative_day(1000, Weekday::Fri), "2 years ago");
+    }
+
+    #[test]
+    fn speech_rate_defaults_per_provider_and_can_be_overridden() {
+        let openai = VoiceoverConfig {
+            provider: VoiceoverProvider::OpenAI,
+            ..VoiceoverConfig::default()
+        };
+        assert!((openai.speech_secs("one two three") - 3.0 * 60.0 / 155.0).abs() < 1e-4);
 
-/// When to trigger a voiceover segment
-#[derive(Debug, Clone, PartialEq)]
-pub enum VoiceoverTrigger {
-    FileOpen(String),
+        let slow = VoiceoverConfig {
+            speech_wpm: Some(120.0),
+            ..openai.clone()
+        };
+        assert!((slow.speech_secs("one two three four") - 2.0).abs() < 1e-4);
+
+        let invalid = VoiceoverConfig {
+            speech_wpm: Some(0.0),
+            ..openai
+        };
+        assert!((invalid.words_per_sec() - 155.0 / 60.0).abs() < 1e-4);
+    }
 }
@@ -3,6 +3,11 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand, ValueEnum};
 
+use crate::export::GifOptions;
+
+// Presentation mode triples the pause after each typed line
+const PRESENT_LINE_PAUSE_SCALE: f64 = 3.0;
+
 /// Defines the order in which commits are played back during animation.
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
 pub enum PlaybackOrder {
@@ -12,6 +17,83 @@ pub enum PlaybackOrder {
     Desc,
 }
 
+/// Order in which a commit's files are played and narrated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum FileOrder {
+    /// Development flow chosen by the narrator; file tree order without voiceover
+    #[default]
+    Flow,
+    /// Sorted by path
+    Alpha,
+    /// Development flow, back to front
+    ReverseFlow,
+    /// Most changed lines first
+    Size,
+}
+
+/// Shape of the editor cursor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum CursorStyle {
+    #[default]
+    Block,
+    Bar,
+    Underline,
+}
+
+/// How much of an added line appears per typing step.
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum TypeBy {
+    #[default]
+    Char,
+    Token,
+}
+
+/// Side of the layout made transparent by `--transparent`. Panes on the same
+/// side share one background, so naming either pane clears both.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum TransparentSide {
+    /// File tree and commit info
+    #[value(alias = "file-tree", alias = "status")]
+    Left,
+    /// Editor and terminal
+    #[value(alias = "editor", alias = "terminal")]
+    Right,
+}
+
+/// Colors the terminal can show, set with `--colors` or detected from the environment.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ColorDepth {
+    /// The 16 ANSI colors
+    #[value(name = "16")]
+    Ansi16,
+    /// The 256-color palette
+    #[value(name = "256")]
+    Ansi256,
+    /// 24-bit RGB
+    #[value(name = "true", alias = "truecolor", alias = "24bit")]
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Read from `COLORTERM` and `TERM`. Terminals that announce neither truecolor
+    /// nor a limited palette get truecolor, as most modern terminals support it.
+    pub fn detect() -> Self {
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+        let term = std::env::var("TERM").unwrap_or_default();
+        if colorterm == "truecolor" || colorterm == "24bit" || term.ends_with("-direct") {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else if matches!(term.as_str(), "linux" | "ansi" | "cygwin" | "dumb")
+            || term.starts_with("vt")
+        {
+            ColorDepth::Ansi16
+        } else {
+            ColorDepth::TrueColor
+        }
+    }
+}
+
 #[derive(Parser, Debug)]
 #[command(
     name = "torvax",
@@ -36,6 +118,61 @@ pub struct Args {
     )]
     pub commit: Option<String>,
 
+    #[arg(
+        long,
+        value_name = "REVA...REVB",
+        conflicts_with = "commit",
+        help = "Replay the net diff of a branch or tag comparison as one walkthrough (e.g., main...feature)"
+    )]
+    pub compare: Option<String>,
+
+    #[arg(
+        long = "since-tag",
+        value_name = "TAG",
+        conflicts_with_all = ["commit", "compare", "resume"],
+        help = "Replay every commit since a tag, like --commit <TAG>..HEAD"
+    )]
+    pub since_tag: Option<String>,
+
+    #[arg(
+        long = "pr",
+        value_name = "URL",
+        conflicts_with_all = ["commit", "compare", "since_tag", "resume"],
+        help = "Replay the commits of a GitHub pull request or GitLab merge request; run inside a clone with the commits fetched (GITHUB_TOKEN/GITLAB_TOKEN for private projects)"
+    )]
+    pub pr: Option<String>,
+
+    #[arg(
+        long = "commits-from",
+        value_name = "PATH|-",
+        conflicts_with_all = ["commit", "compare", "since_tag", "pr", "resume"],
+        help = "Replay exactly the commits listed one per line in a file (or - for stdin), in listed order"
+    )]
+    pub commits_from: Option<PathBuf>,
+
+    #[arg(
+        long = "file",
+        value_name = "PATH",
+        conflicts_with_all = ["commit", "compare", "since_tag", "pr", "commits_from"],
+        help = "Replay how one file evolved: every commit touching it, oldest first, following renames and showing only that file"
+    )]
+    pub file: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["commit", "compare"],
+        help = "Reopen the commit and position where the last run in this repository stopped"
+    )]
+    pub resume: bool,
+
+    #[arg(
+        long = "start-file",
+        value_name = "PATH",
+        conflicts_with = "resume",
+        help = "Start the first commit at this file instead of from the top (path or unique end of one)"
+    )]
+    pub start_file: Option<String>,
+
     #[arg(
         short,
         long,
@@ -44,6 +181,29 @@ pub struct Args {
     )]
     pub speed: Option<u64>,
 
+    #[arg(long, value_name = "SECONDS", conflicts_with = "speed",
+          value_parser = |s: &str| match s.parse::<f64>() {
+              Ok(secs) if secs > 0.0 => Ok(secs),
+              Ok(_) => Err("Duration must be greater than 0".to_string()),
+              Err(e) => Err(e.to_string()),
+          },
+          help = "Scale typing speed so the commit replays in roughly this many seconds")]
+    pub duration: Option<f64>,
+
+    #[arg(
+        long = "commit-gap",
+        value_name = "MS",
+        help = "Pause between commits in milliseconds (default: 100x typing speed)"
+    )]
+    pub commit_gap: Option<u64>,
+
+    #[arg(
+        long = "file-gap",
+        value_name = "MS",
+        help = "Pause between files in milliseconds (default: 50x typing speed)"
+    )]
+    pub file_gap: Option<u64>,
+
     #[arg(
         short,
         long,
@@ -52,10 +212,40 @@ pub struct Args {
     )]
     pub theme: Option<String>,
 
+    #[arg(
+        long = "theme-file",
+        value_name = "PATH",
+        help = "Load a custom theme from a TOML or JSON file (takes precedence over --theme)"
+    )]
+    pub theme_file: Option<PathBuf>,
+
     #[arg(long, num_args = 0..=1, default_missing_value = "true", value_name = "BOOL",
           help = "Show background colors (use --background=false for transparent background)")]
     pub background: Option<bool>,
 
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        value_name = "PANES",
+        help = "Drop the background of some panes only, e.g. editor,terminal: left (file-tree, status) or right (editor, terminal)"
+    )]
+    pub transparent: Vec<TransparentSide>,
+
+    #[arg(
+        long = "no-color",
+        help = "Drop all theme colors and backgrounds, like setting NO_COLOR"
+    )]
+    pub no_color: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "LEVEL",
+        help = "Colors the terminal can show: 16, 256 or true (detected from COLORTERM and TERM by default)"
+    )]
+    pub colors: Option<ColorDepth>,
+
     #[arg(
         long,
         value_enum,
@@ -68,6 +258,42 @@ pub struct Args {
           help = "Loop the animation continuously")]
     pub loop_playback: Option<bool>,
 
+    #[arg(long = "max-commits", value_name = "N",
+          value_parser = |s: &str| match s.parse::<usize>() {
+              Ok(0) => Err("Max commits must be at least 1".to_string()),
+              Ok(n) => Ok(n),
+              Err(e) => Err(e.to_string()),
+          },
+          help = "Stop after playing N commits (with --loop, N commits per loop)")]
+    pub max_commits: Option<usize>,
+
+    #[arg(
+        long = "min-changes",
+        value_name = "N",
+        help = "Skip commits changing fewer than N lines (added plus deleted, lock files aside)"
+    )]
+    pub min_changes: Option<usize>,
+
+    #[arg(
+        long = "max-changes",
+        value_name = "N",
+        help = "Skip commits changing more than N lines (added plus deleted, lock files aside)"
+    )]
+    pub max_changes: Option<usize>,
+
+    #[arg(
+        long = "no-merges",
+        conflicts_with = "merges_only",
+        help = "Skip merge commits (the default)"
+    )]
+    pub no_merges: bool,
+
+    #[arg(
+        long = "merges-only",
+        help = "Only play merge commits, diffed against their first parent"
+    )]
+    pub merges_only: bool,
+
     #[arg(long, help = "Display third-party license information")]
     pub license: bool,
 
@@ -80,6 +306,22 @@ pub struct Args {
           help = "Filter commits by author name or email (partial match, case-insensitive)")]
     pub author: Option<String>,
 
+    #[arg(long, value_name = "PATTERN",
+          value_parser = |s: &str| if s.trim().is_empty() {
+              Err("Grep pattern cannot be empty".to_string())
+          } else {
+              Ok(s.to_string())
+          },
+          help = "Filter commits by message (partial match, case-insensitive)")]
+    pub grep: Option<String>,
+
+    #[arg(
+        long = "grep-regex",
+        requires = "grep",
+        help = "Treat the --grep pattern as a regular expression"
+    )]
+    pub grep_regex: bool,
+
     #[arg(
         long,
         value_name = "DATE",
@@ -94,11 +336,21 @@ pub struct Args {
     )]
     pub after: Option<String>,
 
+    #[arg(long = "skip-commit", value_name = "HASH",
+          action = clap::ArgAction::Append,
+          help = "Leave a commit out of playback, e.g. a large reformat (short hashes work, can be specified multiple times)")]
+    pub skip_commit: Vec<String>,
+
     #[arg(short = 'i', long = "ignore", value_name = "PATTERN",
           action = clap::ArgAction::Append,
           help = "Ignore files matching pattern (gitignore syntax, can be specified multiple times)")]
     pub ignore: Vec<String>,
 
+    #[arg(long = "path-filter", value_name = "GLOB",
+          action = clap::ArgAction::Append,
+          help = "Only replay commits touching files matching glob, showing just those files (can be specified multiple times)")]
+    pub path_filter: Vec<String>,
+
     #[arg(
         long = "ignore-file",
         value_name = "PATH",
@@ -106,6 +358,12 @@ pub struct Args {
     )]
     pub ignore_file: Option<PathBuf>,
 
+    #[arg(
+        long = "no-auto-ignore",
+        help = "Don't skip files matched by the repository's .gitignore, .git/info/exclude or global excludes file"
+    )]
+    pub no_auto_ignore: bool,
+
     #[arg(long = "speed-rule", value_name = "PATTERN:MS",
           action = clap::ArgAction::Append,
           help = "Set typing speed for files matching pattern (e.g., '*.java:50'). Can be specified multiple times.")]
@@ -125,10 +383,340 @@ pub struct Args {
     #[arg(
         long = "voiceover-provider",
         value_name = "PROVIDER",
-        help = "Voiceover provider to use: elevenlabs or inworld (overrides config file)"
+        help = "Voiceover provider to use: elevenlabs, inworld, openai, google, azure, or piper (overrides config file)"
     )]
     pub voiceover_provider: Option<String>,
 
+    #[arg(long = "voice", value_name = "ID",
+          value_parser = |s: &str| if s.trim().is_empty() {
+              Err("Voice ID cannot be empty".to_string())
+          } else {
+              Ok(s.to_string())
+          },
+          help = "Voice ID for the TTS provider (overrides config file)")]
+    pub voice: Option<String>,
+
+    #[arg(long = "tts-model", value_name = "ID",
+          value_parser = |s: &str| if s.trim().is_empty() {
+              Err("TTS model ID cannot be empty".to_string())
+          } else {
+              Ok(s.to_string())
+          },
+          help = "Model ID for the TTS provider (overrides config file)")]
+    pub tts_model: Option<String>,
+
+    #[arg(
+        long = "no-cache",
+        help = "Regenerate narration instead of reusing audio cached in ~/.cache/torvax"
+    )]
+    pub no_cache: bool,
+
+    #[arg(
+        long = "intro",
+        conflicts_with = "no_intro",
+        help = "Narrate a short intro before each commit's files and an outro after them"
+    )]
+    pub intro: bool,
+
+    #[arg(
+        long = "no-intro",
+        help = "Skip the commit intro and outro even when enabled in the config file"
+    )]
+    pub no_intro: bool,
+
+    #[arg(
+        long = "no-file-intros",
+        help = "Skip the sentence introducing each file as it opens"
+    )]
+    pub no_file_intros: bool,
+
+    #[arg(long = "narration-speed", value_name = "FACTOR",
+          value_parser = |s: &str| match s.parse::<f32>() {
+              Ok(f) if f > 0.0 && f.is_finite() => Ok(f),
+              Ok(_) => Err("Narration speed must be greater than 0".to_string()),
+              Err(e) => Err(e.to_string()),
+          },
+          help = "Play narration faster or slower, e.g. 1.25 (overrides config file)")]
+    pub narration_speed: Option<f32>,
+
+    #[arg(long = "volume", value_name = "LEVEL",
+          value_parser = |s: &str| match s.parse::<f32>() {
+              Ok(v) if (0.0..=crate::audio::MAX_VOLUME).contains(&v) => Ok(v),
+              Ok(_) => Err(format!("Volume must be between 0.0 and {}", crate::audio::MAX_VOLUME)),
+              Err(e) => Err(e.to_string()),
+          },
+          help = "Narration volume from 0.0 to 1.5; above 1.0 amplifies and may clip (overrides config file)")]
+    pub volume: Option<f32>,
+
+    #[arg(long = "language", value_name = "CODE",
+          value_parser = |s: &str| if s.trim().is_empty() {
+              Err("Language code cannot be empty".to_string())
+          } else {
+              Ok(s.to_string())
+          },
+          help = "Narrate in this language, e.g. 'es' (overrides config file)")]
+    pub language: Option<String>,
+
+    #[arg(
+        long = "narrate-max-files",
+        value_name = "N",
+        help = "Narrate only the first N files of each commit in development-flow order; 0 disables narration (overrides config file)"
+    )]
+    pub narrate_max_files: Option<usize>,
+
+    #[arg(
+        long = "prompt-file",
+        value_name = "PATH",
+        help = "Narration prompt template with {project}, {commit}, {author}, {date}, {file}, {language}, {diff}, {target_words}, {animation_secs} placeholders (overrides config file)"
+    )]
+    pub prompt_file: Option<PathBuf>,
+
+    #[arg(
+        long = "word-diff",
+        help = "Highlight only the changed words within modified lines"
+    )]
+    pub word_diff: bool,
+
+    #[arg(
+        long = "show-deletions",
+        help = "Show deleted lines struck through for a beat before removing them"
+    )]
+    pub show_deletions: bool,
+
+    #[arg(
+        long = "file-order",
+        value_enum,
+        value_name = "ORDER",
+        default_value_t = FileOrder::Flow,
+        help = "Order of files within a commit: development flow, alphabetical, reversed flow, or largest change first"
+    )]
+    pub file_order: FileOrder,
+
+    #[arg(
+        long = "collapse-threshold",
+        value_name = "LINES",
+        default_value_t = 800,
+        help = "Summarize files changing more than this many lines instead of typing them out (0 never collapses)"
+    )]
+    pub collapse_threshold: usize,
+
+    #[arg(
+        long = "stat-mode",
+        help = "Show each file as a one-line 'path | +N −M' summary instead of typing its diff (press e to play one in full)"
+    )]
+    pub stat_mode: bool,
+
+    #[arg(
+        long = "only-additions",
+        conflicts_with = "show_deletions",
+        help = "Skip deletion playback: deleted lines vanish instantly and narration covers only additions"
+    )]
+    pub only_additions: bool,
+
+    #[arg(
+        long,
+        alias = "step",
+        help = "Start paused; press Space to play or h/l/H/L to step manually"
+    )]
+    pub paused: bool,
+
+    #[arg(
+        long = "auto-resume",
+        value_name = "SECONDS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Resume playback after SECONDS without input while paused (menus and dialogs stay open)"
+    )]
+    pub auto_resume: Option<u64>,
+
+    #[arg(
+        long = "present",
+        help = "Presentation mode for demos: longer pauses after each typed line, keystroke sound, and no looping"
+    )]
+    pub present: bool,
+
+    #[arg(
+        long = "keystroke-sound",
+        help = "Play a soft click for each typed character or token"
+    )]
+    pub keystroke_sound: bool,
+
+    #[arg(
+        long = "type-by",
+        value_enum,
+        value_name = "UNIT",
+        default_value_t = TypeBy::Char,
+        help = "Type added lines a character at a time or a whole syntax token at a time"
+    )]
+    pub type_by: TypeBy,
+
+    #[arg(
+        long = "minimap",
+        help = "Show an overview column of the current file's changes beside the editor"
+    )]
+    pub minimap: bool,
+
+    #[arg(
+        long = "no-line-numbers",
+        help = "Hide the line-number gutter in the editor (overrides config file)"
+    )]
+    pub no_line_numbers: bool,
+
+    #[arg(
+        long = "no-wrap",
+        help = "Cut long lines off at the editor edge instead of soft-wrapping them (overrides config file)"
+    )]
+    pub no_wrap: bool,
+
+    #[arg(
+        long = "cursor-style",
+        value_enum,
+        value_name = "STYLE",
+        default_value_t = CursorStyle::Block,
+        help = "Shape of the editor cursor"
+    )]
+    pub cursor_style: CursorStyle,
+
+    #[arg(
+        long = "no-cursor-blink",
+        help = "Keep the editor cursor steady instead of blinking"
+    )]
+    pub no_cursor_blink: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Record the walkthrough to an asciicast v2 file (playable with asciinema)"
+    )]
+    pub record: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write the voiceover narration as an SRT subtitle file"
+    )]
+    pub subtitles: Option<PathBuf>,
+
+    #[arg(
+        long = "audio-out",
+        value_name = "PATH",
+        help = "Write the voiceover narration as one WAV track timed to playback, for muxing with a screen recording"
+    )]
+    pub audio_out: Option<PathBuf>,
+
+    #[arg(
+        long = "stats",
+        help = "Start with the commit stats overlay shown (toggle with s)"
+    )]
+    pub stats: bool,
+
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..=120),
+        help = "Draw at most N frames per second while typing, to save CPU (e.g. on battery)"
+    )]
+    pub fps: Option<u32>,
+
+    #[arg(
+        long = "captions",
+        help = "Show the narration being spoken in a caption bar, highlighted word by word (requires voiceover)"
+    )]
+    pub captions: bool,
+
+    #[arg(
+        long = "no-voiceover-wait",
+        help = "Start each commit right away and narrate files as their audio is ready, instead of waiting for all of it (early files may play silent)"
+    )]
+    pub no_voiceover_wait: bool,
+
+    #[arg(
+        long = "no-hints",
+        help = "Hide the key hint line at the bottom of the screen (? toggles it; recordings never show it)"
+    )]
+    pub no_hints: bool,
+
+    #[arg(
+        long,
+        value_name = "PATH|-",
+        help = "Stream playback events (commit_start, file_open, chunk_start, chunk_end, line_typed, commit_end) as JSON lines to a file, or '-' for stdout"
+    )]
+    pub events: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Render the walkthrough to an animated GIF"
+    )]
+    pub gif: Option<PathBuf>,
+
+    #[arg(
+        long = "gif-fps",
+        value_name = "N",
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u32).range(1..=50),
+        help = "Frame rate of the --gif output"
+    )]
+    pub gif_fps: u32,
+
+    #[arg(
+        long = "gif-max-width",
+        value_name = "PX",
+        default_value_t = 1280,
+        help = "Scale --gif frames down to at most this width in pixels"
+    )]
+    pub gif_max_width: u32,
+
+    #[arg(
+        long = "gif-max-height",
+        value_name = "PX",
+        default_value_t = 720,
+        help = "Scale --gif frames down to at most this height in pixels"
+    )]
+    pub gif_max_height: u32,
+
+    #[arg(
+        long = "report",
+        value_name = "PATH",
+        help = "Write the LLM explanations for the selected commit (or every commit in a range) to a Markdown file instead of playing"
+    )]
+    pub report: Option<PathBuf>,
+
+    #[arg(
+        long = "print-order",
+        conflicts_with = "report",
+        help = "Print the selected commit's files in the narrator's development-flow order, with their status, and exit without animating"
+    )]
+    pub print_order: bool,
+
+    #[arg(
+        long = "dry-run",
+        conflicts_with_all = ["report", "print_order"],
+        help = "Print the commits that would be played, in order, and exit without animating"
+    )]
+    pub dry_run: bool,
+
+    #[arg(
+        long = "config-dir",
+        value_name = "PATH",
+        help = "Keep the config file and narration cache in this directory (also TORVAX_CONFIG_DIR; default: ~/.config/torvax)"
+    )]
+    pub config_dir: Option<PathBuf>,
+
+    #[arg(
+        short = 'q',
+        long = "quiet",
+        conflicts_with = "verbose",
+        help = "Hide all diagnostic output, including warnings from narration"
+    )]
+    pub quiet: bool,
+
+    #[arg(
+        short = 'v',
+        long = "verbose",
+        help = "Print debug diagnostics to stderr (otherwise RUST_LOG sets the level, none by default)"
+    )]
+    pub verbose: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -140,11 +728,23 @@ pub enum Commands {
         #[command(subcommand)]
         command: ThemeCommands,
     },
-    /// Show staged working tree changes (use --unstaged for unstaged changes)
+    /// View and edit settings in the config file
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+    /// Show staged working tree changes (use --unstaged for unstaged changes, --all for both)
     Diff {
         #[arg(long, help = "Show unstaged changes instead of staged")]
         unstaged: bool,
 
+        #[arg(
+            long,
+            conflicts_with = "unstaged",
+            help = "Show staged and unstaged changes together, against HEAD"
+        )]
+        all: bool,
+
         #[arg(
             short,
             long,
@@ -173,6 +773,33 @@ pub enum Commands {
               action = clap::ArgAction::Append,
               help = "Set typing speed for files matching pattern (e.g., '*.java:50')")]
         speed_rule: Vec<String>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Record the walkthrough to an asciicast v2 file"
+        )]
+        record: Option<PathBuf>,
+
+        #[arg(
+            long = "word-diff",
+            help = "Highlight only the changed words within modified lines"
+        )]
+        word_diff: bool,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Write the voiceover narration as an SRT subtitle file"
+        )]
+        subtitles: Option<PathBuf>,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["all", "loop_playback"],
+            help = "Keep running and play each save: animate the unstaged changes made since the last render"
+        )]
+        watch: bool,
     },
 }
 
@@ -185,9 +812,82 @@ pub enum ThemeCommands {
         #[arg(value_name = "NAME", help = "Theme name to set as default")]
         name: String,
     },
+    /// Show a short sample diff rendered with a theme
+    Preview {
+        #[arg(value_name = "NAME", help = "Theme name to preview")]
+        name: String,
+    },
+    /// Write a built-in theme to a TOML file to use as a starting point for --theme-file
+    Export {
+        #[arg(value_name = "NAME", help = "Theme name to export")]
+        name: String,
+        #[arg(value_name = "PATH", help = "File to write the theme to")]
+        path: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommands {
+    /// Print the resolved configuration
+    Show,
+    /// Print the config file location
+    Path,
+    /// Update a setting (speed, theme, order, background, loop)
+    Set {
+        #[arg(value_name = "KEY", help = "Setting to change")]
+        key: String,
+        #[arg(value_name = "VALUE", help = "New value")]
+        value: String,
+    },
 }
 
 impl Args {
+    /// Changed-line count above which a file is collapsed, if collapsing is on.
+    pub fn collapse_threshold(&self) -> Option<usize> {
+        (self.collapse_threshold > 0).then_some(self.collapse_threshold)
+    }
+
+    /// Scale of the pause after each typed line, stretched for `--present`.
+    pub fn line_pause_scale(&self) -> f64 {
+        if self.present {
+            PRESENT_LINE_PAUSE_SCALE
+        } else {
+            1.0
+        }
+    }
+
+    /// Which sides of the layout `--transparent` clears, as (left, right).
+    pub fn transparent_sides(&self) -> (bool, bool) {
+        (
+            self.transparent.contains(&TransparentSide::Left),
+            self.transparent.contains(&TransparentSide::Right),
+        )
+    }
+
+    /// Whether colors are off, via `--no-color` or a non-empty `NO_COLOR`.
+    pub fn monochrome(&self) -> bool {
+        self.no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+    }
+
+    /// Color depth to draw with: `--colors`, else what the terminal announces.
+    pub fn color_depth(&self) -> ColorDepth {
+        self.colors.unwrap_or_else(ColorDepth::detect)
+    }
+
+    /// Whether typing plays keystroke clicks (`--keystroke-sound` or `--present`).
+    pub fn plays_keystroke_sound(&self) -> bool {
+        self.keystroke_sound || self.present
+    }
+
+    /// Frame rate and size limits for `--gif`.
+    pub fn gif_options(&self) -> GifOptions {
+        GifOptions {
+            fps: self.gif_fps,
+            max_width: self.gif_max_width,
+            max_height: self.gif_max_height,
+        }
+    }
+
     /// Validate args and return the Git repository root path.
     pub fn validate(&self) -> Result<PathBuf> {
         let start_path = self.path.clone().unwrap_or_else(|| PathBuf::from("."));
@@ -1,8 +1,15 @@
 use crate::audio::VoiceoverConfig;
+use crate::theme::Theme;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::Display;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::OnceLock;
+
+// Directory given with --config-dir; takes precedence over TORVAX_CONFIG_DIR
+static CONFIG_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -14,16 +21,62 @@ pub struct Config {
     pub background: bool,
     #[serde(default = "default_order")]
     pub order: String,
-    #[serde(default = "default_loop")]
+    #[serde(default = "default_loop", rename = "loop", alias = "loop_playback")]
     pub loop_playback: bool,
     #[serde(default = "default_ignore_patterns")]
     pub ignore_patterns: Vec<String>,
     #[serde(default)]
     pub speed_rules: Vec<String>,
+    #[serde(default = "default_line_numbers")]
+    pub line_numbers: bool,
+    #[serde(default = "default_wrap")]
+    pub wrap: bool,
+    #[serde(default)]
+    pub commit_gap: Option<u64>,
+    #[serde(default)]
+    pub file_gap: Option<u64>,
+    #[serde(default)]
+    pub pauses: EditPauses,
     #[serde(default)]
     pub voiceover: VoiceoverConfig,
 }
 
+/// Pauses around typed edits as multiples of the typing speed, tunable under
+/// `[pauses]`. The animation engine plays them and the narration sizes itself
+/// to them, so both must read the same values.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EditPauses {
+    /// After inserting a line
+    pub insert_line: f64,
+    /// After deleting a line
+    pub delete_line: f64,
+    /// Struck-through line shown before deleting it
+    pub delete_mark: f64,
+    /// After showing a binary file's summary line
+    pub binary_summary: f64,
+    /// Between hunks
+    pub hunk: f64,
+    /// Cursor movement between lines
+    pub cursor_move: f64,
+}
+
+impl Default for EditPauses {
+    fn default() -> Self {
+        Self {
+            insert_line: 6.7,
+            delete_line: 10.0,
+            delete_mark: 20.0,
+            binary_summary: 40.0,
+            hunk: 50.0,
+            cursor_move: 0.5,
+        }
+    }
+}
+
+/// Keys that `torvax config set` can update
+pub const SETTABLE_KEYS: &[&str] = &["speed", "theme", "order", "background", "loop"];
+
 fn default_theme() -> String {
     "tokyo-night".to_string()
 }
@@ -44,6 +97,14 @@ fn default_loop() -> bool {
     false
 }
 
+fn default_line_numbers() -> bool {
+    true
+}
+
+fn default_wrap() -> bool {
+    true
+}
+
 fn default_ignore_patterns() -> Vec<String> {
     Vec::new()
 }
@@ -58,6 +119,11 @@ impl Default for Config {
             loop_playback: default_loop(),
             ignore_patterns: default_ignore_patterns(),
             speed_rules: Vec::new(),
+            line_numbers: default_line_numbers(),
+            wrap: default_wrap(),
+            commit_gap: None,
+            file_gap: None,
+            pauses: EditPauses::default(),
             voiceover: VoiceoverConfig::default(),
         }
     }
@@ -112,6 +178,8 @@ impl Config {
                 speed_array.push(rule.as_str());
             }
             doc["speed_rules"] = toml_edit::value(speed_array);
+            doc["line_numbers"] = toml_edit::value(self.line_numbers);
+            doc["wrap"] = toml_edit::value(self.wrap);
 
             doc.to_string()
         } else {
@@ -165,15 +233,46 @@ impl Config {
                  # Examples: [\"*.java:50\", \"*.xml:5\", \"*.rs:30\"]\n\
                  speed_rules = {}\n\
                  \n\
+                 # Show line numbers in the editor gutter\n\
+                 line_numbers = {}\n\
+                 \n\
+                 # Soft-wrap long lines in the editor (false cuts them off at the edge)\n\
+                 wrap = {}\n\
+                 \n\
+                 # Pauses in milliseconds between commits and between files (0 for back-to-back)\n\
+                 # commit_gap = 3000\n\
+                 # file_gap = 1500\n\
+                 \n\
                  # Voiceover settings for narrating git changes\n\
                  [voiceover]\n\
                  enabled = {}\n\
-                 provider = \"{}\"  # Options: \"inworld\" (default) or \"elevenlabs\"\n\
+                 provider = \"{}\"  # Options: \"inworld\" (default), \"elevenlabs\", \"openai\", \"google\", \"azure\", or \"piper\" (local)\n\
                  use_llm_explanations = {}  # Use OpenAI GPT-5.2 to generate detailed teaching explanations\n\
-                 # api_key = \"your-base64-api-key\"  # TTS provider API key (or use INWORLD_API_KEY/ELEVENLABS_API_KEY env var)\n\
+                 # api_key = \"your-base64-api-key\"  # TTS provider API key (or use INWORLD_API_KEY/ELEVENLABS_API_KEY env var; not needed for openai)\n\
                  # openai_api_key = \"your-openai-key\"  # OpenAI API key (required for LLM explanations, or use OPENAI_API_KEY env var)\n\
-                 # voice_id = \"Simon\"  # Optional: Inworld voice ID (default: Simon) or ElevenLabs voice ID\n\
-                 # model_id = \"inworld-tts-1.5-max\"  # Optional: Inworld model (default) or ElevenLabs model\n",
+                 # google_api_key = \"your-google-key\"  # Google Cloud API key or access token for provider = \"google\" (or use GOOGLE_API_KEY env var)\n\
+                 # azure_api_key = \"your-azure-key\"  # Azure Speech key for provider = \"azure\" (or use AZURE_SPEECH_KEY env var)\n\
+                 # azure_region = \"westeurope\"  # Azure Speech region (or use AZURE_SPEECH_REGION env var)\n\
+                 # piper_model = \"en_US-lessac-medium.onnx\"  # Piper voice model for provider = \"piper\" (offline, no key needed)\n\
+                 # piper_bin = \"piper\"  # Optional: path to the piper executable (default: piper on PATH)\n\
+                 # llm_base_url = \"http://localhost:11434/v1\"  # Optional: OpenAI-compatible endpoint for explanations (e.g. Ollama)\n\
+                 # llm_model = \"gpt-5.2\"  # Optional: chat model for explanations (e.g. llama3 with Ollama)\n\
+                 # ordering_model = \"gpt-5-mini\"  # Optional: cheaper model for ordering files (likewise grouping_model, narration_model)\n\
+                 # narration_temperature = 0.7  # Optional: 0.0-2.0, higher is more creative (ordering_temperature 0.2, grouping_temperature 0.3)\n\
+                 # voice_id = \"Simon\"  # Optional: Inworld voice ID (default: Simon), ElevenLabs voice ID, Google voice (e.g. en-US-Neural2-F), or Azure voice (e.g. en-US-JennyNeural)\n\
+                 # model_id = \"inworld-tts-1.5-max\"  # Optional: Inworld model (default) or ElevenLabs model\n\
+                 # cache = true  # Reuse narration cached in ~/.cache/torvax (disable per run with --no-cache)\n\
+                 # narration_speed = 1.0  # Playback rate for narration (e.g. 1.25 for faster speech)\n\
+                 # speech_wpm = 150  # Optional: speaking rate of the voice in words per minute, used to size narration (default depends on provider)\n\
+                 # tts_format = \"mp3\"  # Optional: audio the TTS provider returns, mp3 or wav (Piper always writes wav)\n\
+                 # tts_sample_rate = 24000  # Optional: sample rate in Hz requested from the TTS provider (default depends on provider)\n\
+                 # volume = 1.0  # Narration volume from 0.0 to 1.5 (above 1.0 may clip)\n\
+                 # request_timeout_secs = 60  # Give up on a TTS or LLM request after this many seconds\n\
+                 # language = \"es\"  # Optional: narration language (default English; picks a matching default voice)\n\
+                 # file_intros = true  # Introduce each file in one sentence as it opens (disable per run with --no-file-intros)\n\
+                 # narrate_author = true  # Mention the commit author and date in narration (false for privacy-sensitive repos)\n\
+                 # max_files = 5  # Optional: narrate only the first N files of each commit (0 disables narration)\n\
+                 # narration_prompt = \"Summarize {{file}} in {{target_words}} words:\\n{{diff}}\"  # Optional: custom prompt template (see README)\n",
                 self.theme,
                 self.speed,
                 self.background,
@@ -181,11 +280,10 @@ impl Config {
                 self.loop_playback,
                 patterns_str,
                 speed_rules_str,
+                self.line_numbers,
+                self.wrap,
                 self.voiceover.enabled,
-                match self.voiceover.provider {
-                    crate::audio::VoiceoverProvider::Inworld => "inworld",
-                    crate::audio::VoiceoverProvider::ElevenLabs => "elevenlabs",
-                },
+                self.voiceover.provider.as_str(),
                 self.voiceover.use_llm_explanations
             )
         };
@@ -194,20 +292,55 @@ impl Config {
             .with_context(|| format!("Failed to write config file: {}", config_path.display()))
     }
 
-    pub fn config_path() -> Result<PathBuf> {
-        let config_dir = dirs::home_dir()
-            .context("Failed to determine home directory")?
-            .join(".config")
-            .join("torvax");
+    /// Update a single top-level setting from its command-line string form.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "speed" => {
+                self.speed = value.parse().with_context(|| {
+                    format!(
+                        "Invalid speed '{}': expected milliseconds per character",
+                        value
+                    )
+                })?;
+            }
+            "theme" => {
+                Theme::load(value)?;
+                self.theme = value.to_string();
+            }
+            "order" => {
+                if !["random", "asc", "desc"].contains(&value) {
+                    anyhow::bail!("Invalid order '{}': expected random, asc, or desc", value);
+                }
+                self.order = value.to_string();
+            }
+            "background" => self.background = parse_bool(key, value)?,
+            "loop" => self.loop_playback = parse_bool(key, value)?,
+            _ => anyhow::bail!(
+                "Unknown config key '{}'. Valid keys: {}",
+                key,
+                SETTABLE_KEYS.join(", ")
+            ),
+        }
+        Ok(())
+    }
 
-        fs::create_dir_all(&config_dir).with_context(|| {
-            format!(
-                "Failed to create config directory: {}",
-                config_dir.display()
-            )
-        })?;
+    /// Render the resolved configuration as TOML, with API keys masked.
+    pub fn to_display_string(&self) -> Result<String> {
+        let mut shown = self.clone();
+        let mask = |key: &mut Option<String>| {
+            if key.is_some() {
+                *key = Some("********".to_string());
+            }
+        };
+        mask(&mut shown.voiceover.api_key);
+        mask(&mut shown.voiceover.openai_api_key);
+        mask(&mut shown.voiceover.google_api_key);
+        mask(&mut shown.voiceover.azure_api_key);
+        toml::to_string_pretty(&shown).context("Failed to serialize config")
+    }
 
-        Ok(config_dir.join("config.toml"))
+    pub fn config_path() -> Result<PathBuf> {
+        Ok(config_dir()?.join("config.toml"))
     }
 
     /// Write a string key into the [voiceover] table without touching the rest of the file.
@@ -262,11 +395,7 @@ impl Config {
 
     #[allow(dead_code)]
     pub fn themes_dir() -> Result<PathBuf> {
-        let config_dir = dirs::home_dir()
-            .context("Failed to determine home directory")?
-            .join(".config")
-            .join("torvax")
-            .join("themes");
+        let config_dir = config_dir()?.join("themes");
 
         fs::create_dir_all(&config_dir).with_context(|| {
             format!(
@@ -278,3 +407,220 @@ impl Config {
         Ok(config_dir)
     }
 }
+
+/// Keep the config file and cache in `dir` instead of the defaults (`--config-dir`).
+/// Only the first call has an effect.
+pub fn set_config_dir(dir: PathBuf) {
+    let _ = CONFIG_DIR_OVERRIDE.set(dir);
+}
+
+/// The directory given with `--config-dir` or `TORVAX_CONFIG_DIR`, if any.
+fn config_dir_override() -> Option<PathBuf> {
+    CONFIG_DIR_OVERRIDE.get().cloned().or_else(|| {
+        std::env::var_os("TORVAX_CONFIG_DIR")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+    })
+}
+
+/// Directory holding `config.toml`: the override if set, else `~/.config/torvax`.
+/// Created if missing.
+pub fn config_dir() -> Result<PathBuf> {
+    let dir = match config_dir_override() {
+        Some(dir) => dir,
+        None => dirs::home_dir()
+            .context("Failed to determine home directory")?
+            .join(".config")
+            .join("torvax"),
+    };
+
+    fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create config directory: {}", dir.display()))?;
+
+    Ok(dir)
+}
+
+/// Root of the narration cache and resume state: `cache` inside an overridden
+/// config directory, else `~/.cache/torvax`.
+pub fn cache_dir() -> Option<PathBuf> {
+    match config_dir_override() {
+        Some(dir) => Some(dir.join("cache")),
+        None => dirs::home_dir().map(|home| home.join(".cache").join("torvax")),
+    }
+}
+
+/// FNV-1a hash of `bytes`, for cache directory names that must stay the same
+/// across builds and Rust versions (unlike `DefaultHasher`).
+pub fn stable_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Where an effective setting came from, shown in the About overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingSource {
+    Cli,
+    Env,
+    ConfigFile,
+    Default,
+}
+
+impl SettingSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SettingSource::Cli => "cli",
+            SettingSource::Env => "env",
+            SettingSource::ConfigFile => "config",
+            SettingSource::Default => "default",
+        }
+    }
+}
+
+/// An effective setting value and its source.
+#[derive(Debug, Clone)]
+pub struct ResolvedSetting {
+    pub name: &'static str,
+    pub value: String,
+    pub source: SettingSource,
+}
+
+/// Keys present in the config file, so configured values can be told apart
+/// from defaults. Voiceover keys are stored as `voiceover.<key>`.
+#[derive(Debug, Default)]
+pub struct ConfigKeys(HashSet<String>);
+
+impl ConfigKeys {
+    /// Keys of the config file on disk; empty when it is missing or unreadable.
+    pub fn load() -> Self {
+        Config::config_path()
+            .ok()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|contents| Self::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    fn parse(contents: &str) -> Self {
+        let Ok(table) = contents.parse::<toml::Table>() else {
+            return Self::default();
+        };
+        let mut keys = HashSet::new();
+        for (key, value) in &table {
+            match (key.as_str(), value) {
+                ("voiceover", toml::Value::Table(voiceover)) => {
+                    keys.extend(voiceover.keys().map(|k| format!("voiceover.{}", k)));
+                }
+                ("loop_playback", _) => {
+                    keys.insert("loop".to_string());
+                }
+                _ => {
+                    keys.insert(key.clone());
+                }
+            }
+        }
+        Self(keys)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.0.contains(key)
+    }
+
+    /// Resolve a setting that the CLI can override: the CLI value wins, then
+    /// the config file, then the default already held in `value`.
+    pub fn setting(
+        &self,
+        name: &'static str,
+        cli: Option<impl Display>,
+        value: impl Display,
+    ) -> ResolvedSetting {
+        let (value, source) = match cli {
+            Some(cli) => (cli.to_string(), SettingSource::Cli),
+            None if self.contains(name) => (value.to_string(), SettingSource::ConfigFile),
+            None => (value.to_string(), SettingSource::Default),
+        };
+        ResolvedSetting {
+            name,
+            value,
+            source,
+        }
+    }
+}
+
+fn parse_bool(key: &str, value: &str) -> Result<bool> {
+    value.parse().with_context(|| {
+        format!(
+            "Invalid value '{}' for {}: expected true or false",
+            value, key
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_updates_known_keys() {
+        let mut config = Config::default();
+        config.set("speed", "12").unwrap();
+        config.set("order", "desc").unwrap();
+        config.set("loop", "true").unwrap();
+        config.set("background", "false").unwrap();
+        assert_eq!(config.speed, 12);
+        assert_eq!(config.order, "desc");
+        assert!(config.loop_playback);
+        assert!(!config.background);
+    }
+
+    #[test]
+    fn set_rejects_unknown_keys_and_bad_values() {
+        let mut config = Config::default();
+        let err = config.set("colour", "red").unwrap_err().to_string();
+        assert!(err.contains("speed, theme, order, background, loop"));
+        assert!(config.set("order", "sideways").is_err());
+        assert!(config.set("speed", "fast").is_err());
+    }
+
+    #[test]
+    fn pauses_fill_unset_fields_with_defaults() {
+        let config: Config = toml::from_str("[pauses]\nhunk = 80.0").unwrap();
+        assert_eq!(config.pauses.hunk, 80.0);
+        assert_eq!(config.pauses.insert_line, EditPauses::default().insert_line);
+    }
+
+    #[test]
+    fn gaps_default_to_speed_scaled_pauses() {
+        let config: Config = toml::from_str("commit_gap = 0").unwrap();
+        assert_eq!(config.commit_gap, Some(0));
+        assert_eq!(config.file_gap, None);
+    }
+
+    #[test]
+    fn loop_key_round_trips() {
+        let config: Config = toml::from_str("loop = true").unwrap();
+        assert!(config.loop_playback);
+        assert!(config.to_display_string().unwrap().contains("loop = true"));
+    }
+
+    #[test]
+    fn settings_report_where_values_came_from() {
+        let keys = ConfigKeys::parse(
+            "theme = \"nord\"\nloop_playback = true\n[voiceover]\nenabled = true\n",
+        );
+        assert!(keys.contains("loop"));
+        assert!(keys.contains("voiceover.enabled"));
+
+        let theme = keys.setting("theme", None::<&str>, "nord");
+        assert_eq!(
+            (theme.value.as_str(), theme.source),
+            ("nord", SettingSource::ConfigFile)
+        );
+        let speed = keys.setting("speed", Some(5), 30);
+        assert_eq!(
+            (speed.value.as_str(), speed.source),
+            ("5", SettingSource::Cli)
+        );
+        let order = keys.setting("order", None::<&str>, "random");
+        assert_eq!(order.source, SettingSource::Default);
+    }
+}
@@ -0,0 +1,199 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use ratatui::style::Color;
+
+use crate::theme::Theme;
+
+/// Writes terminal output as an asciicast v2 file (playable with `asciinema play`).
+pub struct CastRecorder {
+    file: BufWriter<File>,
+    start: Instant,
+    pending: Vec<u8>,
+}
+
+impl CastRecorder {
+    /// Create the cast file and write the header line.
+    pub fn create(path: &Path, width: u16, height: u16, theme: &Theme) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create cast file: {}", path.display()))?;
+        let mut recorder = Self {
+            file: BufWriter::new(file),
+            start: Instant::now(),
+            pending: Vec::new(),
+        };
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let mut header = serde_json::json!({
+            "version": 2,
+            "width": width,
+            "height": height,
+            "timestamp": timestamp,
+            "env": { "TERM": "xterm-256color" },
+        });
+        if let Some(theme) = header_theme(theme) {
+            header["theme"] = theme;
+        }
+
+        writeln!(recorder.file, "{}", header).context("Failed to write cast header")?;
+        recorder.file.flush()?;
+        Ok(recorder)
+    }
+
+    /// Buffer raw terminal bytes until the next flush.
+    pub fn record(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+    }
+
+    /// Emit buffered bytes as a single `[time, "o", data]` event and flush it to disk.
+    pub fn flush_event(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+
+        // Keep an incomplete trailing UTF-8 sequence for the next event
+        let valid_len = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+        if valid_len == 0 {
+            return Ok(());
+        }
+        let rest = self.pending.split_off(valid_len);
+        let data = String::from_utf8_lossy(&self.pending).to_string();
+        self.pending = rest;
+
+        let elapsed = self.start.elapsed().as_secs_f64();
+        let event = serde_json::json!([elapsed, "o", data]);
+        writeln!(self.file, "{}", event)?;
+        self.file.flush()
+    }
+}
+
+impl Drop for CastRecorder {
+    fn drop(&mut self) {
+        let _ = self.flush_event();
+    }
+}
+
+/// Terminal writer that tees everything written to the terminal into an optional cast recorder.
+pub struct RecordingWriter<W: Write> {
+    inner: W,
+    recorder: Option<CastRecorder>,
+}
+
+impl<W: Write> RecordingWriter<W> {
+    pub fn new(inner: W, recorder: Option<CastRecorder>) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+impl<W: Write> Write for RecordingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(&buf[..written]);
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()?;
+        if let Some(recorder) = &mut self.recorder {
+            recorder.flush_event()?;
+        }
+        Ok(())
+    }
+}
+
+fn hex(color: Color) -> Option<String> {
+    match color {
+        Color::Rgb(r, g, b) => Some(format!("#{:02x}{:02x}{:02x}", r, g, b)),
+        _ => None,
+    }
+}
+
+/// Build the optional header theme from the active theme (skipped for transparent backgrounds).
+fn header_theme(theme: &Theme) -> Option<serde_json::Value> {
+    let bg = hex(theme.background_right)?;
+    let fg = hex(theme.status_message)?;
+    let base = [
+        theme.background_left,
+        theme.file_tree_deleted,
+        theme.file_tree_added,
+        theme.status_hash,
+        theme.syntax_function,
+        theme.syntax_keyword,
+        theme.syntax_type,
+        theme.status_message,
+    ]
+    .iter()
+    .map(|c| hex(*c))
+    .collect::<Option<Vec<String>>>()?;
+
+    // 16-color palette: normal colors followed by the same set as "bright" variants
+    let palette = [base.clone(), base].concat().join(":");
+    Some(serde_json::json!({ "fg": fg, "bg": bg, "palette": palette }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cast_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("torvax_{}_{}.cast", name, std::process::id()))
+    }
+
+    #[test]
+    fn writes_header_and_output_events() {
+        let path = temp_cast_path("events");
+        {
+            let recorder = CastRecorder::create(&path, 80, 24, &Theme::default()).unwrap();
+            let mut writer = RecordingWriter::new(Vec::new(), Some(recorder));
+            writer.write_all(b"hello").unwrap();
+            writer.flush().unwrap();
+        }
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let header: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(header["version"], 2);
+        assert_eq!(header["width"], 80);
+        assert_eq!(header["height"], 24);
+        assert_eq!(header["theme"]["bg"], "#1a1b26");
+
+        let event: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(event[1], "o");
+        assert_eq!(event[2], "hello");
+    }
+
+    #[test]
+    fn split_utf8_sequence_is_carried_to_next_event() {
+        let path = temp_cast_path("utf8");
+        let mut recorder = CastRecorder::create(&path, 80, 24, &Theme::default()).unwrap();
+        let bytes = "─".as_bytes();
+
+        recorder.record(&bytes[..1]);
+        recorder.flush_event().unwrap();
+        assert_eq!(recorder.pending, &bytes[..1]);
+
+        recorder.record(&bytes[1..]);
+        recorder.flush_event().unwrap();
+        assert!(recorder.pending.is_empty());
+        drop(recorder);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(content.contains("─"));
+    }
+}
@@ -0,0 +1,110 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde_json::json;
+
+use crate::animation::PlaybackEvent;
+
+/// Streams playback events as newline-delimited JSON, one object per line,
+/// flushed as they happen so other tools can follow along live.
+pub struct EventWriter {
+    out: Box<dyn Write>,
+    // Commit of the most recent `commit_start`, attached to every event
+    commit: String,
+}
+
+impl EventWriter {
+    /// Write to the file at `path`, or to stdout when `path` is `-`.
+    pub fn create(path: &Path) -> Result<Self> {
+        let out: Box<dyn Write> = if is_stdout(path) {
+            Box::new(io::stdout())
+        } else {
+            let file = File::create(path)
+                .with_context(|| format!("Failed to create events file: {}", path.display()))?;
+            Box::new(BufWriter::new(file))
+        };
+        Ok(Self {
+            out,
+            commit: String::new(),
+        })
+    }
+
+    /// Write one event that happened `secs` seconds into the session.
+    pub fn write_event(&mut self, secs: f64, event: &PlaybackEvent) -> io::Result<()> {
+        if let PlaybackEvent::CommitStart { commit } = event {
+            self.commit = commit.clone();
+        }
+        writeln!(self.out, "{}", event_json(secs, &self.commit, event))?;
+        self.out.flush()
+    }
+}
+
+/// Whether an events path means stdout (`-`).
+pub fn is_stdout(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+fn event_json(secs: f64, commit: &str, event: &PlaybackEvent) -> serde_json::Value {
+    // Millisecond precision is plenty for syncing visuals
+    let t = (secs * 1000.0).round() / 1000.0;
+    match event {
+        PlaybackEvent::CommitStart { commit } => {
+            json!({ "t": t, "event": "commit_start", "commit": commit })
+        }
+        PlaybackEvent::FileOpen { path } => {
+            json!({ "t": t, "event": "file_open", "commit": commit, "path": path })
+        }
+        PlaybackEvent::ChunkStart { chunk_id } => {
+            json!({ "t": t, "event": "chunk_start", "commit": commit, "chunk_id": chunk_id })
+        }
+        PlaybackEvent::ChunkEnd { chunk_id } => {
+            json!({ "t": t, "event": "chunk_end", "commit": commit, "chunk_id": chunk_id })
+        }
+        PlaybackEvent::LineTyped { path, line } => json!({
+            "t": t,
+            "event": "line_typed",
+            "commit": commit,
+            "path": path,
+            "line": line,
+        }),
+        PlaybackEvent::CommitEnd { commit } => {
+            json!({ "t": t, "event": "commit_end", "commit": commit })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_carry_the_current_commit() {
+        let start = event_json(
+            0.5,
+            "",
+            &PlaybackEvent::CommitStart {
+                commit: "abc123".to_string(),
+            },
+        );
+        assert_eq!(
+            start.to_string(),
+            r#"{"commit":"abc123","event":"commit_start","t":0.5}"#
+        );
+
+        let line = event_json(
+            1.23456,
+            "abc123",
+            &PlaybackEvent::LineTyped {
+                path: "src/a.rs".to_string(),
+                line: 7,
+            },
+        );
+        assert_eq!(line["event"], "line_typed");
+        assert_eq!(line["commit"], "abc123");
+        assert_eq!(line["line"], 7);
+        assert_eq!(line["t"], 1.235);
+        assert!(is_stdout(Path::new("-")));
+    }
+}
@@ -0,0 +1,382 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::sync::mpsc::{self, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use embedded_graphics::{
+    mono_font::{ascii::FONT_6X10, MonoTextStyle},
+    pixelcolor::Rgb888,
+    prelude::{DrawTarget, OriginDimensions, Pixel, Point, RgbColor, Size},
+    text::{Baseline, Text},
+    Drawable,
+};
+use gif::{Encoder, Frame, Repeat};
+use ratatui::{buffer::Buffer, style::Color, style::Modifier};
+
+use crate::theme::{indexed_rgb, Theme};
+
+// Pixel size of one terminal cell, set by the bitmap font
+const CELL_WIDTH: u32 = 6;
+const CELL_HEIGHT: u32 = 10;
+
+// NeuQuant sampling speed for palette quantization (1 = best, 30 = fastest)
+const QUANTIZE_SPEED: i32 = 10;
+
+/// Frame rate and size limits for `--gif`.
+#[derive(Debug, Clone, Copy)]
+pub struct GifOptions {
+    pub fps: u32,
+    pub max_width: u32,
+    pub max_height: u32,
+}
+
+/// Rasterizes rendered frames with a built-in bitmap font and encodes them as an animated GIF.
+/// Frames drawn faster than the frame rate are merged, so idle stretches cost a single frame.
+/// Rasterizing and quantizing run on an encoder thread so they don't stall the render loop.
+pub struct GifRecorder {
+    frames: Option<Sender<(Buffer, Duration)>>,
+    encoder: Option<JoinHandle<Result<()>>>,
+    interval: Duration,
+    // Latest frame not yet written, and when it first appeared
+    shown: Option<(Buffer, Instant)>,
+}
+
+impl GifRecorder {
+    /// Create the GIF for a `cols` × `rows` terminal, scaled down to fit the size limits.
+    pub fn create(
+        path: &Path,
+        cols: u16,
+        rows: u16,
+        options: GifOptions,
+        theme: &Theme,
+    ) -> Result<Self> {
+        // GIF dimensions are 16-bit, so larger limits can't be honoured anyway
+        let limit = u32::from(u16::MAX);
+        let (width, height) = fit_within(
+            cols as u32 * CELL_WIDTH,
+            rows as u32 * CELL_HEIGHT,
+            options.max_width.min(limit),
+            options.max_height.min(limit),
+        );
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create GIF file: {}", path.display()))?;
+        let mut encoder = Encoder::new(BufWriter::new(file), width as u16, height as u16, &[])
+            .context("Failed to start GIF encoder")?;
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .context("Failed to write GIF header")?;
+
+        let mut frame_encoder = FrameEncoder {
+            encoder,
+            cols,
+            rows,
+            width,
+            height,
+            foreground: rgb(theme.status_message, [0xc0, 0xc0, 0xc0]),
+            background: rgb(theme.background_right, [0, 0, 0]),
+        };
+        let (frames, received) = mpsc::channel::<(Buffer, Duration)>();
+        let encoder = thread::spawn(move || {
+            for (buffer, shown_for) in received {
+                frame_encoder.write_frame(&buffer, shown_for)?;
+            }
+            Ok(())
+        });
+
+        Ok(Self {
+            frames: Some(frames),
+            encoder: Some(encoder),
+            interval: Duration::from_secs_f64(1.0 / options.fps.max(1) as f64),
+            shown: None,
+        })
+    }
+
+    /// Record a rendered frame. The previous frame is written once it has been on
+    /// screen for at least one frame interval; until then it is replaced.
+    pub fn capture(&mut self, buffer: &Buffer) -> Result<()> {
+        let now = Instant::now();
+        match self.shown.take() {
+            Some((_, since)) if now.duration_since(since) < self.interval => {
+                self.shown = Some((buffer.clone(), since));
+            }
+            Some((previous, since)) => {
+                self.send(previous, now.duration_since(since))?;
+                self.shown = Some((buffer.clone(), now));
+            }
+            None => self.shown = Some((buffer.clone(), now)),
+        }
+        Ok(())
+    }
+
+    /// Write the last frame held back by `capture` and wait for the encoder to finish.
+    pub fn finish(&mut self) -> Result<()> {
+        if let Some((buffer, _)) = self.shown.take() {
+            self.send(buffer, self.interval)?;
+        }
+        self.frames = None;
+        self.join()
+    }
+
+    fn send(&mut self, buffer: Buffer, shown_for: Duration) -> Result<()> {
+        let sent = match &self.frames {
+            Some(frames) => frames.send((buffer, shown_for)).is_ok(),
+            None => false,
+        };
+        if sent {
+            return Ok(());
+        }
+        // The encoder thread stopped early; report why
+        self.frames = None;
+        self.join()?;
+        anyhow::bail!("GIF encoder stopped")
+    }
+
+    fn join(&mut self) -> Result<()> {
+        match self.encoder.take() {
+            Some(encoder) => encoder
+                .join()
+                .map_err(|_| anyhow::anyhow!("GIF encoder thread panicked"))?,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for GifRecorder {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+/// Encoder-thread side of `GifRecorder`: rasterizes, scales and quantizes each frame.
+struct FrameEncoder {
+    encoder: Encoder<BufWriter<File>>,
+    cols: u16,
+    rows: u16,
+    width: u32,
+    height: u32,
+    foreground: [u8; 3],
+    background: [u8; 3],
+}
+
+impl FrameEncoder {
+    fn write_frame(&mut self, buffer: &Buffer, shown_for: Duration) -> Result<()> {
+        let canvas = self.rasterize(buffer);
+        let pixels = scale(
+            &canvas.pixels,
+            canvas.width,
+            canvas.height,
+            self.width,
+            self.height,
+        );
+        let mut frame = Frame::from_rgb_speed(
+            self.width as u16,
+            self.height as u16,
+            &pixels,
+            QUANTIZE_SPEED,
+        );
+        // GIF delays are in hundredths of a second
+        frame.delay = (shown_for.as_millis() / 10).clamp(1, u16::MAX as u128) as u16;
+        self.encoder
+            .write_frame(&frame)
+            .context("Failed to write GIF frame")
+    }
+
+    /// Draw every cell of the buffer at full font resolution.
+    fn rasterize(&self, buffer: &Buffer) -> Canvas {
+        let mut canvas = Canvas::new(
+            self.cols as u32 * CELL_WIDTH,
+            self.rows as u32 * CELL_HEIGHT,
+            self.background,
+        );
+        let area = buffer.area;
+        for y in 0..self.rows.min(area.height) {
+            for x in 0..self.cols.min(area.width) {
+                let Some(cell) = buffer.cell((area.x + x, area.y + y)) else {
+                    continue;
+                };
+                let mut fg = rgb(cell.fg, self.foreground);
+                let mut bg = rgb(cell.bg, self.background);
+                if cell.modifier.contains(Modifier::REVERSED) {
+                    std::mem::swap(&mut fg, &mut bg);
+                }
+                let left = x as u32 * CELL_WIDTH;
+                let top = y as u32 * CELL_HEIGHT;
+                canvas.fill(left, top, CELL_WIDTH, CELL_HEIGHT, bg);
+                canvas.glyph(cell.symbol(), left, top, fg);
+            }
+        }
+        canvas
+    }
+}
+
+/// RGB pixel buffer that the bitmap font draws into.
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, background: [u8; 3]) -> Self {
+        Self {
+            width,
+            height,
+            pixels: background.repeat((width * height) as usize),
+        }
+    }
+
+    fn set(&mut self, x: u32, y: u32, color: [u8; 3]) {
+        if x < self.width && y < self.height {
+            let i = ((y * self.width + x) * 3) as usize;
+            self.pixels[i..i + 3].copy_from_slice(&color);
+        }
+    }
+
+    fn fill(&mut self, left: u32, top: u32, width: u32, height: u32, color: [u8; 3]) {
+        for y in top..top + height {
+            for x in left..left + width {
+                self.set(x, y, color);
+            }
+        }
+    }
+
+    /// Draw one cell's symbol. Box-drawing and block characters the font lacks
+    /// are drawn as shapes so pane borders and bars survive.
+    fn glyph(&mut self, symbol: &str, left: u32, top: u32, color: [u8; 3]) {
+        let (mid_x, mid_y) = (left + CELL_WIDTH / 2, top + CELL_HEIGHT / 2);
+        match symbol {
+            "" | " " => {}
+            "─" | "━" => self.fill(left, mid_y, CELL_WIDTH, 1, color),
+            "│" | "┃" => self.fill(mid_x, top, 1, CELL_HEIGHT, color),
+            "█" => self.fill(left, top, CELL_WIDTH, CELL_HEIGHT, color),
+            "▀" => self.fill(left, top, CELL_WIDTH, CELL_HEIGHT / 2, color),
+            "▄" => self.fill(left, mid_y, CELL_WIDTH, CELL_HEIGHT / 2, color),
+            "▌" => self.fill(left, top, CELL_WIDTH / 2, CELL_HEIGHT, color),
+            "▐" => self.fill(mid_x, top, CELL_WIDTH / 2, CELL_HEIGHT, color),
+            _ => {
+                let style =
+                    MonoTextStyle::new(&FONT_6X10, Rgb888::new(color[0], color[1], color[2]));
+                let origin = Point::new(left as i32, top as i32);
+                let _ = Text::with_baseline(symbol, origin, style, Baseline::Top).draw(self);
+            }
+        }
+    }
+}
+
+impl OriginDimensions for Canvas {
+    fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+}
+
+impl DrawTarget for Canvas {
+    type Color = Rgb888;
+    type Error = std::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if point.x >= 0 && point.y >= 0 {
+                self.set(
+                    point.x as u32,
+                    point.y as u32,
+                    [color.r(), color.g(), color.b()],
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Largest size with the same aspect ratio that fits within the limits (never upscaled).
+fn fit_within(width: u32, height: u32, max_width: u32, max_height: u32) -> (u32, u32) {
+    let ratio = (max_width as f64 / width as f64)
+        .min(max_height as f64 / height as f64)
+        .min(1.0);
+    (
+        ((width as f64 * ratio) as u32).max(1),
+        ((height as f64 * ratio) as u32).max(1),
+    )
+}
+
+/// Nearest-neighbor resize of an RGB image.
+fn scale(pixels: &[u8], width: u32, height: u32, to_width: u32, to_height: u32) -> Vec<u8> {
+    if (width, height) == (to_width, to_height) {
+        return pixels.to_vec();
+    }
+    let mut scaled = Vec::with_capacity((to_width * to_height * 3) as usize);
+    for y in 0..to_height {
+        let src_y = y * height / to_height;
+        for x in 0..to_width {
+            let src_x = x * width / to_width;
+            let i = ((src_y * width + src_x) * 3) as usize;
+            scaled.extend_from_slice(&pixels[i..i + 3]);
+        }
+    }
+    scaled
+}
+
+/// RGB value of a terminal color; `Reset` (the terminal default) becomes `default`.
+fn rgb(color: Color, default: [u8; 3]) -> [u8; 3] {
+    match color {
+        Color::Reset => default,
+        Color::Rgb(r, g, b) => [r, g, b],
+        Color::Indexed(i) => indexed_rgb(i),
+        Color::Black => indexed_rgb(0),
+        Color::Red => indexed_rgb(1),
+        Color::Green => indexed_rgb(2),
+        Color::Yellow => indexed_rgb(3),
+        Color::Blue => indexed_rgb(4),
+        Color::Magenta => indexed_rgb(5),
+        Color::Cyan => indexed_rgb(6),
+        Color::Gray => indexed_rgb(7),
+        Color::DarkGray => indexed_rgb(8),
+        Color::LightRed => indexed_rgb(9),
+        Color::LightGreen => indexed_rgb(10),
+        Color::LightYellow => indexed_rgb(11),
+        Color::LightBlue => indexed_rgb(12),
+        Color::LightMagenta => indexed_rgb(13),
+        Color::LightCyan => indexed_rgb(14),
+        Color::White => indexed_rgb(15),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn frames_are_scaled_to_fit_and_encoded() {
+        assert_eq!(fit_within(720, 300, 360, 1000), (360, 150));
+        assert_eq!(fit_within(720, 300, 2000, 2000), (720, 300));
+        assert_eq!(indexed_rgb(196), [255, 0, 0]);
+        assert_eq!(indexed_rgb(244), [128, 128, 128]);
+
+        let path = std::env::temp_dir().join(format!("torvax_{}.gif", std::process::id()));
+        let options = GifOptions {
+            fps: 10,
+            max_width: 60,
+            max_height: 60,
+        };
+        {
+            let mut recorder =
+                GifRecorder::create(&path, 20, 4, options, &Theme::default()).unwrap();
+            let mut buffer = Buffer::empty(Rect::new(0, 0, 20, 4));
+            buffer.set_string(0, 0, "hi │ ─", ratatui::style::Style::default());
+            recorder.capture(&buffer).unwrap();
+        }
+
+        let bytes = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(&bytes[..6], b"GIF89a");
+        // Logical screen size: 20×4 cells of 6×10 px, halved to fit 60 px wide
+        assert_eq!(u16::from_le_bytes([bytes[6], bytes[7]]), 60);
+        assert_eq!(u16::from_le_bytes([bytes[8], bytes[9]]), 20);
+    }
+}
@@ -0,0 +1,13 @@
+pub(crate) mod asciicast;
+pub(crate) mod events;
+pub(crate) mod gif;
+pub(crate) mod report;
+pub(crate) mod srt;
+pub(crate) mod wav;
+
+pub use asciicast::{CastRecorder, RecordingWriter};
+pub use events::EventWriter;
+pub use gif::{GifOptions, GifRecorder};
+pub use report::ReportWriter;
+pub use srt::SrtWriter;
+pub use wav::NarrationTrackWriter;
@@ -0,0 +1,137 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::audio::{DiffChunk, COMMIT_INTRO_PATH, COMMIT_OUTRO_PATH};
+use crate::git::{CommitMetadata, DiffHunk};
+
+/// Writes per-chunk narration as a Markdown code review, one section per commit.
+pub struct ReportWriter {
+    file: BufWriter<File>,
+}
+
+impl ReportWriter {
+    pub fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create report file: {}", path.display()))?;
+        Ok(Self {
+            file: BufWriter::new(file),
+        })
+    }
+
+    /// Write a commit heading, then each file's explanations in narration order,
+    /// headed by the line ranges of the hunks they cover, between the spoken commit
+    /// intro and outro when there are any.
+    pub fn write_commit(
+        &mut self,
+        metadata: &CommitMetadata,
+        chunks: &[DiffChunk],
+    ) -> io::Result<()> {
+        let short_hash = &metadata.hash[..7.min(metadata.hash.len())];
+        let subject = metadata.message.lines().next().unwrap_or("");
+        writeln!(self.file, "# {} {}\n", short_hash, subject)?;
+        writeln!(
+            self.file,
+            "{} · {}\n",
+            metadata.author,
+            metadata.date.format("%Y-%m-%d %H:%M")
+        )?;
+
+        if chunks.is_empty() {
+            writeln!(
+                self.file,
+                "_No explanations were generated for this commit._\n"
+            )?;
+        }
+
+        let mut current_file: Option<&str> = None;
+        for chunk in chunks {
+            // The commit intro reads as a lead paragraph, the outro as a summary
+            if chunk.file_path == COMMIT_INTRO_PATH {
+                writeln!(self.file, "{}\n", chunk.explanation)?;
+                continue;
+            }
+            if chunk.file_path == COMMIT_OUTRO_PATH {
+                writeln!(self.file, "## Summary\n\n{}\n", chunk.explanation)?;
+                continue;
+            }
+            if current_file != Some(chunk.file_path.as_str()) {
+                writeln!(self.file, "## {}\n", chunk.file_path)?;
+                current_file = Some(&chunk.file_path);
+            }
+            // A file intro covers no particular hunk and reads as the section's lead
+            if chunk.hunk_indices.is_empty() {
+                writeln!(self.file, "{}\n", chunk.explanation)?;
+                continue;
+            }
+
+            let hunks = metadata
+                .changes
+                .iter()
+                .find(|c| c.path == chunk.file_path)
+                .map(|c| c.hunks.as_slice())
+                .unwrap_or(&[]);
+            writeln!(
+                self.file,
+                "### {}\n\n{}\n",
+                hunk_ranges(hunks, &chunk.hunk_indices),
+                chunk.explanation
+            )?;
+        }
+
+        self.file.flush()
+    }
+}
+
+/// Heading for a chunk, e.g. `Lines 12-18, 40-44`. Hunks that only delete
+/// lines are named by their range in the old file.
+fn hunk_ranges(hunks: &[DiffHunk], indices: &[usize]) -> String {
+    let ranges: Vec<String> = indices
+        .iter()
+        .filter_map(|&i| hunks.get(i))
+        .map(|hunk| {
+            let (start, len, suffix) = if hunk.new_lines == 0 {
+                (hunk.old_start, hunk.old_lines, " (removed)")
+            } else {
+                (hunk.new_start, hunk.new_lines, "")
+            };
+            if len <= 1 {
+                format!("{}{}", start, suffix)
+            } else {
+                format!("{}-{}{}", start, start + len - 1, suffix)
+            }
+        })
+        .collect();
+
+    match ranges.len() {
+        0 => "Changes".to_string(),
+        1 if !ranges[0].contains('-') => format!("Line {}", ranges[0]),
+        _ => format!("Lines {}", ranges.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hunk(old_start: usize, old_lines: usize, new_start: usize, new_lines: usize) -> DiffHunk {
+        DiffHunk {
+            old_start,
+            old_lines,
+            new_start,
+            new_lines,
+            lines: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn headings_name_new_file_ranges() {
+        let hunks = [hunk(10, 3, 12, 7), hunk(38, 2, 40, 5), hunk(70, 4, 80, 0)];
+        assert_eq!(hunk_ranges(&hunks, &[0, 1]), "Lines 12-18, 40-44");
+        assert_eq!(hunk_ranges(&hunks, &[2]), "Lines 70-73 (removed)");
+        assert_eq!(hunk_ranges(&[hunk(1, 1, 5, 1)], &[0]), "Line 5");
+        assert_eq!(hunk_ranges(&hunks, &[9]), "Changes");
+    }
+}
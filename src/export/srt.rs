@@ -0,0 +1,104 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+// Long explanations are split into cues of at most this many words
+const MAX_CUE_WORDS: usize = 16;
+
+/// Writes narration as SRT subtitle cues.
+pub struct SrtWriter {
+    file: BufWriter<File>,
+    next_index: usize,
+    last_end: f64,
+}
+
+impl SrtWriter {
+    pub fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create subtitle file: {}", path.display()))?;
+        Ok(Self {
+            file: BufWriter::new(file),
+            next_index: 1,
+            last_end: 0.0,
+        })
+    }
+
+    /// Write one narration chunk starting at `start_secs` and lasting `duration_secs`.
+    /// Chunks queue behind each other in the audio sink, so a chunk never starts
+    /// before the previous one ended.
+    pub fn write_chunk(
+        &mut self,
+        start_secs: f64,
+        duration_secs: f64,
+        text: &str,
+    ) -> io::Result<()> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        if words.is_empty() {
+            return Ok(());
+        }
+
+        let mut start = start_secs.max(self.last_end);
+        let secs_per_word = duration_secs.max(0.0) / words.len() as f64;
+
+        for cue_words in words.chunks(MAX_CUE_WORDS) {
+            let end = start + secs_per_word * cue_words.len() as f64;
+            writeln!(
+                self.file,
+                "{}\n{} --> {}\n{}\n",
+                self.next_index,
+                timestamp(start),
+                timestamp(end),
+                cue_words.join(" ")
+            )?;
+            self.next_index += 1;
+            start = end;
+        }
+
+        self.last_end = start;
+        self.file.flush()
+    }
+}
+
+/// Format seconds as an SRT timestamp (`HH:MM:SS,mmm`).
+fn timestamp(secs: f64) -> String {
+    let total_ms = (secs.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        total_ms / 3_600_000,
+        (total_ms / 60_000) % 60,
+        (total_ms / 1000) % 60,
+        total_ms % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamp_formats_hours_minutes_millis() {
+        assert_eq!(timestamp(0.0), "00:00:00,000");
+        assert_eq!(timestamp(3725.5), "01:02:05,500");
+    }
+
+    #[test]
+    fn chunks_queue_behind_previous_cue() {
+        let path = std::env::temp_dir().join(format!("torvax_srt_{}.srt", std::process::id()));
+        {
+            let mut writer = SrtWriter::create(&path).unwrap();
+            writer.write_chunk(1.0, 2.0, "first chunk").unwrap();
+            // Triggered while the first chunk is still playing
+            writer.write_chunk(2.0, 1.0, "second").unwrap();
+        }
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            content,
+            "1\n00:00:01,000 --> 00:00:03,000\nfirst chunk\n\n\
+             2\n00:00:03,000 --> 00:00:04,000\nsecond\n\n"
+        );
+    }
+}
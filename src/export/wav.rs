@@ -0,0 +1,187 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Cursor, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, Sender};
+use std::thread::{self, JoinHandle};
+
+use anyhow::{Context, Result};
+use rodio::source::UniformSourceIterator;
+use rodio::{Decoder, Source};
+
+// Mono at CD rate covers every provider's output without audible resampling
+const SAMPLE_RATE: u32 = 44_100;
+const CHANNELS: u16 = 1;
+const BYTES_PER_SAMPLE: u32 = 2;
+const HEADER_LEN: u32 = 44;
+
+/// Writes the narration as a single 16-bit PCM WAV track on the session
+/// timeline, with silence wherever nothing is being said, so it lines up
+/// with a screen recording started at the same moment. Decoding and
+/// resampling run on a writer thread so they don't stall rendering.
+pub struct NarrationTrackWriter {
+    commands: Sender<TrackCommand>,
+    writer: JoinHandle<Result<()>>,
+}
+
+enum TrackCommand {
+    Chunk {
+        start_secs: f64,
+        audio: Vec<u8>,
+        speed: f32,
+    },
+    Finish {
+        end_secs: f64,
+    },
+}
+
+impl NarrationTrackWriter {
+    pub fn create(path: &Path) -> Result<Self> {
+        let mut track = TrackFile::create(path)?;
+        let (commands, received) = mpsc::channel();
+        let writer = thread::spawn(move || {
+            for command in received {
+                match command {
+                    TrackCommand::Chunk {
+                        start_secs,
+                        audio,
+                        speed,
+                    } => track.write_chunk(start_secs, audio, speed)?,
+                    TrackCommand::Finish { end_secs } => return track.finish(end_secs),
+                }
+            }
+            Ok(())
+        });
+        Ok(Self { commands, writer })
+    }
+
+    /// Add a chunk's encoded audio starting at `start_secs`, played at `speed`.
+    /// Like the audio sink, a chunk never starts before the previous one ended.
+    /// A chunk that can't be decoded is left out with a warning.
+    pub fn write_chunk(&mut self, start_secs: f64, audio: Vec<u8>, speed: f32) -> Result<()> {
+        self.commands
+            .send(TrackCommand::Chunk {
+                start_secs,
+                audio,
+                speed,
+            })
+            .map_err(|_| anyhow::anyhow!("Audio track writer stopped"))
+    }
+
+    /// Pad with silence to `end_secs`, fill in the header sizes and wait for the
+    /// writer thread to finish.
+    pub fn finish(self, end_secs: f64) -> Result<()> {
+        // A writer that already stopped reports its error on join
+        let _ = self.commands.send(TrackCommand::Finish { end_secs });
+        self.writer
+            .join()
+            .map_err(|_| anyhow::anyhow!("Audio track writer panicked"))?
+    }
+}
+
+/// Writer-thread side of `NarrationTrackWriter`: the WAV file being filled.
+struct TrackFile {
+    file: BufWriter<File>,
+    samples_written: u64,
+}
+
+impl TrackFile {
+    fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create audio file: {}", path.display()))?;
+        let mut file = BufWriter::new(file);
+        // Sizes are patched in by `finish` once the length is known
+        write_header(&mut file, 0)?;
+        Ok(Self {
+            file,
+            samples_written: 0,
+        })
+    }
+
+    fn write_chunk(&mut self, start_secs: f64, audio: Vec<u8>, speed: f32) -> Result<()> {
+        let source = match Decoder::new(Cursor::new(audio)) {
+            Ok(source) => source.speed(speed),
+            Err(e) => {
+                log::warn!(
+                    "Leaving undecodable narration out of the audio track: {}",
+                    e
+                );
+                return Ok(());
+            }
+        };
+        self.pad_to(start_secs)?;
+        for sample in UniformSourceIterator::<_, i16>::new(source, CHANNELS, SAMPLE_RATE) {
+            self.file.write_all(&sample.to_le_bytes())?;
+            self.samples_written += 1;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self, end_secs: f64) -> Result<()> {
+        self.pad_to(end_secs)?;
+        let data_len = u32::try_from(self.samples_written * BYTES_PER_SAMPLE as u64)
+            .context("Narration track is too long for a WAV file")?;
+        self.file.seek(SeekFrom::Start(0))?;
+        write_header(&mut self.file, data_len)?;
+        self.file.flush().context("Failed to write audio file")
+    }
+
+    fn pad_to(&mut self, secs: f64) -> io::Result<()> {
+        let target = (secs.max(0.0) * SAMPLE_RATE as f64 * CHANNELS as f64) as u64;
+        while self.samples_written < target {
+            self.file.write_all(&0i16.to_le_bytes())?;
+            self.samples_written += 1;
+        }
+        Ok(())
+    }
+}
+
+fn write_header(out: &mut impl Write, data_len: u32) -> io::Result<()> {
+    let block_align = CHANNELS as u32 * BYTES_PER_SAMPLE;
+    out.write_all(b"RIFF")?;
+    out.write_all(&(HEADER_LEN - 8 + data_len).to_le_bytes())?;
+    out.write_all(b"WAVEfmt ")?;
+    out.write_all(&16u32.to_le_bytes())?;
+    // PCM
+    out.write_all(&1u16.to_le_bytes())?;
+    out.write_all(&CHANNELS.to_le_bytes())?;
+    out.write_all(&SAMPLE_RATE.to_le_bytes())?;
+    out.write_all(&(SAMPLE_RATE * block_align).to_le_bytes())?;
+    out.write_all(&(block_align as u16).to_le_bytes())?;
+    out.write_all(&(BYTES_PER_SAMPLE as u16 * 8).to_le_bytes())?;
+    out.write_all(b"data")?;
+    out.write_all(&data_len.to_le_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_are_placed_on_the_timeline_with_silence_between() {
+        let click = include_bytes!("../audio/keystroke.wav");
+        let click_samples = UniformSourceIterator::<_, i16>::new(
+            Decoder::new(Cursor::new(click)).unwrap(),
+            1,
+            44_100,
+        )
+        .count() as u64;
+
+        let path = std::env::temp_dir().join(format!("torvax_track_{}.wav", std::process::id()));
+        let mut writer = NarrationTrackWriter::create(&path).unwrap();
+        writer.write_chunk(0.5, click.to_vec(), 1.0).unwrap();
+        // Undecodable audio is skipped rather than ending the track
+        writer.write_chunk(0.6, vec![0, 1, 2], 1.0).unwrap();
+        writer.finish(1.0).unwrap();
+
+        let track = Decoder::new(Cursor::new(std::fs::read(&path).unwrap())).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(track.channels(), 1);
+        assert_eq!(track.sample_rate(), SAMPLE_RATE);
+        let samples: Vec<i16> = track.collect();
+        assert_eq!(samples.len(), SAMPLE_RATE as usize);
+        assert!(samples[..22_050].iter().all(|&s| s == 0));
+        assert!(samples[22_050..22_050 + click_samples as usize]
+            .iter()
+            .any(|&s| s != 0));
+    }
+}
@@ -5,7 +5,8 @@ use git2::{Commit as Git2Commit, Delta, DiffOptions, Oid, Repository};
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use rand::RngExt;
 use std::cell::RefCell;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 // Thread-safe global pattern matcher for user-defined ignore patterns
@@ -24,6 +25,7 @@ pub enum DiffMode {
     #[default]
     Staged, // Only staged changes (index vs HEAD)
     Unstaged, // Only unstaged changes (workdir vs index)
+    All,      // Staged and unstaged changes together (workdir vs HEAD)
 }
 
 // Files to exclude from diff animation (lock files and generated files)
@@ -130,6 +132,53 @@ pub fn should_exclude_file(path: &str) -> bool {
     false
 }
 
+/// Convert one `.gitignore` line to globs for `init_ignore_patterns`.
+/// Comments, blank lines and negations (`!pattern`) yield nothing, since
+/// re-including a file can't be expressed as another exclusion.
+fn gitignore_globs(line: &str) -> Vec<String> {
+    let line = line.trim_end();
+    if line.is_empty() || line.starts_with('#') || line.starts_with('!') {
+        return Vec::new();
+    }
+
+    // A trailing slash matches directories only
+    let (pattern, dir_only) = match line.strip_suffix('/') {
+        Some(dir) => (dir, true),
+        None => (line, false),
+    };
+    // A leading or inner slash anchors the pattern to the repository root
+    let base = match pattern.strip_prefix('/') {
+        Some(anchored) => anchored.to_string(),
+        None if pattern.contains('/') => pattern.to_string(),
+        None => format!("**/{}", pattern),
+    };
+    if base.is_empty() {
+        return Vec::new();
+    }
+
+    let contents = format!("{}/**", base);
+    let globs = if dir_only {
+        vec![contents]
+    } else {
+        vec![base, contents]
+    };
+    globs.into_iter().filter(|g| Glob::new(g).is_ok()).collect()
+}
+
+/// The user's global excludes file: `core.excludesFile`, else `$XDG_CONFIG_HOME/git/ignore`.
+fn global_excludes_file(repo: &Repository) -> Option<PathBuf> {
+    let configured = repo
+        .config()
+        .ok()
+        .and_then(|c| c.get_path("core.excludesFile").ok());
+    configured.or_else(|| {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+            .map(|dir| dir.join("git").join("ignore"))
+    })
+}
+
 // Check if a commit matches the author filter pattern (case-insensitive partial match)
 fn matches_author(commit: &Git2Commit, pattern: &str) -> bool {
     let author = commit.author();
@@ -140,6 +189,53 @@ fn matches_author(commit: &Git2Commit, pattern: &str) -> bool {
     name.to_lowercase().contains(&pattern_lower) || email.to_lowercase().contains(&pattern_lower)
 }
 
+/// Commit message filter (`--grep`)
+#[derive(Debug, Clone)]
+pub enum MessageFilter {
+    /// Case-insensitive substring match (pattern stored lowercased)
+    Contains(String),
+    Regex(regex::Regex),
+}
+
+impl MessageFilter {
+    pub fn new(pattern: &str, use_regex: bool) -> Result<Self> {
+        if use_regex {
+            let re = regex::Regex::new(pattern)
+                .with_context(|| format!("Invalid --grep regex: '{}'", pattern))?;
+            Ok(Self::Regex(re))
+        } else {
+            Ok(Self::Contains(pattern.to_lowercase()))
+        }
+    }
+
+    fn matches(&self, message: &str) -> bool {
+        match self {
+            Self::Contains(pattern) => message.to_lowercase().contains(pattern),
+            Self::Regex(re) => re.is_match(message),
+        }
+    }
+}
+
+/// Which commits to play by parent count (a merge has two or more parents)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeFilter {
+    /// Skip merge commits (`--no-merges`, the default)
+    #[default]
+    Exclude,
+    /// Only merge commits, diffed against their first parent (`--merges-only`)
+    Only,
+}
+
+impl MergeFilter {
+    fn matches(self, commit: &Git2Commit) -> bool {
+        let is_merge = commit.parent_count() >= 2;
+        match self {
+            Self::Exclude => !is_merge,
+            Self::Only => is_merge,
+        }
+    }
+}
+
 // Parse a date string using chrono-english (supports Git-like formats)
 pub fn parse_date(input: &str) -> Result<DateTime<Utc>> {
     let now = Local::now();
@@ -173,6 +269,196 @@ fn matches_date_filter(
     Ok(true)
 }
 
+const BINARY_SUMMARY_PREFIX: &str = "Binary file changed (";
+
+/// Summary line standing in for a binary file's diff.
+fn binary_summary(old_size: u64, new_size: u64) -> String {
+    format!(
+        "{}{} bytes → {} bytes)",
+        BINARY_SUMMARY_PREFIX, old_size, new_size
+    )
+}
+
+/// Whether a diff line is the summary produced for a binary file.
+pub fn is_binary_summary(line: &str) -> bool {
+    line.starts_with(BINARY_SUMMARY_PREFIX) && line.ends_with(" bytes)")
+}
+
+/// Single added line describing a binary change, shown instead of its content.
+fn binary_hunk(old_size: u64, new_size: u64) -> DiffHunk {
+    DiffHunk {
+        old_start: 0,
+        old_lines: 0,
+        new_start: 1,
+        new_lines: 1,
+        lines: vec![LineChange {
+            change_type: LineChangeType::Addition,
+            content: binary_summary(old_size, new_size),
+            old_line_no: None,
+            new_line_no: None,
+            inline_changes: Vec::new(),
+        }],
+    }
+}
+
+/// Catches binary content git did not flag, e.g. files forced to text by attributes.
+fn patch_has_null_bytes(patch: &git2::Patch) -> bool {
+    (0..patch.num_hunks()).any(|hunk_idx| {
+        let num_lines = patch.num_lines_in_hunk(hunk_idx).unwrap_or(0);
+        (0..num_lines).any(|line_idx| {
+            patch
+                .line_in_hunk(hunk_idx, line_idx)
+                .is_ok_and(|line| line.content().contains(&0))
+        })
+    })
+}
+
+type CharRanges = Vec<(usize, usize)>;
+
+// Upper bound on token pairs compared per line (keeps the LCS table small)
+const MAX_WORD_DIFF_CELLS: usize = 40_000;
+
+/// Pair each run of deleted lines with the added lines that follow it and record
+/// which words changed within each pair.
+fn annotate_inline_changes(lines: &mut [LineChange]) {
+    let mut i = 0;
+    while i < lines.len() {
+        if !matches!(lines[i].change_type, LineChangeType::Deletion) {
+            i += 1;
+            continue;
+        }
+
+        let del_start = i;
+        while i < lines.len() && matches!(lines[i].change_type, LineChangeType::Deletion) {
+            i += 1;
+        }
+        let add_start = i;
+        while i < lines.len() && matches!(lines[i].change_type, LineChangeType::Addition) {
+            i += 1;
+        }
+
+        let pairs = (add_start - del_start).min(i - add_start);
+        for offset in 0..pairs {
+            let (old_ranges, new_ranges) = word_diff(
+                &lines[del_start + offset].content,
+                &lines[add_start + offset].content,
+            );
+            lines[del_start + offset].inline_changes = old_ranges;
+            lines[add_start + offset].inline_changes = new_ranges;
+        }
+    }
+}
+
+/// Split a line into word, whitespace and punctuation tokens on grapheme boundaries.
+/// Returns `(char_start, char_end, text)` for each token.
+fn tokenize_words(line: &str) -> Vec<(usize, usize, String)> {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    #[derive(PartialEq)]
+    enum Class {
+        Word,
+        Space,
+        Other,
+    }
+    let class_of = |g: &str| {
+        let c = g.chars().next().unwrap_or(' ');
+        if c.is_alphanumeric() || c == '_' {
+            Class::Word
+        } else if c.is_whitespace() {
+            Class::Space
+        } else {
+            Class::Other
+        }
+    };
+
+    let mut tokens: Vec<(usize, usize, String)> = Vec::new();
+    let mut last_class = Class::Other;
+    let mut pos = 0;
+    for g in line.graphemes(true) {
+        let len = g.chars().count();
+        let class = class_of(g);
+        let extend = !tokens.is_empty() && class != Class::Other && class == last_class;
+        match tokens.last_mut() {
+            Some(last) if extend => {
+                last.1 += len;
+                last.2.push_str(g);
+            }
+            _ => tokens.push((pos, pos + len, g.to_string())),
+        }
+        last_class = class;
+        pos += len;
+    }
+    tokens
+}
+
+/// Word-level diff of two versions of a line. Returns the changed character ranges
+/// in the old and new line; both are empty when the lines share no words.
+fn word_diff(old: &str, new: &str) -> (CharRanges, CharRanges) {
+    let old_tokens = tokenize_words(old.trim_end_matches(['\n', '\r']));
+    let new_tokens = tokenize_words(new.trim_end_matches(['\n', '\r']));
+    let (n, m) = (old_tokens.len(), new_tokens.len());
+    if n == 0 || m == 0 || n * m > MAX_WORD_DIFF_CELLS {
+        return (Vec::new(), Vec::new());
+    }
+
+    // Longest common subsequence over tokens
+    let mut table = vec![vec![0u32; m + 1]; n + 1];
+    for a in (0..n).rev() {
+        for b in (0..m).rev() {
+            table[a][b] = if old_tokens[a].2 == new_tokens[b].2 {
+                table[a + 1][b + 1] + 1
+            } else {
+                table[a + 1][b].max(table[a][b + 1])
+            };
+        }
+    }
+
+    let mut old_kept = vec![false; n];
+    let mut new_kept = vec![false; m];
+    let mut shares_words = false;
+    let (mut a, mut b) = (0, 0);
+    while a < n && b < m {
+        if old_tokens[a].2 == new_tokens[b].2 {
+            old_kept[a] = true;
+            new_kept[b] = true;
+            shares_words |= !old_tokens[a].2.trim().is_empty();
+            a += 1;
+            b += 1;
+        } else if table[a + 1][b] >= table[a][b + 1] {
+            a += 1;
+        } else {
+            b += 1;
+        }
+    }
+
+    if !shares_words {
+        return (Vec::new(), Vec::new());
+    }
+
+    let ranges = |tokens: &[(usize, usize, String)], kept: &[bool]| {
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for (token, _) in tokens.iter().zip(kept).filter(|(_, kept)| !**kept) {
+            match ranges.last_mut() {
+                Some(last) if last.1 == token.0 => last.1 = token.1,
+                _ => ranges.push((token.0, token.1)),
+            }
+        }
+        ranges
+    };
+
+    (
+        ranges(&old_tokens, &old_kept),
+        ranges(&new_tokens, &new_kept),
+    )
+}
+
+/// One commit of a range, listed in the chapters overlay
+#[derive(Debug, Clone)]
+pub struct RangeChapter {
+    pub hash: String,
+    pub subject: String,
+}
+
 pub struct GitRepository {
     repo: Repository,
     commit_cache: RefCell<Option<Vec<Oid>>>,
@@ -180,9 +466,22 @@ pub struct GitRepository {
     // These modes are mutually exclusive based on CLI arguments.
     commit_index: RefCell<usize>,
     commit_range: RefCell<Option<Vec<Oid>>>,
+    range_chapters: RefCell<Vec<RangeChapter>>,
     author_filter: Option<String>,
+    message_filter: Option<MessageFilter>,
+    path_filter: Option<GlobSet>,
     before_filter: Option<DateTime<Utc>>,
     after_filter: Option<DateTime<Utc>>,
+    merge_filter: MergeFilter,
+    // Smallest and largest number of changed lines a played commit may have
+    min_changes: Option<usize>,
+    max_changes: Option<usize>,
+    skipped_commits: HashSet<Oid>,
+    // File history mode: the followed file's names (after, before) per commit
+    file_scope: HashMap<Oid, [String; 2]>,
+    max_commits: Option<usize>,
+    // Commits handed out since the last reset, checked against max_commits
+    played_count: RefCell<usize>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -235,8 +534,10 @@ pub struct LineChange {
     pub content: String,
     #[allow(dead_code)]
     pub old_line_no: Option<usize>,
-    #[allow(dead_code)]
     pub new_line_no: Option<usize>,
+    /// Changed character ranges `[start, end)` within a modified line,
+    /// aligned to grapheme boundaries. Empty when the whole line changed.
+    pub inline_changes: Vec<(usize, usize)>,
 }
 
 #[derive(Debug, Clone)]
@@ -244,7 +545,6 @@ pub struct DiffHunk {
     pub old_start: usize,
     #[allow(dead_code)]
     pub old_lines: usize,
-    #[allow(dead_code)]
     pub new_start: usize,
     #[allow(dead_code)]
     pub new_lines: usize,
@@ -257,7 +557,6 @@ pub struct FileChange {
     #[allow(dead_code)]
     pub old_path: Option<String>,
     pub status: FileStatus,
-    #[allow(dead_code)]
     pub is_binary: bool,
     pub is_excluded: bool,
     pub exclusion_reason: Option<String>,
@@ -267,6 +566,87 @@ pub struct FileChange {
     pub hunks: Vec<DiffHunk>,
     #[allow(dead_code)]
     pub diff: String,
+    /// Language the file is written in (e.g. `Rust`), when recognized
+    pub language: Option<&'static str>,
+}
+
+impl FileChange {
+    /// Text form of the diff sent for narration, one `@@` header per hunk
+    /// so chunk hunk indices line up with `hunks` and durations can be estimated.
+    pub fn narration_diff(&self) -> String {
+        let mut diff = String::new();
+
+        for hunk in &self.hunks {
+            // Include hunk header so calculate_animation_duration can parse it
+            diff.push_str(&format!(
+                "@@ -{},{} +{},{} @@\n",
+                hunk.old_start, hunk.old_lines, hunk.new_start, hunk.new_lines
+            ));
+            for line in &hunk.lines {
+                match line.change_type {
+                    LineChangeType::Addition => {
+                        diff.push_str(&format!("+{}\n", line.content));
+                    }
+                    LineChangeType::Deletion => {
+                        diff.push_str(&format!("-{}\n", line.content));
+                    }
+                    LineChangeType::Context => {
+                        diff.push_str(&format!(" {}\n", line.content));
+                    }
+                }
+            }
+        }
+
+        diff
+    }
+
+    /// Number of added and deleted lines across all hunks
+    pub fn line_counts(&self) -> (usize, usize) {
+        let mut additions = 0;
+        let mut deletions = 0;
+        for line in self.hunks.iter().flat_map(|h| &h.lines) {
+            match line.change_type {
+                LineChangeType::Addition => additions += 1,
+                LineChangeType::Deletion => deletions += 1,
+                LineChangeType::Context => {}
+            }
+        }
+        (additions, deletions)
+    }
+
+    /// Whether the file changes more than `threshold` lines and is shown collapsed.
+    /// Excluded and binary files are never collapsed.
+    pub fn exceeds_collapse_threshold(&self, threshold: Option<usize>) -> bool {
+        let (additions, deletions) = self.line_counts();
+        !self.is_excluded
+            && !self.is_binary
+            && threshold.is_some_and(|max| additions + deletions > max)
+    }
+
+    /// Narration diff for a collapsed file: one hunk whose header gives the size of
+    /// the change, followed by an excerpt of its first changed lines.
+    pub fn collapsed_narration_diff(&self) -> String {
+        const EXCERPT_LINES: usize = 20;
+        let (additions, deletions) = self.line_counts();
+        let mut diff = format!(
+            "@@ large change collapsed in playback: +{} -{} lines, excerpt follows @@\n",
+            additions, deletions
+        );
+        for line in self
+            .hunks
+            .iter()
+            .flat_map(|h| &h.lines)
+            .filter(|l| !matches!(l.change_type, LineChangeType::Context))
+            .take(EXCERPT_LINES)
+        {
+            let sign = match line.change_type {
+                LineChangeType::Deletion => '-',
+                _ => '+',
+            };
+            diff.push_str(&format!("{}{}\n", sign, line.content));
+        }
+        diff
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -279,6 +659,25 @@ pub struct CommitMetadata {
 }
 
 impl CommitMetadata {
+    /// Index of the changed file at `path`, matched exactly or else by a unique
+    /// trailing part of its path (`main.rs` finds `src/main.rs`).
+    pub fn find_file(&self, path: &str) -> Option<usize> {
+        let path = path.trim_start_matches("./");
+        if let Some(index) = self.changes.iter().position(|c| c.path == path) {
+            return Some(index);
+        }
+        let suffix = format!("/{}", path);
+        let mut matches = self
+            .changes
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.path.ends_with(&suffix));
+        match (matches.next(), matches.next()) {
+            (Some((index, _)), None) => Some(index),
+            _ => None,
+        }
+    }
+
     /// Returns indices sorted in FileTree display order (directory -> filename)
     pub fn sorted_file_indices(&self) -> Vec<usize> {
         let mut indices: Vec<usize> = (0..self.changes.len()).collect();
@@ -300,6 +699,32 @@ impl CommitMetadata {
     }
 }
 
+/// Parse the text of a unified diff (as printed by `git diff`) into file changes,
+/// without a repository. File contents aren't available, so only the hunks are filled in.
+pub fn parse_unified_diff(text: &str) -> Result<Vec<FileChange>> {
+    let diff = git2::Diff::from_buffer(text.as_bytes()).context("Failed to parse diff")?;
+    GitRepository::extract_changes_from_diff_with_content(&diff, |_| (None, None))
+}
+
+/// Change to `path` between two snapshots of its content, e.g. between two saves
+/// replayed by `diff --watch`. `None` when the content didn't change.
+pub fn content_delta(path: &str, old: &str, new: &str) -> Result<Option<FileChange>> {
+    if old == new {
+        return Ok(None);
+    }
+    let path_ref = Some(Path::new(path));
+    let mut patch =
+        git2::Patch::from_buffers(old.as_bytes(), path_ref, new.as_bytes(), path_ref, None)
+            .context("Failed to diff file contents")?;
+    let text = patch.to_buf().context("Failed to print diff")?;
+    let Some(mut change) = parse_unified_diff(&String::from_utf8_lossy(&text))?.pop() else {
+        return Ok(None);
+    };
+    change.old_content = Some(old.to_string());
+    change.new_content = Some(new.to_string());
+    Ok(Some(change))
+}
+
 impl GitRepository {
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
         let repo = Repository::open(path).context("Failed to open Git repository")?;
@@ -308,25 +733,90 @@ impl GitRepository {
             commit_cache: RefCell::new(None),
             commit_index: RefCell::new(0),
             commit_range: RefCell::new(None),
+            range_chapters: RefCell::new(Vec::new()),
             author_filter: None,
+            message_filter: None,
+            path_filter: None,
             before_filter: None,
             after_filter: None,
+            merge_filter: MergeFilter::default(),
+            min_changes: None,
+            max_changes: None,
+            skipped_commits: HashSet::new(),
+            file_scope: HashMap::new(),
+            max_commits: None,
+            played_count: RefCell::new(0),
         })
     }
 
+    /// Whether `path` (absolute, or relative to the working tree) is ignored by the
+    /// repository's ignore rules, e.g. build output under `target/`.
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let relative = self
+            .repo
+            .workdir()
+            .and_then(|dir| path.strip_prefix(dir).ok())
+            .unwrap_or(path);
+        self.repo.is_path_ignored(relative).unwrap_or(false)
+    }
+
+    /// Globs for the patterns in the top-level `.gitignore`, `.git/info/exclude`
+    /// and the global excludes file, so ignored files stay out of the walkthrough.
+    pub fn gitignore_patterns(&self) -> Vec<String> {
+        let mut files = Vec::new();
+        if let Some(workdir) = self.repo.workdir() {
+            files.push(workdir.join(".gitignore"));
+        }
+        files.push(self.repo.path().join("info").join("exclude"));
+        files.extend(global_excludes_file(&self.repo));
+
+        files
+            .iter()
+            .filter_map(|path| std::fs::read_to_string(path).ok())
+            .flat_map(|content| {
+                content
+                    .lines()
+                    .flat_map(gitignore_globs)
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// URLs of the repository's remotes, e.g. to tell which project a clone is of.
+    pub fn remote_urls(&self) -> Vec<String> {
+        let Ok(names) = self.repo.remotes() else {
+            return Vec::new();
+        };
+        names
+            .iter()
+            .flatten()
+            .filter_map(|name| self.repo.find_remote(name).ok())
+            .filter_map(|remote| remote.url().map(String::from))
+            .collect()
+    }
+
+    /// Whether a commit is present locally (e.g. fetched from a remote).
+    pub fn has_commit(&self, hash: &str) -> bool {
+        self.resolve_commit(hash).is_ok()
+    }
+
     pub fn get_commit(&self, hash: &str) -> Result<CommitMetadata> {
+        let commit = self.resolve_commit(hash)?;
+        self.commit_metadata(&commit)
+    }
+
+    fn resolve_commit(&self, hash: &str) -> Result<Git2Commit<'_>> {
         let hash = Self::normalize_ref(hash);
         let obj = self
             .repo
             .revparse_single(&hash)
             .context("Invalid commit hash or commit not found")?;
 
-        let commit = obj.peel_to_commit().context("Object is not a commit")?;
-
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        obj.peel_to_commit().context("Object is not a commit")
     }
 
     pub fn random_commit(&self) -> Result<CommitMetadata> {
+        self.take_commit_slot()?;
         self.populate_cache()?;
 
         let cache = self.commit_cache.borrow();
@@ -337,10 +827,11 @@ impl GitRepository {
             .context("Failed to select random commit")?;
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.commit_metadata(&commit)
     }
 
     pub fn next_asc_commit(&self) -> Result<CommitMetadata> {
+        self.take_commit_slot()?;
         self.populate_cache()?;
 
         let cache = self.commit_cache.borrow();
@@ -364,10 +855,11 @@ impl GitRepository {
         *index += 1;
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.commit_metadata(&commit)
     }
 
     pub fn next_desc_commit(&self) -> Result<CommitMetadata> {
+        self.take_commit_slot()?;
         self.populate_cache()?;
 
         let cache = self.commit_cache.borrow();
@@ -388,17 +880,76 @@ impl GitRepository {
         *index += 1;
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.commit_metadata(&commit)
     }
 
     pub fn reset_index(&self) {
         *self.commit_index.borrow_mut() = 0;
+        *self.played_count.borrow_mut() = 0;
+    }
+
+    pub fn set_max_commits(&mut self, max: Option<usize>) {
+        self.max_commits = max;
+    }
+
+    // Count one more played commit, failing once the max_commits cap is reached
+    fn take_commit_slot(&self) -> Result<()> {
+        let mut played = self.played_count.borrow_mut();
+        if let Some(max) = self.max_commits {
+            if *played >= max {
+                anyhow::bail!("Reached the limit of {} commits", max);
+            }
+        }
+        *played += 1;
+        Ok(())
     }
 
     pub fn set_author_filter(&mut self, author: Option<String>) {
         self.author_filter = author;
     }
 
+    pub fn set_message_filter(&mut self, filter: Option<MessageFilter>) {
+        self.message_filter = filter;
+    }
+
+    /// Only yield commits touching files that match one of the globs (`--path-filter`)
+    pub fn set_path_filter(&mut self, patterns: &[String]) -> Result<()> {
+        if patterns.is_empty() {
+            self.path_filter = None;
+            return Ok(());
+        }
+
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let glob =
+                Glob::new(pattern).with_context(|| format!("Invalid glob pattern: {}", pattern))?;
+            builder.add(glob);
+        }
+        self.path_filter = Some(builder.build().context("Failed to build glob set")?);
+        Ok(())
+    }
+
+    /// Only yield commits changing between `min` and `max` lines (`--min-changes`,
+    /// `--max-changes`), counted like `changed_lines`.
+    pub fn set_change_filter(&mut self, min: Option<usize>, max: Option<usize>) -> Result<()> {
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                anyhow::bail!(
+                    "--min-changes ({}) is larger than --max-changes ({})",
+                    min,
+                    max
+                );
+            }
+        }
+        self.min_changes = min;
+        self.max_changes = max;
+        Ok(())
+    }
+
+    pub fn set_merge_filter(&mut self, filter: MergeFilter) {
+        self.merge_filter = filter;
+    }
+
     pub fn set_before_filter(&mut self, before: Option<DateTime<Utc>>) {
         self.before_filter = before;
     }
@@ -407,14 +958,89 @@ impl GitRepository {
         self.after_filter = after;
     }
 
+    /// Leave these commits out of history and range playback (`--skip-commit`).
+    /// Short hashes are resolved up front so typos fail instead of skipping nothing.
+    pub fn set_skipped_commits(&mut self, hashes: &[String]) -> Result<()> {
+        self.skipped_commits = hashes
+            .iter()
+            .map(|hash| {
+                self.resolve_commit(hash)
+                    .map(|commit| commit.id())
+                    .with_context(|| format!("Cannot skip commit '{}'", hash))
+            })
+            .collect::<Result<_>>()?;
+        Ok(())
+    }
+
+    /// Range of the commits after `tag` up to HEAD, for `set_commit_range`.
+    pub fn range_since_tag(&self, tag: &str) -> Result<String> {
+        let tag_ref = format!("refs/tags/{}", tag);
+        self.repo
+            .revparse_single(&tag_ref)
+            .with_context(|| format!("Tag not found: {}", tag))?;
+        Ok(format!("{}..HEAD", tag_ref))
+    }
+
     pub fn set_commit_range(&self, range: &str) -> Result<()> {
         let commits = self.parse_commit_range(range)?;
+        self.set_range_commits(commits)
+    }
+
+    /// Play exactly the listed commits in the given order, like a range. Entries
+    /// that don't resolve to a commit are skipped and returned.
+    pub fn set_commit_list(&self, hashes: &[String]) -> Result<Vec<String>> {
+        let mut commits = Vec::new();
+        let mut skipped = Vec::new();
+        for hash in hashes {
+            match self.resolve_commit(hash) {
+                Ok(commit) => commits.push(commit.id()),
+                Err(_) => skipped.push(hash.clone()),
+            }
+        }
+        if commits.is_empty() {
+            anyhow::bail!("No valid commits in the list");
+        }
+        self.set_range_commits(commits)?;
+        Ok(skipped)
+    }
+
+    fn set_range_commits(&self, commits: Vec<Oid>) -> Result<()> {
+        let chapters = commits
+            .iter()
+            .map(|oid| {
+                let commit = self.repo.find_commit(*oid)?;
+                Ok(RangeChapter {
+                    hash: oid.to_string(),
+                    subject: commit.summary().unwrap_or("").to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
         *self.commit_range.borrow_mut() = Some(commits);
-        *self.commit_index.borrow_mut() = 0;
+        *self.range_chapters.borrow_mut() = chapters;
+        self.reset_index();
         Ok(())
     }
 
+    /// Commits of the range set by `set_commit_range`, oldest first.
+    pub fn range_chapters(&self) -> Vec<RangeChapter> {
+        self.range_chapters.borrow().clone()
+    }
+
+    /// 0-based position of `hash` within the range and the range length.
+    pub fn range_position(&self, hash: &str) -> Option<(usize, usize)> {
+        let chapters = self.range_chapters.borrow();
+        let position = chapters.iter().position(|c| c.hash == hash)?;
+        Some((position, chapters.len()))
+    }
+
+    /// Make the next `next_range_commit_*` call return the commit `played` steps
+    /// into its order (0 = first).
+    pub fn set_range_position(&self, played: usize) {
+        *self.commit_index.borrow_mut() = played;
+    }
+
     pub fn next_range_commit_asc(&self) -> Result<CommitMetadata> {
+        self.take_commit_slot()?;
         let range = self.commit_range.borrow();
         let commits = range.as_ref().context("Commit range not set")?;
         let mut index = self.commit_index.borrow_mut();
@@ -431,10 +1057,11 @@ impl GitRepository {
         *index += 1;
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.commit_metadata(&commit)
     }
 
     pub fn next_range_commit_desc(&self) -> Result<CommitMetadata> {
+        self.take_commit_slot()?;
         let range = self.commit_range.borrow();
         let commits = range.as_ref().context("Commit range not set")?;
         let mut index = self.commit_index.borrow_mut();
@@ -453,10 +1080,11 @@ impl GitRepository {
         *index += 1;
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.commit_metadata(&commit)
     }
 
     pub fn random_range_commit(&self) -> Result<CommitMetadata> {
+        self.take_commit_slot()?;
         let range = self.commit_range.borrow();
         let commits = range.as_ref().context("Commit range not set")?;
 
@@ -469,10 +1097,10 @@ impl GitRepository {
             .context("Failed to select random commit")?;
 
         let commit = self.repo.find_commit(*selected_oid)?;
-        Self::extract_metadata_with_changes(&self.repo, &commit)
+        self.commit_metadata(&commit)
     }
 
-    // Collect non-merge commits from a revwalk, applying author and date filters if set
+    // Collect commits from a revwalk, applying the merge, author and date filters
     fn collect_commits_from_revwalk(
         &self,
         revwalk: git2::Revwalk,
@@ -481,19 +1109,7 @@ impl GitRepository {
         let mut commits = Vec::new();
         for oid in revwalk.filter_map(|oid| oid.ok()) {
             if let Ok(commit) = self.repo.find_commit(oid) {
-                if commit.parent_count() <= 1 {
-                    if let Some(ref pattern) = self.author_filter {
-                        if !matches_author(&commit, pattern) {
-                            continue;
-                        }
-                    }
-                    if !matches_date_filter(
-                        &commit,
-                        self.before_filter.as_ref(),
-                        self.after_filter.as_ref(),
-                    )? {
-                        continue;
-                    }
+                if self.matches_filters(&commit)? {
                     commits.push(oid);
                 }
             }
@@ -501,17 +1117,125 @@ impl GitRepository {
 
         if commits.is_empty() {
             if self.author_filter.is_some()
+                || self.message_filter.is_some()
+                || self.path_filter.is_some()
                 || self.before_filter.is_some()
                 || self.after_filter.is_some()
+                || !self.skipped_commits.is_empty()
             {
                 anyhow::bail!("No commits found matching the filters {}", context);
             }
-            anyhow::bail!("No non-merge commits found {}", context);
+            match self.merge_filter {
+                MergeFilter::Exclude => anyhow::bail!("No non-merge commits found {}", context),
+                MergeFilter::Only => anyhow::bail!("No merge commits found {}", context),
+            }
         }
 
         Ok(commits)
     }
 
+    // Whether a commit passes the skip list and the merge, author, message, path, size and date filters
+    fn matches_filters(&self, commit: &Git2Commit) -> Result<bool> {
+        if self.skipped_commits.contains(&commit.id()) || !self.merge_filter.matches(commit) {
+            return Ok(false);
+        }
+        // Cheapest checks first: the date is in the header, the size needs a full diff
+        if !matches_date_filter(
+            commit,
+            self.before_filter.as_ref(),
+            self.after_filter.as_ref(),
+        )? {
+            return Ok(false);
+        }
+        if let Some(ref pattern) = self.author_filter {
+            if !matches_author(commit, pattern) {
+                return Ok(false);
+            }
+        }
+        if let Some(ref filter) = self.message_filter {
+            if !filter.matches(commit.message().unwrap_or("")) {
+                return Ok(false);
+            }
+        }
+        if let Some(ref filter) = self.path_filter {
+            if !self.touches_filtered_path(commit, filter)? {
+                return Ok(false);
+            }
+        }
+        if self.min_changes.is_some() || self.max_changes.is_some() {
+            let changed = self.changed_lines(commit)?;
+            if self.min_changes.is_some_and(|min| changed < min)
+                || self.max_changes.is_some_and(|max| changed > max)
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Play the history of one file, oldest first, following renames (`--file`).
+    /// Each commit is narrowed to that file under the name it had at the time.
+    pub fn set_file_history(&mut self, path: &str) -> Result<()> {
+        let mut name = path.trim_start_matches("./").to_string();
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+        // Newest first, so a rename switches to the old name for the commits before it
+        let mut commits = Vec::new();
+        let mut scope = HashMap::new();
+        for oid in revwalk.filter_map(|oid| oid.ok()) {
+            let commit = self.repo.find_commit(oid)?;
+            if commit.parent_count() > 1 {
+                continue;
+            }
+            let Some(previous_name) = self.follow_file(&commit, &name)? else {
+                continue;
+            };
+            if self.matches_filters(&commit)? {
+                commits.push(oid);
+                scope.insert(oid, [name.clone(), previous_name.clone()]);
+            }
+            name = previous_name;
+        }
+
+        if commits.is_empty() {
+            anyhow::bail!("No commits found touching {}", path);
+        }
+        commits.reverse();
+        self.file_scope = scope;
+        self.set_range_commits(commits)
+    }
+
+    // If the commit changes the file called `name`, the file's name before the
+    // commit (different when the commit renamed it)
+    fn follow_file(&self, commit: &Git2Commit, name: &str) -> Result<Option<String>> {
+        let tree = commit.tree().context("Failed to get commit tree")?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree().context("Failed to get parent tree")?),
+            Err(_) => None,
+        };
+        let mut diff = self
+            .repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .context("Failed to diff commit")?;
+        diff.find_similar(Some(git2::DiffFindOptions::new().renames(true)))
+            .context("Failed to detect renames")?;
+
+        let previous = diff.deltas().find_map(|delta| {
+            let new_path = delta.new_file().path()?.to_str()?;
+            (new_path == name).then(|| {
+                delta
+                    .old_file()
+                    .path()
+                    .and_then(|p| p.to_str())
+                    .unwrap_or(name)
+                    .to_string()
+            })
+        });
+        Ok(previous)
+    }
+
     /// Normalize shorthand commit refs before passing to libgit2.
     /// Converts `HEAD@N` → `HEAD~N` (e.g. `HEAD@3..HEAD` → `HEAD~3..HEAD`).
     fn normalize_ref(s: &str) -> String {
@@ -593,6 +1317,161 @@ impl GitRepository {
         Ok(())
     }
 
+    // Diff of a commit against its first parent, without reading file contents
+    fn commit_diff(&self, commit: &Git2Commit) -> Result<git2::Diff<'_>> {
+        let tree = commit.tree().context("Failed to get commit tree")?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree().context("Failed to get parent tree")?),
+            Err(_) => None,
+        };
+        self.repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .context("Failed to diff commit")
+    }
+
+    // Check whether a commit modified at least one file matching the path filter
+    fn touches_filtered_path(&self, commit: &Git2Commit, filter: &GlobSet) -> Result<bool> {
+        let diff = self.commit_diff(commit)?;
+        Ok(diff.deltas().any(|delta| {
+            [delta.new_file().path(), delta.old_file().path()]
+                .into_iter()
+                .flatten()
+                .any(|path| filter.is_match(path))
+        }))
+    }
+
+    /// Added plus deleted lines of a commit across the files it would play: lock
+    /// and generated files don't count, and only files matching the path filter do.
+    fn changed_lines(&self, commit: &Git2Commit) -> Result<usize> {
+        let diff = self.commit_diff(commit)?;
+        let mut total = 0;
+        for (i, delta) in diff.deltas().enumerate() {
+            let Some(path) = delta
+                .new_file()
+                .path()
+                .or_else(|| delta.old_file().path())
+                .and_then(|p| p.to_str())
+            else {
+                continue;
+            };
+            let filtered_out = self
+                .path_filter
+                .as_ref()
+                .is_some_and(|filter| !filter.is_match(path));
+            if should_exclude_file(path) || filtered_out {
+                continue;
+            }
+            if let Some(patch) = git2::Patch::from_diff(&diff, i)? {
+                let (_, additions, deletions) = patch.line_stats()?;
+                total += additions + deletions;
+            }
+        }
+        Ok(total)
+    }
+
+    // Extract metadata, narrowed to the files matching the path filter if set,
+    // and to the followed file in file history mode
+    fn commit_metadata(&self, commit: &Git2Commit) -> Result<CommitMetadata> {
+        let mut metadata = Self::extract_metadata_with_changes(&self.repo, commit)?;
+        self.retain_filtered_paths(&mut metadata.changes);
+        if let Some(names) = self.file_scope.get(&commit.id()) {
+            metadata
+                .changes
+                .retain(|change| names.contains(&change.path));
+        }
+        Ok(metadata)
+    }
+
+    fn retain_filtered_paths(&self, changes: &mut Vec<FileChange>) {
+        if let Some(ref filter) = self.path_filter {
+            changes.retain(|change| {
+                filter.is_match(&change.path)
+                    || change
+                        .old_path
+                        .as_deref()
+                        .is_some_and(|old| filter.is_match(old))
+            });
+        }
+    }
+
+    /// Net diff of `<rev_a>...<rev_b>` (from their merge base to `rev_b`) as a single
+    /// synthetic commit. An empty `rev_b` means HEAD.
+    ///
+    /// The hash is derived from both revisions so narration for a comparison is
+    /// cached separately from the narration of the tip commit itself.
+    pub fn diff_refs(&self, spec: &str) -> Result<CommitMetadata> {
+        let spec = Self::normalize_ref(spec);
+        let Some((rev_a, rev_b)) = spec.split_once("...") else {
+            anyhow::bail!(
+                "Invalid comparison: {}. Use the form 'main...feature'",
+                spec
+            );
+        };
+        if rev_a.is_empty() {
+            anyhow::bail!("Invalid comparison: {}. The base revision is missing", spec);
+        }
+        let rev_b = if rev_b.is_empty() { "HEAD" } else { rev_b };
+
+        let resolve = |rev: &str| {
+            self.repo
+                .revparse_single(rev)
+                .and_then(|obj| obj.peel_to_commit())
+                .with_context(|| format!("Revision not found: {}", rev))
+        };
+        let commit_a = resolve(rev_a)?;
+        let commit_b = resolve(rev_b)?;
+
+        let base_oid = self
+            .repo
+            .merge_base(commit_a.id(), commit_b.id())
+            .with_context(|| format!("No common ancestor between {} and {}", rev_a, rev_b))?;
+        let base_tree = self
+            .repo
+            .find_commit(base_oid)
+            .and_then(|c| c.tree())
+            .context("Failed to get merge base tree")?;
+        let tip_tree = commit_b.tree().context("Failed to get commit tree")?;
+
+        let mut diff_opts = DiffOptions::new();
+        diff_opts.context_lines(3);
+        let mut diff = self
+            .repo
+            .diff_tree_to_tree(Some(&base_tree), Some(&tip_tree), Some(&mut diff_opts))
+            .context("Failed to diff revisions")?;
+        diff.find_similar(Some(git2::DiffFindOptions::new().renames(true)))
+            .context("Failed to detect renames")?;
+        let mut changes =
+            self.extract_changes_from_diff(&diff, Some(&base_tree), Some(&tip_tree))?;
+        self.retain_filtered_paths(&mut changes);
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(commit_b.id())?;
+        revwalk.hide(base_oid)?;
+        let commit_count = revwalk.count();
+
+        let hash = Oid::hash_object(
+            git2::ObjectType::Blob,
+            format!("compare {}...{}", base_oid, commit_b.id()).as_bytes(),
+        )?
+        .to_string();
+        let author = commit_b.author();
+
+        Ok(CommitMetadata {
+            hash,
+            author: author.name().unwrap_or("Unknown").to_string(),
+            date: DateTime::from_timestamp(author.when().seconds(), 0).unwrap_or_else(Utc::now),
+            message: format!(
+                "Compare {}...{}\n\n{} commit{} since merge base {}",
+                rev_a,
+                rev_b,
+                commit_count,
+                if commit_count == 1 { "" } else { "s" },
+                &base_oid.to_string()[..7]
+            ),
+            changes,
+        })
+    }
+
     fn extract_metadata_with_changes(
         repo: &Repository,
         commit: &Git2Commit,
@@ -629,7 +1508,7 @@ impl GitRepository {
         let mut diff_opts = DiffOptions::new();
         diff_opts.context_lines(3);
 
-        let diff = match repo.diff_tree_to_tree(
+        let mut diff = match repo.diff_tree_to_tree(
             parent_tree.as_ref(),
             Some(&commit_tree),
             Some(&mut diff_opts),
@@ -637,6 +1516,9 @@ impl GitRepository {
             Ok(d) => d,
             Err(_) => return Ok(Vec::new()), // Skip if diff fails
         };
+        // A renamed file plays as one change rather than a deletion and an addition
+        diff.find_similar(Some(git2::DiffFindOptions::new().renames(true)))
+            .context("Failed to detect renames")?;
 
         let mut changes = Vec::new();
 
@@ -664,8 +1546,6 @@ impl GitRepository {
                 None
             };
 
-            let is_binary = delta.new_file().is_binary() || delta.old_file().is_binary();
-
             let old_content = if let Some(parent_tree) = parent_tree.as_ref() {
                 if let Some(old_file_path) = delta.old_file().path() {
                     parent_tree
@@ -702,69 +1582,7 @@ impl GitRepository {
                 None
             };
 
-            let mut hunks = Vec::new();
-            let mut diff_text = String::new();
-
-            if let Ok(Some(mut patch)) = git2::Patch::from_diff(&diff, i) {
-                if let Ok(patch_str) = patch.to_buf() {
-                    diff_text = String::from_utf8_lossy(patch_str.as_ref()).to_string();
-                }
-
-                if !is_binary {
-                    for hunk_idx in 0..patch.num_hunks() {
-                        if let Ok((hunk, _hunk_lines)) = patch.hunk(hunk_idx) {
-                            let mut lines = Vec::new();
-                            let num_lines = patch.num_lines_in_hunk(hunk_idx).unwrap_or(0);
-
-                            let mut old_line_no = hunk.old_start() as usize;
-                            let mut new_line_no = hunk.new_start() as usize;
-
-                            for line_idx in 0..num_lines {
-                                if let Ok(line) = patch.line_in_hunk(hunk_idx, line_idx) {
-                                    let content =
-                                        String::from_utf8_lossy(line.content()).to_string();
-                                    let origin = line.origin();
-
-                                    let (change_type, old_no, new_no) = match origin {
-                                        '+' => {
-                                            let no = new_line_no;
-                                            new_line_no += 1;
-                                            (LineChangeType::Addition, None, Some(no))
-                                        }
-                                        '-' => {
-                                            let no = old_line_no;
-                                            old_line_no += 1;
-                                            (LineChangeType::Deletion, Some(no), None)
-                                        }
-                                        _ => {
-                                            let old_no = old_line_no;
-                                            let new_no = new_line_no;
-                                            old_line_no += 1;
-                                            new_line_no += 1;
-                                            (LineChangeType::Context, Some(old_no), Some(new_no))
-                                        }
-                                    };
-
-                                    lines.push(LineChange {
-                                        change_type,
-                                        content,
-                                        old_line_no: old_no,
-                                        new_line_no: new_no,
-                                    });
-                                }
-                            }
-
-                            hunks.push(DiffHunk {
-                                old_start: hunk.old_start() as usize,
-                                old_lines: hunk.old_lines() as usize,
-                                new_start: hunk.new_start() as usize,
-                                new_lines: hunk.new_lines() as usize,
-                                lines,
-                            });
-                        }
-                    }
-                }
-            }
+            let (hunks, diff_text, is_binary) = Self::extract_hunks_from_diff(&diff, i)?;
 
             // Calculate total changed lines (additions + deletions)
             let total_changed_lines: usize = hunks
@@ -785,6 +1603,10 @@ impl GitRepository {
                 (false, None)
             };
 
+            let language = crate::syntax::detect_language(
+                &path,
+                new_content.as_deref().or(old_content.as_deref()),
+            );
             changes.push(FileChange {
                 path,
                 old_path,
@@ -796,6 +1618,7 @@ impl GitRepository {
                 new_content,
                 hunks,
                 diff: diff_text,
+                language,
             });
         }
 
@@ -806,15 +1629,18 @@ impl GitRepository {
     ///
     /// DiffMode::Staged - Only staged changes (index vs HEAD)
     /// DiffMode::Unstaged - Only unstaged changes (workdir vs index)
+    /// DiffMode::All - Staged and unstaged changes together (workdir vs HEAD)
     pub fn get_working_tree_diff(&self, mode: DiffMode) -> Result<CommitMetadata> {
         let changes = match mode {
             DiffMode::Staged => self.extract_staged_changes()?,
             DiffMode::Unstaged => self.extract_unstaged_changes()?,
+            DiffMode::All => self.extract_all_changes()?,
         };
 
         let message = match mode {
             DiffMode::Staged => "Staged changes",
             DiffMode::Unstaged => "Unstaged changes",
+            DiffMode::All => "Staged and unstaged changes",
         };
 
         Ok(CommitMetadata {
@@ -870,6 +1696,34 @@ impl GitRepository {
         self.extract_changes_from_diff_workdir(&diff, &index)
     }
 
+    /// Extract staged and unstaged changes together (workdir vs HEAD). A file
+    /// changed in both appears once, diffed from HEAD to the working tree.
+    fn extract_all_changes(&self) -> Result<Vec<FileChange>> {
+        let head_tree = self
+            .repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_tree().ok());
+
+        let mut diff_opts = DiffOptions::new();
+        diff_opts.context_lines(3);
+        diff_opts.include_untracked(true);
+
+        // Going through the index picks up staged additions and deletions too
+        let diff = self
+            .repo
+            .diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut diff_opts))
+            .context("Failed to diff tree to workdir")?;
+
+        Self::extract_changes_from_diff_with_content(&diff, |delta| {
+            let old_content = head_tree
+                .as_ref()
+                .and_then(|tree| self.get_blob_content_from_tree(tree, delta.old_file().path()));
+            let new_content = self.get_workdir_content(delta.new_file().path());
+            (old_content, new_content)
+        })
+    }
+
     /// Extract FileChange data from a git2::Diff (for staged changes)
     fn extract_changes_from_diff(
         &self,
@@ -877,7 +1731,7 @@ impl GitRepository {
         old_tree: Option<&git2::Tree>,
         new_tree: Option<&git2::Tree>,
     ) -> Result<Vec<FileChange>> {
-        self.extract_changes_from_diff_with_content(diff, |delta| {
+        Self::extract_changes_from_diff_with_content(diff, |delta| {
             let old_content = old_tree
                 .and_then(|tree| self.get_blob_content_from_tree(tree, delta.old_file().path()));
             let new_content = if let Some(tree) = new_tree {
@@ -896,7 +1750,7 @@ impl GitRepository {
         diff: &git2::Diff,
         index: &git2::Index,
     ) -> Result<Vec<FileChange>> {
-        self.extract_changes_from_diff_with_content(diff, |delta| {
+        Self::extract_changes_from_diff_with_content(diff, |delta| {
             let old_content = self.get_index_content_from(index, delta.old_file().path());
             let new_content = self.get_workdir_content(delta.new_file().path());
             (old_content, new_content)
@@ -905,7 +1759,6 @@ impl GitRepository {
 
     /// Common diff extraction logic with pluggable content retrieval
     fn extract_changes_from_diff_with_content<F>(
-        &self,
         diff: &git2::Diff,
         get_content: F,
     ) -> Result<Vec<FileChange>>
@@ -938,9 +1791,8 @@ impl GitRepository {
                 None
             };
 
-            let is_binary = delta.new_file().is_binary() || delta.old_file().is_binary();
             let (old_content, new_content) = get_content(&delta);
-            let (hunks, diff_text) = self.extract_hunks_from_diff(diff, i, is_binary)?;
+            let (hunks, diff_text, is_binary) = Self::extract_hunks_from_diff(diff, i)?;
 
             // Calculate total changed lines
             let total_changed_lines: usize = hunks
@@ -960,6 +1812,10 @@ impl GitRepository {
                 (false, None)
             };
 
+            let language = crate::syntax::detect_language(
+                &path,
+                new_content.as_deref().or(old_content.as_deref()),
+            );
             changes.push(FileChange {
                 path,
                 old_path,
@@ -971,6 +1827,7 @@ impl GitRepository {
                 new_content,
                 hunks,
                 diff: diff_text,
+                language,
             });
         }
 
@@ -993,22 +1850,34 @@ impl GitRepository {
         }
     }
 
-    /// Extract hunks from a diff at given delta index
+    /// Extract hunks from a diff at given delta index, and whether the file is binary.
+    /// Binary files get a single synthetic hunk summarizing the size change.
     fn extract_hunks_from_diff(
-        &self,
         diff: &git2::Diff,
         delta_idx: usize,
-        is_binary: bool,
-    ) -> Result<(Vec<DiffHunk>, String)> {
+    ) -> Result<(Vec<DiffHunk>, String, bool)> {
         let mut hunks = Vec::new();
         let mut diff_text = String::new();
+        let mut is_binary = false;
 
         if let Ok(Some(mut patch)) = git2::Patch::from_diff(diff, delta_idx) {
             if let Ok(patch_str) = patch.to_buf() {
                 diff_text = String::from_utf8_lossy(patch_str.as_ref()).to_string();
             }
 
-            if !is_binary {
+            // Binary detection is only reliable once the patch has loaded both sides
+            let delta = patch.delta();
+            is_binary = delta.flags().is_binary()
+                || delta.old_file().is_binary()
+                || delta.new_file().is_binary()
+                || patch_has_null_bytes(&patch);
+
+            if is_binary {
+                hunks.push(binary_hunk(
+                    delta.old_file().size(),
+                    delta.new_file().size(),
+                ));
+            } else {
                 for hunk_idx in 0..patch.num_hunks() {
                     if let Ok((hunk, _hunk_lines)) = patch.hunk(hunk_idx) {
                         let mut lines = Vec::new();
@@ -1019,8 +1888,12 @@ impl GitRepository {
 
                         for line_idx in 0..num_lines {
                             if let Ok(line) = patch.line_in_hunk(hunk_idx, line_idx) {
-                                let content = String::from_utf8_lossy(line.content()).to_string();
                                 let origin = line.origin();
+                                // "\ No newline at end of file" markers aren't lines of the file
+                                if matches!(origin, '=' | '>' | '<') {
+                                    continue;
+                                }
+                                let content = String::from_utf8_lossy(line.content()).to_string();
 
                                 let (change_type, old_no, new_no) = match origin {
                                     '+' => {
@@ -1047,10 +1920,12 @@ impl GitRepository {
                                     content,
                                     old_line_no: old_no,
                                     new_line_no: new_no,
+                                    inline_changes: Vec::new(),
                                 });
                             }
                         }
 
+                        annotate_inline_changes(&mut lines);
                         hunks.push(DiffHunk {
                             old_start: hunk.old_start() as usize,
                             old_lines: hunk.old_lines() as usize,
@@ -1063,7 +1938,7 @@ impl GitRepository {
             }
         }
 
-        Ok((hunks, diff_text))
+        Ok((hunks, diff_text, is_binary))
     }
 
     /// Get file content from the current index
@@ -1220,6 +2095,35 @@ mod tests {
         assert!(!should_exclude_file("src/index.js"));
     }
 
+    #[test]
+    fn test_gitignore_lines_become_globs() {
+        assert_eq!(gitignore_globs("*.log"), vec!["**/*.log", "**/*.log/**"]);
+        assert_eq!(gitignore_globs("target/"), vec!["**/target/**"]);
+        assert_eq!(gitignore_globs("/dist"), vec!["dist", "dist/**"]);
+        assert_eq!(
+            gitignore_globs("docs/generated/"),
+            vec!["docs/generated/**"]
+        );
+        assert!(gitignore_globs("# comment").is_empty());
+        assert!(gitignore_globs("!keep.log").is_empty());
+        assert!(gitignore_globs("   ").is_empty());
+
+        let globs: Vec<String> = ["*.log", "target/"]
+            .iter()
+            .flat_map(|l| gitignore_globs(l))
+            .collect();
+        let mut builder = GlobSetBuilder::new();
+        for g in &globs {
+            builder.add(Glob::new(g).unwrap());
+        }
+        let set = builder.build().unwrap();
+        assert!(set.is_match("debug.log"));
+        assert!(set.is_match("logs/today.log"));
+        assert!(set.is_match("target/debug/app"));
+        assert!(set.is_match("crates/cli/target/out.rs"));
+        assert!(!set.is_match("src/target.rs"));
+    }
+
     #[test]
     fn test_empty_patterns() {
         let patterns: Vec<String> = vec![];
@@ -1281,6 +2185,33 @@ mod tests {
 
             Self { path, repo }
         }
+
+        fn commit_file(&self, name: &str, content: &str, message: &str) {
+            std::fs::write(self.path.join(name), content).unwrap();
+            let mut index = self.repo.index().unwrap();
+            index.add_path(std::path::Path::new(name)).unwrap();
+            index.write().unwrap();
+            let tree_id = index.write_tree().unwrap();
+            let tree = self.repo.find_tree(tree_id).unwrap();
+            let sig = self.repo.signature().unwrap();
+            let parent = self.repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+            let parents: Vec<&git2::Commit> = parent.iter().collect();
+            self.repo
+                .commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+                .unwrap();
+        }
+
+        fn rename_file(&self, from: &str, to: &str, message: &str) {
+            std::fs::rename(self.path.join(from), self.path.join(to)).unwrap();
+            let mut index = self.repo.index().unwrap();
+            index.remove_path(std::path::Path::new(from)).unwrap();
+            index.write().unwrap();
+            self.commit_file(
+                to,
+                &std::fs::read_to_string(self.path.join(to)).unwrap(),
+                message,
+            );
+        }
     }
 
     #[test]
@@ -1402,6 +2333,61 @@ mod tests {
         assert_eq!(unstaged.changes[0].path, "file2.txt");
     }
 
+    #[test]
+    fn test_find_file_by_path_or_unique_suffix() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("main.rs", "fn main() {}\n", "Initial commit");
+        std::fs::create_dir_all(test_repo.path.join("src/ui")).unwrap();
+        std::fs::write(test_repo.path.join("src/ui/mod.rs"), "mod a;\n").unwrap();
+        std::fs::write(test_repo.path.join("src/mod.rs"), "mod b;\n").unwrap();
+        let mut index = test_repo.repo.index().unwrap();
+        index
+            .add_path(std::path::Path::new("src/ui/mod.rs"))
+            .unwrap();
+        index.add_path(std::path::Path::new("src/mod.rs")).unwrap();
+        index.write().unwrap();
+        std::fs::write(test_repo.path.join("main.rs"), "fn main() { run() }\n").unwrap();
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let diff = repo.get_working_tree_diff(DiffMode::All).unwrap();
+        let find = |path: &str| diff.find_file(path).map(|i| diff.changes[i].path.as_str());
+        assert_eq!(find("./main.rs"), Some("main.rs"));
+        assert_eq!(find("ui/mod.rs"), Some("src/ui/mod.rs"));
+        // Ambiguous or missing paths find nothing
+        assert_eq!(find("mod.rs"), None);
+        assert_eq!(find("lib.rs"), None);
+    }
+
+    #[test]
+    fn test_working_tree_diff_all_combines_staged_and_unstaged() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("file1.txt", "one\n", "Initial commit");
+        test_repo.commit_file("file2.txt", "two\n", "Second commit");
+
+        // file1 staged and then modified again, file2 only unstaged, file3 new
+        std::fs::write(test_repo.path.join("file1.txt"), "one staged\n").unwrap();
+        let mut index = test_repo.repo.index().unwrap();
+        index.add_path(std::path::Path::new("file1.txt")).unwrap();
+        index.write().unwrap();
+        std::fs::write(test_repo.path.join("file1.txt"), "one staged\nthen more\n").unwrap();
+        std::fs::write(test_repo.path.join("file2.txt"), "two unstaged\n").unwrap();
+        std::fs::write(test_repo.path.join("file3.txt"), "three\n").unwrap();
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let all = repo.get_working_tree_diff(DiffMode::All).unwrap();
+        assert_eq!(all.message, "Staged and unstaged changes");
+
+        let paths: Vec<&str> = all.changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, ["file1.txt", "file2.txt", "file3.txt"]);
+        let file1 = &all.changes[0];
+        assert_eq!(file1.status, FileStatus::Modified);
+        assert_eq!(file1.old_content.as_deref(), Some("one\n"));
+        assert_eq!(
+            file1.new_content.as_deref(),
+            Some("one staged\nthen more\n")
+        );
+    }
+
     #[test]
     fn test_working_tree_diff_new_file() {
         let test_repo = TestRepo::new();
@@ -1486,4 +2472,580 @@ mod tests {
         let diff = now.signed_duration_since(result.date);
         assert!(diff.num_seconds() < 60);
     }
+
+    #[test]
+    fn test_range_chapters_and_jumping_to_a_position() {
+        let test_repo = TestRepo::new();
+        for i in 1..=4 {
+            test_repo.commit_file(
+                "a.txt",
+                &format!("v{}\n", i),
+                &format!("Commit {}\n\nbody", i),
+            );
+        }
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_commit_range("HEAD~3..HEAD").unwrap();
+
+        let chapters = repo.range_chapters();
+        let subjects: Vec<&str> = chapters.iter().map(|c| c.subject.as_str()).collect();
+        assert_eq!(subjects, vec!["Commit 2", "Commit 3", "Commit 4"]);
+        assert_eq!(repo.range_position(&chapters[1].hash), Some((1, 3)));
+        assert_eq!(repo.range_position("not-in-range"), None);
+
+        repo.set_range_position(2);
+        let commit = repo.next_range_commit_asc().unwrap();
+        assert_eq!(commit.hash, chapters[2].hash);
+
+        repo.set_range_position(0);
+        let commit = repo.next_range_commit_desc().unwrap();
+        assert_eq!(commit.hash, chapters[2].hash);
+    }
+
+    #[test]
+    fn test_commit_list_plays_in_listed_order_and_skips_unknown_hashes() {
+        let test_repo = TestRepo::new();
+        for i in 1..=3 {
+            test_repo.commit_file("a.txt", &format!("v{}\n", i), &format!("Commit {}", i));
+        }
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+
+        let list = ["HEAD", "deadbeef", "HEAD~2"].map(String::from);
+        let skipped = repo.set_commit_list(&list).unwrap();
+        assert_eq!(skipped, vec!["deadbeef".to_string()]);
+
+        let subjects: Vec<String> = std::iter::from_fn(|| repo.next_range_commit_asc().ok())
+            .map(|c| c.message)
+            .collect();
+        assert_eq!(subjects, vec!["Commit 3", "Commit 1"]);
+
+        assert!(repo.set_commit_list(&["nope".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_binary_changes_become_a_summary_line() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("logo.bin", "\0PNG\0\0", "Add logo");
+        test_repo.commit_file("logo.bin", "\0PNG\0\0\0\0\0\0", "Grow logo");
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let metadata = repo.get_commit("HEAD").unwrap();
+        let change = &metadata.changes[0];
+        assert!(change.is_binary);
+        assert_eq!(change.hunks.len(), 1);
+        let summary = &change.hunks[0].lines[0].content;
+        assert_eq!(summary, "Binary file changed (6 bytes → 10 bytes)");
+        assert!(is_binary_summary(summary));
+    }
+
+    #[test]
+    fn test_range_since_tag_starts_after_the_tag() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "v1\n", "Release");
+        let head = test_repo.repo.head().unwrap().peel_to_commit().unwrap();
+        test_repo
+            .repo
+            .tag_lightweight("v1.0.0", head.as_object(), false)
+            .unwrap();
+        test_repo.commit_file("a.txt", "v2\n", "After release");
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let range = repo.range_since_tag("v1.0.0").unwrap();
+        repo.set_commit_range(&range).unwrap();
+        let subjects: Vec<String> = repo
+            .range_chapters()
+            .into_iter()
+            .map(|c| c.subject)
+            .collect();
+        assert_eq!(subjects, vec!["After release"]);
+
+        let err = repo.range_since_tag("v9.9.9").unwrap_err();
+        assert!(err.to_string().contains("Tag not found: v9.9.9"));
+    }
+
+    #[test]
+    fn test_merge_filter_selects_merges_diffed_against_first_parent() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "base\n", "Base");
+        let base = test_repo.repo.head().unwrap().peel_to_commit().unwrap();
+        test_repo.commit_file("a.txt", "main\n", "Main change");
+        let main_tip = test_repo.repo.head().unwrap().peel_to_commit().unwrap();
+
+        // Side branch adds a file on top of the base commit
+        std::fs::write(test_repo.path.join("b.txt"), "side\n").unwrap();
+        let mut index = test_repo.repo.index().unwrap();
+        index.read_tree(&base.tree().unwrap()).unwrap();
+        index.add_path(std::path::Path::new("b.txt")).unwrap();
+        let side_tree = test_repo
+            .repo
+            .find_tree(index.write_tree().unwrap())
+            .unwrap();
+        let sig = test_repo.repo.signature().unwrap();
+        let side_oid = test_repo
+            .repo
+            .commit(None, &sig, &sig, "Side change", &side_tree, &[&base])
+            .unwrap();
+        let side = test_repo.repo.find_commit(side_oid).unwrap();
+
+        // Merge keeps main's a.txt and the side branch's b.txt
+        let mut index = test_repo.repo.index().unwrap();
+        index.read_tree(&main_tip.tree().unwrap()).unwrap();
+        index.add_path(std::path::Path::new("b.txt")).unwrap();
+        let merge_tree = test_repo
+            .repo
+            .find_tree(index.write_tree().unwrap())
+            .unwrap();
+        test_repo
+            .repo
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "Merge side",
+                &merge_tree,
+                &[&main_tip, &side],
+            )
+            .unwrap();
+
+        // By default the merge is skipped but both branches' commits are played
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let mut played: Vec<String> = std::iter::from_fn(|| repo.next_asc_commit().ok())
+            .map(|c| c.message)
+            .collect();
+        played.sort();
+        assert_eq!(played, vec!["Base", "Main change", "Side change"]);
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_merge_filter(MergeFilter::Only);
+        let merge = repo.next_asc_commit().unwrap();
+        assert_eq!(merge.message, "Merge side");
+        let paths: Vec<&str> = merge.changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["b.txt"]);
+        assert!(repo.next_asc_commit().is_err());
+    }
+
+    #[test]
+    fn test_max_commits_stops_playback_until_reset() {
+        let test_repo = TestRepo::new();
+        for i in 0..3 {
+            test_repo.commit_file("a.txt", &format!("v{}\n", i), &format!("Commit {}", i));
+        }
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_max_commits(Some(2));
+
+        assert_eq!(repo.next_asc_commit().unwrap().message, "Commit 0");
+        assert_eq!(repo.next_asc_commit().unwrap().message, "Commit 1");
+        assert!(repo.next_asc_commit().is_err());
+        assert!(repo.random_commit().is_err());
+
+        repo.reset_index();
+        assert_eq!(repo.next_asc_commit().unwrap().message, "Commit 0");
+    }
+
+    #[test]
+    fn test_skipped_commits_match_short_hashes() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "1\n", "Commit 0");
+        test_repo.commit_file("a.txt", "2\n", "Reformat everything");
+        let reformat = test_repo.repo.head().unwrap().target().unwrap().to_string();
+        test_repo.commit_file("a.txt", "3\n", "Commit 2");
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_skipped_commits(&[reformat[..7].to_string()])
+            .unwrap();
+        assert_eq!(repo.next_asc_commit().unwrap().message, "Commit 0");
+        assert_eq!(repo.next_asc_commit().unwrap().message, "Commit 2");
+        assert!(repo.next_asc_commit().is_err());
+
+        repo.set_commit_range("HEAD~2..HEAD").unwrap();
+        assert_eq!(repo.next_range_commit_asc().unwrap().message, "Commit 2");
+        assert!(repo.next_range_commit_asc().is_err());
+
+        assert!(repo.set_skipped_commits(&["deadbeef".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_file_history_follows_renames() {
+        let test_repo = TestRepo::new();
+        let body = "fn main() {\n    let a = 1;\n    let b = 2;\n    println!(\"{}\", a + b);\n}\n";
+        test_repo.commit_file("old.rs", body, "Create old");
+        test_repo.commit_file("other.rs", "x\n", "Unrelated");
+        test_repo.commit_file("old.rs", &format!("{}// more\n", body), "Extend old");
+        test_repo.rename_file("old.rs", "new.rs", "Rename to new");
+        test_repo.commit_file("new.rs", body, "Edit new");
+        test_repo.commit_file("old.rs", "unrelated\n", "Reuse the old name");
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_file_history("new.rs").unwrap();
+        let mut played = Vec::new();
+        while let Ok(metadata) = repo.next_range_commit_asc() {
+            let paths: Vec<_> = metadata.changes.iter().map(|c| c.path.clone()).collect();
+            played.push((metadata.message, paths));
+        }
+        let expect = |message: &str, paths: &[&str]| {
+            (
+                message.to_string(),
+                paths.iter().map(|p| p.to_string()).collect::<Vec<_>>(),
+            )
+        };
+        assert_eq!(
+            played,
+            vec![
+                expect("Create old", &["old.rs"]),
+                expect("Extend old", &["old.rs"]),
+                expect("Rename to new", &["new.rs"]),
+                expect("Edit new", &["new.rs"]),
+            ]
+        );
+        assert!(repo.set_file_history("missing.rs").is_err());
+    }
+
+    #[test]
+    fn test_word_diff_marks_only_changed_word() {
+        let (old, new) = word_diff("let count = 1;\n", "let total = 1;\n");
+        assert_eq!(old, vec![(4, 9)]);
+        assert_eq!(new, vec![(4, 9)]);
+    }
+
+    #[test]
+    fn test_word_diff_aligns_on_graphemes() {
+        // "é" written as e + combining accent is two chars but one grapheme
+        let (_, new) = word_diff("name = \"cafe\"", "name = \"cafe\u{301}\"   ");
+        assert_eq!(new, vec![(8, 13), (14, 17)]);
+    }
+
+    #[test]
+    fn test_word_diff_unrelated_lines_have_no_ranges() {
+        let (old, new) = word_diff("foo()", "bar");
+        assert!(old.is_empty());
+        assert!(new.is_empty());
+    }
+
+    #[test]
+    fn test_message_filter_combines_with_author_filter() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "1\n", "Fix login bug (PROJ-12)");
+        test_repo.commit_file("a.txt", "2\n", "Refactor parser");
+        test_repo.commit_file("a.txt", "3\n", "proj-12: follow-up");
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_message_filter(Some(MessageFilter::new("PROJ-12", false).unwrap()));
+        assert!(repo
+            .next_asc_commit()
+            .unwrap()
+            .message
+            .starts_with("Fix login"));
+        assert!(repo
+            .next_asc_commit()
+            .unwrap()
+            .message
+            .starts_with("proj-12"));
+        assert!(repo.next_asc_commit().is_err());
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_message_filter(Some(MessageFilter::new("^Fix", true).unwrap()));
+        repo.set_author_filter(Some("nobody".to_string()));
+        assert!(repo.next_asc_commit().is_err());
+    }
+
+    #[test]
+    fn test_path_filter_skips_and_narrows_commits() {
+        let test_repo = TestRepo::new();
+        std::fs::create_dir_all(test_repo.path.join("api")).unwrap();
+        test_repo.commit_file("api/main.rs", "1\n", "Touch api");
+        test_repo.commit_file("web.txt", "1\n", "Touch web only");
+        std::fs::write(test_repo.path.join("web.txt"), "2\n").unwrap();
+        let mut index = test_repo.repo.index().unwrap();
+        index.add_path(std::path::Path::new("web.txt")).unwrap();
+        index.write().unwrap();
+        test_repo.commit_file("api/main.rs", "2\n", "Touch both");
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        repo.set_path_filter(&["api/**".to_string()]).unwrap();
+
+        let first = repo.next_asc_commit().unwrap();
+        assert_eq!(first.message, "Touch api");
+        let second = repo.next_asc_commit().unwrap();
+        assert_eq!(second.message, "Touch both");
+        assert_eq!(second.changes.len(), 1);
+        assert_eq!(second.changes[0].path, "api/main.rs");
+        assert!(repo.next_asc_commit().is_err());
+    }
+
+    #[test]
+    fn test_change_filter_skips_commits_outside_the_size_range() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "1\n", "One line");
+        test_repo.commit_file("a.txt", "1\n2\n3\n4\n", "Three lines");
+        test_repo.commit_file("Cargo.lock", "1\n2\n3\n4\n5\n6\n", "Lock file only");
+        test_repo.commit_file("a.txt", &"x\n".repeat(20), "Rewrite");
+
+        let mut repo = GitRepository::open(&test_repo.path).unwrap();
+        assert!(repo.set_change_filter(Some(5), Some(2)).is_err());
+        repo.set_change_filter(Some(2), Some(10)).unwrap();
+
+        assert_eq!(repo.next_asc_commit().unwrap().message, "Three lines");
+        assert!(repo.next_asc_commit().is_err());
+    }
+
+    #[test]
+    fn test_diff_refs_shows_net_change_since_merge_base() {
+        let test_repo = TestRepo::new();
+        test_repo.commit_file("a.txt", "base\n", "Base");
+        let base = test_repo.repo.head().unwrap().peel_to_commit().unwrap();
+        test_repo.repo.branch("main", &base, false).unwrap();
+
+        test_repo.commit_file("a.txt", "feature 1\n", "Feature 1");
+        test_repo.commit_file("a.txt", "feature 2\n", "Feature 2");
+
+        let repo = GitRepository::open(&test_repo.path).unwrap();
+        let metadata = repo.diff_refs("main...HEAD").unwrap();
+
+        assert!(metadata.message.starts_with("Compare main...HEAD"));
+        assert!(metadata.message.contains("2 commits since merge base"));
+        assert_eq!(metadata.changes.len(), 1);
+        let change = &metadata.changes[0];
+        assert_eq!(change.old_content.as_deref(), Some("base\n"));
+        assert_eq!(change.new_content.as_deref(), Some("feature 2\n"));
+        assert_ne!(metadata.hash, repo.get_commit("HEAD").unwrap().hash);
+
+        assert!(repo.diff_refs("main..HEAD").is_err());
+    }
+
+    /// Kind and text of each line in a parsed hunk, line endings trimmed
+    fn hunk_lines(hunk: &DiffHunk) -> Vec<(char, &str)> {
+        hunk.lines
+            .iter()
+            .map(|line| {
+                let sign = match line.change_type {
+                    LineChangeType::Addition => '+',
+                    LineChangeType::Deletion => '-',
+                    LineChangeType::Context => ' ',
+                };
+                (sign, line.content.trim_end_matches(['\n', '\r']))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_unified_diff_modified_file() {
+        let changes = parse_unified_diff(
+            "diff --git a/src/lib.rs b/src/lib.rs\n\
+             index 1111111..2222222 100644\n\
+             --- a/src/lib.rs\n\
+             +++ b/src/lib.rs\n\
+             @@ -1,3 +1,3 @@\n \
+             fn main() {\n\
+             -    old();\n\
+             +    new();\n \
+             }\n",
+        )
+        .unwrap();
+        assert_eq!(changes.len(), 1);
+        let change = &changes[0];
+        assert_eq!(change.path, "src/lib.rs");
+        assert_eq!(change.status, FileStatus::Modified);
+        assert!(!change.is_binary && !change.is_excluded);
+        assert_eq!(change.line_counts(), (1, 1));
+
+        let hunk = &change.hunks[0];
+        assert_eq!((hunk.old_start, hunk.new_start), (1, 1));
+        assert_eq!(
+            hunk_lines(hunk),
+            vec![
+                (' ', "fn main() {"),
+                ('-', "    old();"),
+                ('+', "    new();"),
+                (' ', "}"),
+            ]
+        );
+        let numbers: Vec<_> = hunk
+            .lines
+            .iter()
+            .map(|l| (l.old_line_no, l.new_line_no))
+            .collect();
+        assert_eq!(
+            numbers,
+            vec![
+                (Some(1), Some(1)),
+                (Some(2), None),
+                (None, Some(2)),
+                (Some(3), Some(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_unified_diff_renames() {
+        let changes = parse_unified_diff(
+            "diff --git a/old.txt b/new.txt\n\
+             similarity index 80%\n\
+             rename from old.txt\n\
+             rename to new.txt\n\
+             index 1111111..2222222 100644\n\
+             --- a/old.txt\n\
+             +++ b/new.txt\n\
+             @@ -1,2 +1,2 @@\n \
+             a\n\
+             -b\n\
+             +c\n\
+             diff --git a/moved.txt b/renamed.txt\n\
+             similarity index 100%\n\
+             rename from moved.txt\n\
+             rename to renamed.txt\n",
+        )
+        .unwrap();
+        assert_eq!(changes.len(), 2);
+
+        assert_eq!(changes[0].path, "new.txt");
+        assert_eq!(changes[0].old_path.as_deref(), Some("old.txt"));
+        assert_eq!(changes[0].status, FileStatus::Renamed);
+        assert_eq!(
+            hunk_lines(&changes[0].hunks[0]),
+            vec![(' ', "a"), ('-', "b"), ('+', "c")]
+        );
+
+        // A pure rename has no hunks to animate
+        assert_eq!(changes[1].path, "renamed.txt");
+        assert_eq!(changes[1].old_path.as_deref(), Some("moved.txt"));
+        assert_eq!(changes[1].status, FileStatus::Renamed);
+        assert!(changes[1].hunks.is_empty());
+    }
+
+    #[test]
+    fn test_parse_unified_diff_new_and_deleted_files() {
+        let changes = parse_unified_diff(
+            "diff --git a/gone.txt b/gone.txt\n\
+             deleted file mode 100644\n\
+             index 1111111..0000000\n\
+             --- a/gone.txt\n\
+             +++ /dev/null\n\
+             @@ -1,2 +0,0 @@\n\
+             -one\n\
+             -two\n\
+             diff --git a/new.txt b/new.txt\n\
+             new file mode 100644\n\
+             index 0000000..2222222\n\
+             --- /dev/null\n\
+             +++ b/new.txt\n\
+             @@ -0,0 +1,2 @@\n\
+             +one\n\
+             +two\n",
+        )
+        .unwrap();
+        assert_eq!(changes.len(), 2);
+
+        let deleted = &changes[0];
+        assert_eq!(deleted.path, "gone.txt");
+        assert_eq!(deleted.status, FileStatus::Deleted);
+        assert_eq!(deleted.line_counts(), (0, 2));
+        assert_eq!(deleted.hunks[0].new_start, 0);
+
+        let added = &changes[1];
+        assert_eq!(added.path, "new.txt");
+        assert_eq!(added.status, FileStatus::Added);
+        assert_eq!(added.line_counts(), (2, 0));
+        assert_eq!(added.hunks[0].lines[1].new_line_no, Some(2));
+    }
+
+    #[test]
+    fn test_parse_unified_diff_keeps_crlf_lines_apart() {
+        let changes = parse_unified_diff(
+            "diff --git a/win.txt b/win.txt\n\
+             index 1111111..2222222 100644\n\
+             --- a/win.txt\n\
+             +++ b/win.txt\n\
+             @@ -1,2 +1,2 @@\n \
+             keep\r\n\
+             -old\r\n\
+             +new\r\n",
+        )
+        .unwrap();
+        let hunk = &changes[0].hunks[0];
+        assert_eq!(
+            hunk_lines(hunk),
+            vec![(' ', "keep"), ('-', "old"), ('+', "new")]
+        );
+        assert_eq!(hunk.lines[2].content, "new\r\n");
+    }
+
+    #[test]
+    fn test_parse_unified_diff_skips_no_newline_markers() {
+        let changes = parse_unified_diff(
+            "diff --git a/eof.txt b/eof.txt\n\
+             index 1111111..2222222 100644\n\
+             --- a/eof.txt\n\
+             +++ b/eof.txt\n\
+             @@ -1,2 +1,2 @@\n \
+             keep\n\
+             -old\n\
+             \\ No newline at end of file\n\
+             +new\n\
+             \\ No newline at end of file\n",
+        )
+        .unwrap();
+        let hunk = &changes[0].hunks[0];
+        assert_eq!(
+            hunk_lines(hunk),
+            vec![(' ', "keep"), ('-', "old"), ('+', "new")]
+        );
+        assert_eq!(hunk.lines[2].new_line_no, Some(2));
+    }
+
+    #[test]
+    fn test_parse_unified_diff_binary_file() {
+        let changes = parse_unified_diff(
+            "diff --git a/img.png b/img.png\n\
+             index 1111111..2222222 100644\n\
+             Binary files a/img.png and b/img.png differ\n",
+        )
+        .unwrap();
+        assert!(changes[0].is_binary);
+        assert_eq!(changes[0].hunks.len(), 1);
+        assert!(is_binary_summary(&changes[0].hunks[0].lines[0].content));
+    }
+
+    #[test]
+    fn test_content_delta_diffs_two_snapshots() {
+        assert!(content_delta("a.txt", "same\n", "same\n")
+            .unwrap()
+            .is_none());
+
+        let change = content_delta("a.txt", "one\ntwo\n", "one\n2\n")
+            .unwrap()
+            .unwrap();
+        assert_eq!(change.path, "a.txt");
+        assert_eq!(change.old_content.as_deref(), Some("one\ntwo\n"));
+        assert_eq!(
+            hunk_lines(&change.hunks[0]),
+            vec![(' ', "one"), ('-', "two"), ('+', "2")]
+        );
+    }
+
+    #[test]
+    fn test_parse_unified_diff_metadata_finds_files() {
+        let metadata = CommitMetadata {
+            hash: "fixture".to_string(),
+            author: "Alice".to_string(),
+            date: Utc::now(),
+            message: "Fixture".to_string(),
+            changes: parse_unified_diff(
+                "diff --git a/src/a.rs b/src/a.rs\n\
+                 new file mode 100644\n\
+                 --- /dev/null\n\
+                 +++ b/src/a.rs\n\
+                 @@ -0,0 +1 @@\n\
+                 +a\n\
+                 diff --git a/Cargo.lock b/Cargo.lock\n\
+                 --- a/Cargo.lock\n\
+                 +++ b/Cargo.lock\n\
+                 @@ -1 +1 @@\n\
+                 -a\n\
+                 +b\n",
+            )
+            .unwrap(),
+        };
+        assert_eq!(metadata.find_file("a.rs"), Some(0));
+        assert!(metadata.changes[1].is_excluded);
+    }
 }
@@ -0,0 +1,65 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::cli::Args;
+
+// Set while the TUI owns the terminal, so log lines don't draw over it
+static HOLDING: AtomicBool = AtomicBool::new(false);
+static HELD: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+/// Diagnostics go through `log` and are off by default: everything from torvax
+/// with `--verbose`, nothing with `--quiet`, otherwise whatever `RUST_LOG` asks for.
+pub fn init(args: &Args) {
+    let mut builder = env_logger::Builder::new();
+    if args.quiet {
+        builder.filter_level(log::LevelFilter::Off);
+    } else if args.verbose {
+        // Debug output from our own modules only; dependencies stay at warnings
+        builder
+            .filter_level(log::LevelFilter::Warn)
+            .filter_module("torvax", log::LevelFilter::Debug);
+    } else {
+        builder
+            .filter_level(log::LevelFilter::Off)
+            .parse_default_env();
+    }
+    builder.target(env_logger::Target::Pipe(Box::new(HeldStderr)));
+    builder.init();
+}
+
+/// Buffer log lines until `release`, while the TUI is on screen.
+pub fn hold() {
+    HOLDING.store(true, Ordering::SeqCst);
+}
+
+/// Write the log lines buffered since `hold` to stderr and stop buffering.
+pub fn release() {
+    if let Ok(mut held) = HELD.lock() {
+        HOLDING.store(false, Ordering::SeqCst);
+        let _ = io::stderr().write_all(&held);
+        held.clear();
+    }
+}
+
+/// Stderr, or the hold buffer while the TUI is up.
+struct HeldStderr;
+
+impl Write for HeldStderr {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if HOLDING.load(Ordering::SeqCst) {
+            if let Ok(mut held) = HELD.lock() {
+                // Checked again under the lock in case `release` just drained it
+                if HOLDING.load(Ordering::SeqCst) {
+                    held.extend_from_slice(buf);
+                    return Ok(buf.len());
+                }
+            }
+        }
+        io::stderr().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stderr().flush()
+    }
+}
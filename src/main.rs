@@ -2,24 +2,39 @@ mod animation;
 mod audio;
 mod cli;
 mod config;
+mod export;
 mod git;
+mod logging;
 mod panes;
+mod pr;
+mod resume;
 mod setup;
 mod syntax;
 mod theme;
 mod ui;
+mod watch;
 mod widgets;
 
-use anyhow::Result;
-use clap::Parser;
-use cli::{Args, Commands, PlaybackOrder, ThemeCommands};
-use config::Config;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+use cli::{Args, ColorDepth, Commands, ConfigCommands, PlaybackOrder, ThemeCommands, TypeBy};
+use config::{Config, ConfigKeys, EditPauses, ResolvedSetting, SettingSource};
 use git::{DiffMode, GitRepository};
 use theme::Theme;
 use ui::UI;
 
+// How long `theme preview` shows the sample diff unless a key is pressed
+const THEME_PREVIEW_SECS: u64 = 3;
+
 fn main() -> Result<()> {
     let args = Args::parse();
+    logging::init(&args);
+
+    if let Some(ref dir) = args.config_dir {
+        config::set_config_dir(dir.clone());
+    }
 
     if args.license {
         println!("{}", include_str!("../LICENSE-THIRD-PARTY"));
@@ -50,47 +65,126 @@ fn handle_subcommand(command: &Commands, args: &Args) -> Result<()> {
                 let path = Config::config_path()?;
                 println!("Theme set to '{}' in {}", name, path.display());
             }
+            ThemeCommands::Export { name, path } => {
+                let theme = Theme::load(name)?;
+                let contents = format!(
+                    "# Exported from the '{}' theme; load with --theme-file\n{}",
+                    name,
+                    theme.to_toml()
+                );
+                std::fs::write(path, contents)
+                    .with_context(|| format!("Failed to write theme file: {}", path.display()))?;
+                println!("Exported theme '{}' to {}", name, path.display());
+            }
+            ThemeCommands::Preview { name } => {
+                let config = Config::load().unwrap_or_default();
+                let mut theme = Theme::load(name)?;
+                if !config.background {
+                    theme = theme.with_transparent_background();
+                }
+                let mut ui = UI::new(
+                    config.speed,
+                    None,
+                    theme,
+                    PlaybackOrder::Asc,
+                    false,
+                    None,
+                    false,
+                    Vec::new(),
+                    None,
+                );
+                ui.set_line_numbers(config.line_numbers);
+                ui.set_wrap(config.wrap);
+                ui.preview(Duration::from_secs(THEME_PREVIEW_SECS))?;
+            }
+        },
+        Commands::Config { command } => match command {
+            ConfigCommands::Show => {
+                let config = Config::load()?;
+                print!("{}", config.to_display_string()?);
+            }
+            ConfigCommands::Path => {
+                println!("{}", Config::config_path()?.display());
+            }
+            ConfigCommands::Set { key, value } => {
+                let mut config = Config::load().unwrap_or_default();
+                config.set(key, value)?;
+                config.save()?;
+                let path = Config::config_path()?;
+                println!("Set {} = {} in {}", key, value, path.display());
+            }
         },
         Commands::Diff {
             unstaged,
+            all,
             speed,
             theme,
             background,
             loop_playback,
             ignore,
             speed_rule,
+            record,
+            word_diff,
+            subtitles,
+            watch,
         } => {
             let repo_path = args.validate()?;
             let repo = GitRepository::open(&repo_path)?;
-            let mode = if *unstaged {
+
+            // Patterns must be in place before the diff marks excluded files
+            let config = Config::load()?;
+            let mut patterns = config.ignore_patterns.clone();
+            patterns.extend(ignore.clone());
+            if !args.no_auto_ignore {
+                patterns.extend(repo.gitignore_patterns());
+            }
+            git::init_ignore_patterns(&patterns).ok();
+
+            let mode = if *all {
+                DiffMode::All
+            } else if *unstaged || *watch {
                 DiffMode::Unstaged
             } else {
                 DiffMode::Staged
             };
             let metadata = repo.get_working_tree_diff(mode)?;
 
-            if metadata.changes.is_empty() {
+            if metadata.changes.is_empty() && !*watch {
                 println!("No changes to display");
                 return Ok(());
             }
 
-            let config = Config::load()?;
-            let mut patterns = config.ignore_patterns.clone();
-            patterns.extend(ignore.clone());
-            git::init_ignore_patterns(&patterns).ok();
-
             let speed_rules = build_speed_rules(speed_rule, &config.speed_rules);
+            let keys = ConfigKeys::load();
+            let mut settings =
+                display_settings(&keys, args, &config, theme.as_deref(), *speed, *background);
             let theme_name = theme.as_deref().unwrap_or(&config.theme);
             let speed = speed.unwrap_or(config.speed);
             let background = background.unwrap_or(config.background);
-            let loop_playback = loop_playback.unwrap_or(false);
-            let mut theme = Theme::load(theme_name)?;
+            let loop_playback = !args.present && loop_playback.unwrap_or(false);
+            let mut theme = load_theme(theme_name, args.theme_file.as_deref())?;
             if !background {
                 theme = theme.with_transparent_background();
             }
+            let (left, right) = args.transparent_sides();
+            theme = theme.with_transparent_sides(left, right);
+            if args.monochrome() {
+                theme = theme.monochrome();
+            }
+            theme = fit_color_depth(theme, args.color_depth());
 
             let audio_player = setup::create_audio_player(&config, args)?;
-            let repo_ref = if loop_playback { Some(&repo) } else { None };
+            settings.extend(setup::voiceover_settings(
+                &config,
+                args,
+                &keys,
+                audio_player.as_deref(),
+            ));
+            let repo_ref = if loop_playback || *watch {
+                Some(&repo)
+            } else {
+                None
+            };
             let mut ui = UI::new(
                 speed,
                 repo_ref,
@@ -103,34 +197,102 @@ fn handle_subcommand(command: &Commands, args: &Args) -> Result<()> {
                 audio_player,
             );
             ui.set_diff_mode(Some(mode));
-            ui.load_commit(metadata);
+            ui.set_record_path(record.clone().or_else(|| args.record.clone()));
+            ui.set_gif(args.gif.clone(), args.gif_options());
+            ui.set_events_path(args.events.clone());
+            ui.set_word_diff(*word_diff || args.word_diff);
+            ui.set_type_by_token(args.type_by == TypeBy::Token);
+            ui.set_show_deletions(args.show_deletions);
+            ui.set_only_additions(args.only_additions);
+            ui.set_collapse_threshold(args.collapse_threshold());
+            ui.set_stat_mode(args.stat_mode);
+            ui.set_file_order(args.file_order);
+            ui.set_minimap(args.minimap);
+            ui.set_captions(args.captions);
+            ui.set_voiceover_wait(!args.no_voiceover_wait);
+            ui.set_show_stats(args.stats);
+            ui.set_hints(!args.no_hints);
+            ui.set_max_fps(args.fps);
+            ui.set_line_numbers(config.line_numbers && !args.no_line_numbers);
+            ui.set_wrap(config.wrap && !args.no_wrap);
+            ui.set_cursor_style(args.cursor_style);
+            ui.set_cursor_blink(!args.no_cursor_blink);
+            ui.set_commit_gap(args.commit_gap.or(config.commit_gap));
+            ui.set_file_gap(args.file_gap.or(config.file_gap));
+            ui.set_line_pause_scale(args.line_pause_scale());
+            ui.set_pauses(config.pauses);
+            ui.set_keystroke_sound(args.plays_keystroke_sound());
+            ui.set_start_paused(args.paused);
+            ui.set_auto_resume(args.auto_resume.map(Duration::from_secs));
+            ui.set_subtitles_path(subtitles.clone().or_else(|| args.subtitles.clone()));
+            ui.set_audio_out_path(args.audio_out.clone());
+            ui.set_settings(settings);
+            if *watch {
+                let mut working_tree = watch::WorkingTreeWatch::new(&repo_path)?;
+                working_tree.mark_rendered(&metadata);
+                ui.set_watch(Some(working_tree));
+            }
+            if !metadata.changes.is_empty() {
+                ui.load_commit(metadata);
+            }
             ui.run()?;
         }
     }
     Ok(())
 }
 
-fn run_playback(args: Args) -> Result<()> {
+fn run_playback(mut args: Args) -> Result<()> {
     let repo_path = args.validate()?;
     let mut repo = GitRepository::open(&repo_path)?;
 
+    if let Some(ref tag) = args.since_tag {
+        args.commit = Some(repo.range_since_tag(tag)?);
+    }
+
+    if let Some(ref url) = args.pr {
+        args.commit = Some(pr::commit_range(&repo, url)?);
+    }
+
     if args.author.is_some() {
         repo.set_author_filter(args.author.clone());
     }
 
+    if let Some(ref pattern) = args.grep {
+        repo.set_message_filter(Some(git::MessageFilter::new(pattern, args.grep_regex)?));
+    }
+
+    repo.set_path_filter(&args.path_filter)?;
+    repo.set_max_commits(args.max_commits);
+    repo.set_change_filter(args.min_changes, args.max_changes)?;
+    // --no-merges spells out the default; clap rejects it alongside --merges-only
+    repo.set_merge_filter(match (args.no_merges, args.merges_only) {
+        (false, true) => git::MergeFilter::Only,
+        _ => git::MergeFilter::Exclude,
+    });
+
     if let Some(ref s) = args.before {
         repo.set_before_filter(Some(git::parse_date(s)?));
     }
     if let Some(ref s) = args.after {
         repo.set_after_filter(Some(git::parse_date(s)?));
     }
+    repo.set_skipped_commits(&args.skip_commit)?;
 
-    let is_range = args
-        .commit
-        .as_ref()
-        .map(|c| c.contains(".."))
-        .unwrap_or(false);
-    let is_filtered = args.author.is_some() || args.before.is_some() || args.after.is_some();
+    let is_range = args.commits_from.is_some()
+        || args.file.is_some()
+        || args
+            .commit
+            .as_ref()
+            .map(|c| c.contains(".."))
+            .unwrap_or(false);
+    let is_filtered = args.author.is_some()
+        || args.merges_only
+        || args.grep.is_some()
+        || !args.path_filter.is_empty()
+        || args.before.is_some()
+        || args.after.is_some()
+        || args.min_changes.is_some()
+        || args.max_changes.is_some();
     let config = Config::load()?;
 
     let mut patterns = config.ignore_patterns.clone();
@@ -145,12 +307,16 @@ fn run_playback(args: Args) -> Result<()> {
         }
     }
     patterns.extend(args.ignore.clone());
+    if !args.no_auto_ignore {
+        patterns.extend(repo.gitignore_patterns());
+    }
     git::init_ignore_patterns(&patterns).ok();
 
     let theme_name = args.theme.as_deref().unwrap_or(&config.theme);
     let speed = args.speed.unwrap_or(config.speed);
     let background = args.background.unwrap_or(config.background);
-    let loop_playback = args.loop_playback.unwrap_or(config.loop_playback);
+    // Presentations play once
+    let loop_playback = !args.present && args.loop_playback.unwrap_or(config.loop_playback);
     let mut order = args.order.unwrap_or(match config.order.as_str() {
         "asc" => PlaybackOrder::Asc,
         "desc" => PlaybackOrder::Desc,
@@ -160,16 +326,59 @@ fn run_playback(args: Args) -> Result<()> {
         order = PlaybackOrder::Asc;
     }
 
-    let mut theme = Theme::load(theme_name)?;
+    let mut theme = load_theme(theme_name, args.theme_file.as_deref())?;
     if !background {
         theme = theme.with_transparent_background();
     }
+    let (left, right) = args.transparent_sides();
+    theme = theme.with_transparent_sides(left, right);
+    if args.monochrome() {
+        theme = theme.monochrome();
+    }
+    theme = fit_color_depth(theme, args.color_depth());
 
-    if is_range {
+    if let Some(ref path) = args.commits_from {
+        for hash in repo.set_commit_list(&read_commit_list(path)?)? {
+            log::warn!("'{}' is not a commit in this repository, skipping", hash);
+        }
+    } else if let Some(ref file) = args.file {
+        repo.set_file_history(file)?;
+    } else if is_range {
         repo.set_commit_range(args.commit.as_ref().unwrap())?;
     }
 
-    let metadata = if is_range {
+    if args.dry_run {
+        return print_plan(&repo, &args, order, is_range);
+    }
+
+    if args.print_order {
+        return print_file_order(&repo, &args, &config, is_range);
+    }
+
+    if let Some(ref path) = args.report {
+        return write_report(&repo, &args, &config, speed, is_range, path);
+    }
+
+    let resume_path = resume::state_path(&repo_path);
+    // A missing or stale state file (e.g. the commit no longer exists) starts fresh
+    let resumed = if args.resume {
+        resume_path
+            .as_deref()
+            .and_then(resume::load)
+            .and_then(|state| {
+                let metadata = repo.get_commit(&state.commit).ok()?;
+                Some((metadata, state.progress))
+            })
+    } else {
+        None
+    };
+    let resume_progress = resumed.as_ref().map(|(_, progress)| *progress);
+
+    let metadata = if let Some((metadata, _)) = resumed {
+        metadata
+    } else if let Some(ref spec) = args.compare {
+        repo.diff_refs(spec)?
+    } else if is_range {
         match order {
             PlaybackOrder::Random => repo.random_range_commit()?,
             PlaybackOrder::Asc => repo.next_range_commit_asc()?,
@@ -185,10 +394,48 @@ fn run_playback(args: Args) -> Result<()> {
         }
     };
 
+    let speed = match args.duration {
+        Some(secs) => {
+            let speed = speed_for_duration(&metadata, secs, &args, &config.pauses);
+            log::info!("Using typing speed {}ms to fit {}s", speed, secs);
+            speed
+        }
+        None => speed,
+    };
+
     let speed_rules = build_speed_rules(&args.speed_rule, &config.speed_rules);
     let audio_player = setup::create_audio_player(&config, &args)?;
+    let keys = ConfigKeys::load();
+    let mut settings = display_settings(
+        &keys,
+        &args,
+        &config,
+        args.theme.as_deref(),
+        args.speed.or(args.duration.map(|_| speed)),
+        args.background,
+    );
+    let order_name = order
+        .to_possible_value()
+        .map_or(String::new(), |v| v.get_name().to_string());
+    let mut order_setting = keys.setting("order", args.order.map(|_| &order_name), &order_name);
+    if (is_range || is_filtered) && args.order.is_none() {
+        // Ranges and filters play oldest first unless told otherwise
+        order_setting.source = SettingSource::Default;
+    }
+    settings.push(order_setting);
+    settings.push(keys.setting("loop", args.loop_playback, loop_playback));
+    settings.extend(setup::voiceover_settings(
+        &config,
+        &args,
+        &keys,
+        audio_player.as_deref(),
+    ));
     let is_commit_specified = args.commit.is_some();
-    let repo_ref = if is_range || is_filtered || !is_commit_specified || loop_playback {
+    let repo_ref = if args.compare.is_some() {
+        // A comparison is a single walkthrough; the repo is only needed to replay it
+        loop_playback.then_some(&repo)
+    } else if is_range || is_filtered || !is_commit_specified || loop_playback {
+        // A single commit only needs the repo to replay itself when looping
         Some(&repo)
     } else {
         None
@@ -205,18 +452,321 @@ fn run_playback(args: Args) -> Result<()> {
         speed_rules,
         audio_player,
     );
+    ui.set_record_path(args.record.clone());
+    ui.set_gif(args.gif.clone(), args.gif_options());
+    ui.set_events_path(args.events.clone());
+    ui.set_word_diff(args.word_diff);
+    ui.set_type_by_token(args.type_by == TypeBy::Token);
+    ui.set_show_deletions(args.show_deletions);
+    ui.set_only_additions(args.only_additions);
+    ui.set_collapse_threshold(args.collapse_threshold());
+    ui.set_stat_mode(args.stat_mode);
+    ui.set_file_order(args.file_order);
+    ui.set_minimap(args.minimap);
+    ui.set_captions(args.captions);
+    ui.set_voiceover_wait(!args.no_voiceover_wait);
+    ui.set_show_stats(args.stats);
+    ui.set_hints(!args.no_hints);
+    ui.set_max_fps(args.fps);
+    ui.set_line_numbers(config.line_numbers && !args.no_line_numbers);
+    ui.set_wrap(config.wrap && !args.no_wrap);
+    ui.set_cursor_style(args.cursor_style);
+    ui.set_cursor_blink(!args.no_cursor_blink);
+    ui.set_commit_gap(args.commit_gap.or(config.commit_gap));
+    ui.set_file_gap(args.file_gap.or(config.file_gap));
+    ui.set_line_pause_scale(args.line_pause_scale());
+    ui.set_pauses(config.pauses);
+    ui.set_keystroke_sound(args.plays_keystroke_sound());
+    ui.set_start_paused(args.paused);
+    ui.set_auto_resume(args.auto_resume.map(Duration::from_secs));
+    ui.set_subtitles_path(args.subtitles.clone());
+    ui.set_audio_out_path(args.audio_out.clone());
+    ui.set_compare_spec(args.compare.clone());
+    ui.set_filtered(is_filtered);
+    ui.set_settings(settings);
+    if args.compare.is_none() {
+        ui.set_resume_path(resume_path);
+    }
     ui.load_commit(metadata);
+    if let Some(progress) = resume_progress {
+        ui.seek_on_start(progress);
+    }
+    ui.start_at_file(args.start_file.clone());
     ui.run()?;
     Ok(())
 }
 
+/// Write LLM explanations for the selected commits as Markdown, without starting the UI.
+/// A range reports every commit in it (oldest first); otherwise a single commit
+/// (`--compare`, `--commit`, or HEAD).
+fn write_report(
+    repo: &GitRepository,
+    args: &Args,
+    config: &Config,
+    speed: u64,
+    is_range: bool,
+    path: &std::path::Path,
+) -> Result<()> {
+    let vc = setup::llm_config(config, args)?;
+    let timing = audio::llm::AnimationTiming {
+        speed_ms: speed,
+        show_deletions: args.show_deletions,
+        only_additions: args.only_additions,
+        collapse_threshold: None,
+        line_pause_scale: args.line_pause_scale(),
+        pauses: config.pauses,
+    };
+
+    let commits = if is_range {
+        std::iter::from_fn(|| repo.next_range_commit_asc().ok()).collect()
+    } else if let Some(ref spec) = args.compare {
+        vec![repo.diff_refs(spec)?]
+    } else {
+        vec![repo.get_commit(args.commit.as_deref().unwrap_or("HEAD"))?]
+    };
+    if commits.is_empty() {
+        anyhow::bail!("No commits to report");
+    }
+
+    let mut writer = export::ReportWriter::create(path)?;
+    for (i, metadata) in commits.iter().enumerate() {
+        // Progress rather than a diagnostic, so shown unless --quiet
+        if !args.quiet {
+            eprintln!(
+                "Explaining commit {}/{}: {}",
+                i + 1,
+                commits.len(),
+                &metadata.hash[..7.min(metadata.hash.len())]
+            );
+        }
+        let file_changes = animation::file_play_order(metadata, args.file_order)
+            .into_iter()
+            .map(|i| &metadata.changes[i])
+            .filter(|c| !c.is_excluded)
+            .map(|c| (c.path.clone(), c.narration_diff(), c.status.clone()))
+            .collect();
+        let chunks = audio::explain_commit(
+            &vc,
+            &audio::CommitContext::new(metadata),
+            &metadata.hash,
+            file_changes,
+            args.file_order,
+            timing,
+        )?;
+        writer.write_commit(metadata, &chunks)?;
+    }
+
+    println!("Wrote report to {}", path.display());
+    Ok(())
+}
+
+/// Print each selected commit's files in the order the narrator would walk through
+/// them, without starting the UI. Files the narrator skips are listed last.
+fn print_file_order(
+    repo: &GitRepository,
+    args: &Args,
+    config: &Config,
+    is_range: bool,
+) -> Result<()> {
+    let vc = setup::llm_config(config, args)?;
+
+    let commits = if is_range {
+        std::iter::from_fn(|| repo.next_range_commit_asc().ok()).collect()
+    } else if let Some(ref spec) = args.compare {
+        vec![repo.diff_refs(spec)?]
+    } else {
+        vec![repo.get_commit(args.commit.as_deref().unwrap_or("HEAD"))?]
+    };
+    if commits.is_empty() {
+        anyhow::bail!("No commits to order");
+    }
+
+    for (i, metadata) in commits.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!(
+            "{}  {}",
+            &metadata.hash[..7.min(metadata.hash.len())],
+            metadata.message.lines().next().unwrap_or("")
+        );
+        let file_changes = animation::file_play_order(metadata, args.file_order)
+            .into_iter()
+            .map(|i| &metadata.changes[i])
+            .filter(|c| !c.is_excluded)
+            .map(|c| (c.path.clone(), c.narration_diff(), c.status.clone()))
+            .collect();
+        let (ordered, skipped) = audio::suggest_reading_order(
+            &vc,
+            &audio::CommitContext::new(metadata),
+            file_changes,
+            args.file_order,
+        )?;
+        for (path, _, status) in &ordered {
+            println!("  {}  {}", status.as_str(), path);
+        }
+        for (path, _, status) in &skipped {
+            println!("  {}  {}  (not narrated)", status.as_str(), path);
+        }
+    }
+    Ok(())
+}
+
+/// Commit hashes listed one per line in a file, or on stdin for `-`. Only the
+/// first word of a line is used, so `git log --oneline` output works as is.
+fn read_commit_list(path: &std::path::Path) -> Result<Vec<String>> {
+    let contents = if path.as_os_str() == "-" {
+        std::io::read_to_string(std::io::stdin()).context("Failed to read commits from stdin")?
+    } else {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read commit list: {}", path.display()))?
+    };
+    Ok(contents
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(String::from)
+        .collect())
+}
+
+/// Print the commits a run would play with the current filters, one per line, without
+/// starting the UI. Random order is listed oldest first since the draw happens at play time.
+fn print_plan(
+    repo: &GitRepository,
+    args: &Args,
+    order: PlaybackOrder,
+    is_range: bool,
+) -> Result<()> {
+    let descending = matches!(order, PlaybackOrder::Desc);
+    let commits: Vec<git::CommitMetadata> = if let Some(ref spec) = args.compare {
+        vec![repo.diff_refs(spec)?]
+    } else if is_range && descending {
+        std::iter::from_fn(|| repo.next_range_commit_desc().ok()).collect()
+    } else if is_range {
+        std::iter::from_fn(|| repo.next_range_commit_asc().ok()).collect()
+    } else if let Some(ref hash) = args.commit {
+        vec![repo.get_commit(hash)?]
+    } else if descending {
+        std::iter::from_fn(|| repo.next_desc_commit().ok()).collect()
+    } else {
+        std::iter::from_fn(|| repo.next_asc_commit().ok()).collect()
+    };
+
+    if matches!(order, PlaybackOrder::Random) && commits.len() > 1 {
+        println!("Commits are picked at random during playback; listed oldest first");
+    }
+    for metadata in &commits {
+        let files = metadata.changes.len();
+        println!(
+            "{}  {}  {}  {}  ({} file{})",
+            &metadata.hash[..7.min(metadata.hash.len())],
+            metadata.date.format("%Y-%m-%d"),
+            metadata.author,
+            metadata.message.lines().next().unwrap_or(""),
+            files,
+            if files == 1 { "" } else { "s" }
+        );
+    }
+    println!(
+        "{} commit{}",
+        commits.len(),
+        if commits.len() == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+/// Display settings shown in the About overlay. `theme`, `speed` and
+/// `background` are the command-line values, if given.
+fn display_settings(
+    keys: &ConfigKeys,
+    args: &Args,
+    config: &Config,
+    theme: Option<&str>,
+    speed: Option<u64>,
+    background: Option<bool>,
+) -> Vec<ResolvedSetting> {
+    let theme = match args.theme_file {
+        Some(ref path) => ResolvedSetting {
+            name: "theme",
+            value: path.display().to_string(),
+            source: SettingSource::Cli,
+        },
+        None => keys.setting("theme", theme, &config.theme),
+    };
+    let gap = |gap: Option<u64>| gap.map_or("auto".to_string(), |ms| format!("{}ms", ms));
+    vec![
+        theme,
+        keys.setting(
+            "speed",
+            speed.map(|ms| format!("{}ms", ms)),
+            format!("{}ms", config.speed),
+        ),
+        keys.setting("background", background, config.background),
+        keys.setting(
+            "line_numbers",
+            args.no_line_numbers.then_some(false),
+            config.line_numbers,
+        ),
+        keys.setting("wrap", args.no_wrap.then_some(false), config.wrap),
+        keys.setting(
+            "commit_gap",
+            args.commit_gap.map(|ms| gap(Some(ms))),
+            gap(config.commit_gap),
+        ),
+        keys.setting(
+            "file_gap",
+            args.file_gap.map(|ms| gap(Some(ms))),
+            gap(config.file_gap),
+        ),
+    ]
+}
+
+/// Resolve the theme; a `--theme-file` takes precedence over the theme name.
+fn load_theme(name: &str, file: Option<&std::path::Path>) -> Result<Theme> {
+    match file {
+        Some(path) => Theme::load_from_file(path),
+        None => Theme::load(name),
+    }
+}
+
+/// Downgrade the theme's RGB colors for terminals without truecolor.
+fn fit_color_depth(theme: Theme, depth: ColorDepth) -> Theme {
+    match depth {
+        ColorDepth::TrueColor => theme,
+        ColorDepth::Ansi256 => theme.with_256_colors(),
+        ColorDepth::Ansi16 => theme.with_16_colors(),
+    }
+}
+
+/// Typing speed that fits all animated files of a commit into `secs`.
+fn speed_for_duration(
+    metadata: &git::CommitMetadata,
+    secs: f64,
+    args: &Args,
+    pauses: &EditPauses,
+) -> u64 {
+    let diff_lines: Vec<&str> = metadata
+        .changes
+        .iter()
+        .filter(|c| !c.is_excluded && !c.exceeds_collapse_threshold(args.collapse_threshold()))
+        .flat_map(|c| c.diff.lines())
+        .collect();
+    audio::llm::speed_for_duration(
+        &diff_lines,
+        secs,
+        args.show_deletions,
+        args.only_additions,
+        args.line_pause_scale(),
+        pauses,
+    )
+}
+
 fn build_speed_rules(cli_rules: &[String], config_rules: &[String]) -> Vec<animation::SpeedRule> {
     cli_rules
         .iter()
         .chain(config_rules.iter())
         .filter_map(|s| {
             animation::SpeedRule::parse(s).or_else(|| {
-                eprintln!("Warning: Invalid speed rule '{}', skipping", s);
+                log::warn!("Invalid speed rule '{}', skipping", s);
                 None
             })
         })
@@ -0,0 +1,204 @@
+use std::time::Instant;
+
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Padding, Paragraph},
+    Frame,
+};
+
+use crate::theme::Theme;
+
+/// Rows taken from the bottom of the screen when captions are shown:
+/// a separator and two lines of narration
+pub const CAPTION_HEIGHT: u16 = 3;
+
+/// Bottom bar showing the narration being spoken, highlighted word by word.
+#[derive(Default)]
+pub struct CaptionPane {
+    current: Option<Caption>,
+    last_tick: Option<Instant>,
+}
+
+struct Caption {
+    chunk_id: usize,
+    words: Vec<String>,
+    duration_secs: f64,
+    elapsed_secs: f64,
+}
+
+impl CaptionPane {
+    /// Show a chunk's narration from its first word, spoken over `duration_secs`.
+    pub fn start(&mut self, chunk_id: usize, text: &str, duration_secs: f64) {
+        self.current = Some(Caption {
+            chunk_id,
+            words: text.split_whitespace().map(str::to_string).collect(),
+            duration_secs,
+            elapsed_secs: 0.0,
+        });
+    }
+
+    /// Chunk whose narration is showing, if any.
+    pub fn chunk_id(&self) -> Option<usize> {
+        self.current.as_ref().map(|c| c.chunk_id)
+    }
+
+    pub fn clear(&mut self) {
+        self.current = None;
+    }
+
+    /// Move the highlight on by the time since the last tick, unless paused.
+    pub fn tick(&mut self, playing: bool) {
+        let now = Instant::now();
+        let delta = self
+            .last_tick
+            .replace(now)
+            .map_or(0.0, |last| now.duration_since(last).as_secs_f64());
+        if playing {
+            self.advance(delta);
+        }
+    }
+
+    fn advance(&mut self, delta_secs: f64) {
+        if let Some(caption) = &mut self.current {
+            caption.elapsed_secs = (caption.elapsed_secs + delta_secs).min(caption.duration_secs);
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let block = Block::default()
+            .padding(Padding::horizontal(1))
+            .style(Style::default().bg(theme.background_left));
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        if inner.height == 0 {
+            return;
+        }
+
+        let separator = Line::from(Span::styled(
+            "─".repeat(area.width as usize),
+            Style::default().fg(theme.separator),
+        ));
+        f.render_widget(Paragraph::new(separator), Rect { height: 1, ..area });
+
+        let Some(caption) = &self.current else {
+            return;
+        };
+        let fraction = if caption.duration_secs > 0.0 {
+            caption.elapsed_secs / caption.duration_secs
+        } else {
+            1.0
+        };
+        let spoken = spoken_words(&caption.words, fraction);
+        let rows = wrap_words(&caption.words, inner.width as usize);
+        // Keep the line being spoken on top, with the next one below it
+        let current_row = rows
+            .iter()
+            .rposition(|row| row.start < spoken.max(1))
+            .unwrap_or(0);
+
+        let lines: Vec<Line> = rows
+            .iter()
+            .skip(current_row)
+            .take(CAPTION_HEIGHT as usize - 1)
+            .map(|row| {
+                let spans: Vec<Span> = (row.start..row.end)
+                    .flat_map(|i| {
+                        let style = if i + 1 == spoken {
+                            Style::default()
+                                .fg(theme.file_tree_current_file_fg)
+                                .add_modifier(Modifier::BOLD)
+                        } else if i < spoken {
+                            Style::default().fg(theme.status_message)
+                        } else {
+                            Style::default().fg(theme.editor_line_number)
+                        };
+                        let separator = if i + 1 < row.end { " " } else { "" };
+                        [
+                            Span::styled(caption.words[i].as_str(), style),
+                            Span::raw(separator),
+                        ]
+                    })
+                    .collect();
+                Line::from(spans)
+            })
+            .collect();
+
+        let text_area = Rect {
+            y: inner.y + 1,
+            height: inner.height.saturating_sub(1),
+            ..inner
+        };
+        f.render_widget(Paragraph::new(lines), text_area);
+    }
+}
+
+/// Words of a row, as a range into the caption's words
+struct Row {
+    start: usize,
+    end: usize,
+}
+
+/// Number of words reached once `fraction` of the narration has been spoken,
+/// assuming time spent on each word grows with its length.
+fn spoken_words(words: &[String], fraction: f64) -> usize {
+    let total: usize = words.iter().map(|w| w.chars().count() + 1).sum();
+    let position = (fraction.clamp(0.0, 1.0) * total as f64).ceil() as usize;
+    let mut start = 0;
+    for (i, word) in words.iter().enumerate() {
+        if start >= position {
+            return i;
+        }
+        start += word.chars().count() + 1;
+    }
+    words.len()
+}
+
+/// Greedily fill rows of `width` columns; words longer than a row get one to themselves.
+fn wrap_words(words: &[String], width: usize) -> Vec<Row> {
+    let mut rows = Vec::new();
+    let mut start = 0;
+    let mut used = 0;
+    for (i, word) in words.iter().enumerate() {
+        let len = word.chars().count();
+        if i > start && used + 1 + len > width {
+            rows.push(Row { start, end: i });
+            start = i;
+            used = len;
+        } else {
+            used += if i > start { 1 + len } else { len };
+        }
+    }
+    if start < words.len() {
+        rows.push(Row {
+            start,
+            end: words.len(),
+        });
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<String> {
+        text.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn highlight_follows_time_weighted_by_word_length() {
+        let w = words("a parser rewrite");
+        assert_eq!(spoken_words(&w, 0.0), 0);
+        assert_eq!(spoken_words(&w, 0.05), 1);
+        // "a " is 2 of 17 characters, so the second word starts right after
+        assert_eq!(spoken_words(&w, 0.2), 2);
+        assert_eq!(spoken_words(&w, 0.6), 3);
+        assert_eq!(spoken_words(&w, 1.0), 3);
+
+        let rows = wrap_words(&words("one two three four"), 9);
+        let ranges: Vec<_> = rows.iter().map(|r| (r.start, r.end)).collect();
+        assert_eq!(ranges, vec![(0, 2), (2, 3), (3, 4)]);
+    }
+}
@@ -6,67 +6,81 @@ use ratatui::{
     Frame,
 };
 
-use crate::animation::{ActivePane, AnimationEngine};
+use crate::animation::{gutter_width, ActivePane, AnimationEngine, EDITOR_PADDING};
+use crate::cli::CursorStyle;
+use crate::syntax::LineHighlights;
 use crate::theme::Theme;
 use crate::widgets::SelectableParagraph;
 
-pub struct EditorPane;
+pub struct EditorPane {
+    cursor_style: CursorStyle,
+}
 
 struct HighlightContext<'a> {
     line_content: &'a str,
     line_num: usize,
     show_cursor: bool,
+    cursor_style: CursorStyle,
     cursor_col: usize,
     cursor_line: usize,
-    old_highlights: &'a [crate::syntax::HighlightSpan],
-    new_highlights: &'a [crate::syntax::HighlightSpan],
+    old_highlights: &'a LineHighlights,
+    new_highlights: &'a LineHighlights,
     old_line_offsets: &'a [usize],
     new_line_offsets: &'a [usize],
     line_offset: isize,
     is_added_line: bool,
+    is_deleting_line: bool,
+    inline_changes: &'a [(usize, usize)],
     theme: &'a Theme,
 }
 
 impl EditorPane {
+    pub fn new() -> Self {
+        Self {
+            cursor_style: CursorStyle::Block,
+        }
+    }
+
+    pub fn set_cursor_style(&mut self, style: CursorStyle) {
+        self.cursor_style = style;
+    }
+
     pub fn render(&self, f: &mut Frame, area: Rect, engine: &AnimationEngine, theme: &Theme) {
         let block = Block::default()
             .style(Style::default().bg(theme.background_right))
             .padding(Padding::vertical(1));
 
-        let content_height = area.height.saturating_sub(2) as usize; // Subtract top and bottom padding
-        let scroll_offset = engine.buffer.scroll_offset;
-        let buffer_lines = &engine.buffer.lines;
-        let line_num_width = format!("{}", buffer_lines.len()).len().max(3);
-
-        let visible_lines: Vec<Line> = buffer_lines
+        // The engine picks the lines, so scrolling and drawing agree on wrapped heights
+        let window = engine.editor_window();
+        let line_num_width = window.line_number_width;
+        let visible_lines: Vec<Line> = engine.buffer.lines[window.first_line..window.end_line]
             .iter()
-            .skip(scroll_offset)
-            .take(content_height)
             .enumerate()
             .map(|(idx, line_content)| {
-                let line_num = scroll_offset + idx;
+                let line_num = window.first_line + idx;
                 self.build_line(line_content, line_num, line_num_width, engine, theme)
             })
             .collect();
 
         // Calculate selected line index in visible_lines
-        let selected_line_index = if engine.buffer.cursor_line >= scroll_offset {
-            let idx = engine.buffer.cursor_line - scroll_offset;
-            if idx < visible_lines.len() {
-                Some(idx)
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        let selected_line_index = engine
+            .buffer
+            .cursor_line
+            .checked_sub(window.first_line)
+            .filter(|&idx| idx < visible_lines.len());
+
+        // Wrapped rows continue under the text, past the gutter
+        let continuation_indent = (EDITOR_PADDING + gutter_width(line_num_width)) as u16;
 
         let content = SelectableParagraph::new(visible_lines)
             .block(block)
             .selected_line(selected_line_index)
             .selected_style(Style::default().bg(theme.editor_cursor_line_bg))
             .background_style(Style::default().bg(theme.background_right))
-            .padding(Padding::horizontal(2))
+            .padding(Padding::horizontal(EDITOR_PADDING as u16))
+            .wrap(window.wrap)
+            .continuation_indent(continuation_indent)
+            .scroll_to_selected(false)
             .dim(20, 0.6);
         f.render_widget(content, area);
     }
@@ -75,7 +89,7 @@ impl EditorPane {
         &self,
         line_content: &str,
         line_num: usize,
-        line_num_width: usize,
+        line_num_width: Option<usize>,
         engine: &AnimationEngine,
         theme: &Theme,
     ) -> Line<'_> {
@@ -84,12 +98,14 @@ impl EditorPane {
 
         let mut spans = Vec::new();
 
-        spans.push(self.render_line_number(line_num, is_cursor_line, line_num_width, theme));
+        if let Some(line_num_width) = line_num_width {
+            spans.push(self.render_line_number(line_num, is_cursor_line, line_num_width, theme));
 
-        spans.push(Span::styled(
-            "  ",
-            Style::default().fg(theme.editor_separator),
-        ));
+            spans.push(Span::styled(
+                "  ",
+                Style::default().fg(theme.editor_separator),
+            ));
+        }
 
         let show_cursor =
             is_cursor_line && engine.cursor_visible && engine.active_pane == ActivePane::Editor;
@@ -100,6 +116,7 @@ impl EditorPane {
             line_content,
             line_num,
             show_cursor,
+            cursor_style: self.cursor_style,
             cursor_col: engine.buffer.cursor_col,
             cursor_line: engine.buffer.cursor_line,
             old_highlights: &engine.buffer.old_highlights,
@@ -108,6 +125,13 @@ impl EditorPane {
             new_line_offsets: &engine.buffer.new_content_line_offsets,
             line_offset: engine.line_offset,
             is_added_line,
+            is_deleting_line: engine.buffer.deleting_line == Some(line_num),
+            inline_changes: engine
+                .buffer
+                .inline_changes
+                .get(&line_num)
+                .map(Vec::as_slice)
+                .unwrap_or(&[]),
             theme,
         });
 
@@ -138,80 +162,26 @@ impl EditorPane {
     }
 
     fn highlight_line(&self, ctx: HighlightContext<'_>) -> Vec<Span<'_>> {
-        let (highlights, line_offsets) = self.select_highlights_and_offsets(
-            ctx.line_num,
-            ctx.cursor_line,
-            ctx.old_highlights,
-            ctx.new_highlights,
-            ctx.old_line_offsets,
-            ctx.new_line_offsets,
-        );
-
-        let byte_offset = self.calculate_byte_offset(
-            ctx.line_num,
-            ctx.cursor_line,
-            ctx.line_offset,
-            line_offsets,
-        );
-
-        let line_highlights =
-            self.filter_line_highlights(highlights, byte_offset, ctx.line_content.len());
-
-        self.apply_highlights(&line_highlights, byte_offset, &ctx)
-    }
-
-    fn select_highlights_and_offsets<'a>(
-        &self,
-        line_num: usize,
-        cursor_line: usize,
-        old_highlights: &'a [crate::syntax::HighlightSpan],
-        new_highlights: &'a [crate::syntax::HighlightSpan],
-        old_line_offsets: &'a [usize],
-        new_line_offsets: &'a [usize],
-    ) -> (&'a [crate::syntax::HighlightSpan], &'a [usize]) {
-        if line_num <= cursor_line {
-            (new_highlights, new_line_offsets)
-        } else {
-            (old_highlights, old_line_offsets)
-        }
-    }
-
-    fn calculate_byte_offset(
-        &self,
-        line_num: usize,
-        cursor_line: usize,
-        line_offset: isize,
-        line_offsets: &[usize],
-    ) -> usize {
-        let target_line = if line_num > cursor_line {
-            ((line_num as isize) - line_offset).max(0) as usize
+        // Lines up to the cursor show new content; those below still show old content
+        let (highlights, line_offsets, source_line) = if ctx.line_num <= ctx.cursor_line {
+            (ctx.new_highlights, ctx.new_line_offsets, ctx.line_num)
         } else {
-            line_num
+            (
+                ctx.old_highlights,
+                ctx.old_line_offsets,
+                ((ctx.line_num as isize) - ctx.line_offset).max(0) as usize,
+            )
         };
+        let source_line = source_line.min(line_offsets.len().saturating_sub(1));
+        let byte_offset = line_offsets.get(source_line).copied().unwrap_or(0);
 
-        line_offsets
-            .get(target_line)
-            .copied()
-            .unwrap_or_else(|| *line_offsets.last().unwrap_or(&0))
-    }
-
-    fn filter_line_highlights(
-        &self,
-        highlights: &[crate::syntax::HighlightSpan],
-        byte_offset: usize,
-        line_len: usize,
-    ) -> Vec<(usize, usize, crate::syntax::TokenType)> {
-        let line_end = byte_offset + line_len;
-        highlights
+        let line_highlights: Vec<_> = highlights
+            .line(source_line)
             .iter()
-            .filter_map(|h| {
-                if h.start < line_end && h.end > byte_offset {
-                    Some((h.start, h.end, h.token_type))
-                } else {
-                    None
-                }
-            })
-            .collect()
+            .map(|h| (h.start, h.end, h.token_type))
+            .collect();
+
+        self.apply_highlights(&line_highlights, byte_offset, &ctx)
     }
 
     fn apply_highlights(
@@ -232,38 +202,47 @@ impl EditorPane {
             let color =
                 self.get_char_color(char_byte_start, char_byte_end, line_highlights, ctx.theme);
 
-            if ctx.show_cursor && char_idx == ctx.cursor_col {
-                // Cursor character - bright highlight
-                spans.push(Span::styled(
-                    ch.to_string(),
-                    Style::default()
-                        .bg(ctx.theme.editor_cursor_char_bg)
-                        .fg(ctx.theme.editor_cursor_char_fg)
-                        .add_modifier(Modifier::BOLD),
-                ));
+            let style = if ctx.is_deleting_line {
+                // Line about to be deleted - struck through in the deletion color
+                Style::default()
+                    .fg(ctx.theme.file_tree_deleted)
+                    .add_modifier(Modifier::CROSSED_OUT)
             } else if ctx.is_added_line {
-                // Added line - show with green background
-                spans.push(Span::styled(
-                    ch.to_string(),
-                    Style::default().fg(color).bg(Color::Rgb(0, 64, 0)), // Dark green background
-                ));
+                // Added line - tinted background under the syntax colors, changed words stand out
+                let in_changed_word = ctx
+                    .inline_changes
+                    .iter()
+                    .any(|&(start, end)| char_idx >= start && char_idx < end);
+                let bg = if in_changed_word {
+                    ctx.theme.editor_word_diff_bg
+                } else {
+                    ctx.theme.editor_added_line_bg
+                };
+                Style::default().fg(color).bg(bg)
             } else {
                 // Normal character
-                spans.push(Span::styled(ch.to_string(), Style::default().fg(color)));
+                Style::default().fg(color)
+            };
+
+            if ctx.show_cursor && char_idx == ctx.cursor_col {
+                spans.push(Span::styled(ch.to_string(), cursor_style(ctx, style)));
+            } else {
+                spans.push(Span::styled(ch.to_string(), style));
             }
         }
 
-        if ctx.show_cursor && ctx.cursor_col >= chars.len() {
-            spans.push(Span::styled(
-                " ",
+        if ctx.cursor_col >= chars.len() && (ctx.show_cursor || ctx.is_added_line) {
+            // Extend the added-line background to the end of the line, under the cursor if any
+            let style = if ctx.is_added_line {
+                Style::default().bg(ctx.theme.editor_added_line_bg)
+            } else {
                 Style::default()
-                    .bg(ctx.theme.editor_cursor_char_bg)
-                    .fg(ctx.theme.editor_cursor_char_fg)
-                    .add_modifier(Modifier::BOLD),
-            ));
-        } else if ctx.is_added_line && ctx.cursor_col >= chars.len() {
-            // Extend green background to end of added line
-            spans.push(Span::styled(" ", Style::default().bg(Color::Rgb(0, 64, 0))));
+            };
+            if ctx.show_cursor {
+                spans.push(end_of_line_cursor(ctx, style));
+            } else {
+                spans.push(Span::styled(" ", style));
+            }
         }
 
         spans
@@ -283,3 +262,85 @@ impl EditorPane {
             .unwrap_or(theme.syntax_variable) // Use theme color instead of Color::White
     }
 }
+
+/// Style for the glyph under the cursor. A block inverts the whole cell; bar
+/// and underline keep the line's own background and only tint the glyph.
+fn cursor_style(ctx: &HighlightContext, style: Style) -> Style {
+    let cursor = ctx.theme.editor_cursor_char_bg;
+    match ctx.cursor_style {
+        // Without colors the block falls back to inverse video
+        CursorStyle::Block if cursor == Color::Reset => {
+            Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        }
+        CursorStyle::Block => Style::default()
+            .bg(cursor)
+            .fg(ctx.theme.editor_cursor_char_fg)
+            .add_modifier(Modifier::BOLD),
+        CursorStyle::Bar => style.fg(cursor).add_modifier(Modifier::BOLD),
+        CursorStyle::Underline => style
+            .underline_color(cursor)
+            .add_modifier(Modifier::UNDERLINED),
+    }
+}
+
+/// Cursor past the last character, drawn as a thin glyph for bar and underline.
+fn end_of_line_cursor(ctx: &HighlightContext, style: Style) -> Span<'static> {
+    let thin = |symbol| Span::styled(symbol, style.fg(ctx.theme.editor_cursor_char_bg));
+    match ctx.cursor_style {
+        CursorStyle::Block => Span::styled(" ", cursor_style(ctx, style)),
+        CursorStyle::Bar => thin("▏"),
+        CursorStyle::Underline => thin("▁"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context<'a>(
+        theme: &'a Theme,
+        cursor_style: CursorStyle,
+        cursor_col: usize,
+    ) -> HighlightContext<'a> {
+        HighlightContext {
+            line_content: "ab",
+            line_num: 0,
+            show_cursor: true,
+            cursor_style,
+            cursor_col,
+            cursor_line: 0,
+            old_highlights: Box::leak(Box::default()),
+            new_highlights: Box::leak(Box::default()),
+            old_line_offsets: &[],
+            new_line_offsets: &[],
+            line_offset: 0,
+            is_added_line: true,
+            is_deleting_line: false,
+            inline_changes: &[],
+            theme,
+        }
+    }
+
+    #[test]
+    fn thin_cursors_keep_the_line_background() {
+        let theme = Theme::default();
+        let pane = EditorPane::new();
+        let added_bg = Some(theme.editor_added_line_bg);
+
+        let block = pane.apply_highlights(&[], 0, &context(&theme, CursorStyle::Block, 1));
+        assert_eq!(block[1].style.bg, Some(theme.editor_cursor_char_bg));
+
+        let underline = pane.apply_highlights(&[], 0, &context(&theme, CursorStyle::Underline, 1));
+        assert_eq!(underline[1].style.bg, added_bg);
+        assert!(underline[1]
+            .style
+            .add_modifier
+            .contains(Modifier::UNDERLINED));
+
+        let bar = pane.apply_highlights(&[], 0, &context(&theme, CursorStyle::Bar, 2));
+        assert_eq!(bar.len(), 3);
+        assert_eq!(bar[2].content, "▏");
+        assert_eq!(bar[2].style.bg, added_bg);
+        assert_eq!(bar[2].style.fg, Some(theme.editor_cursor_char_bg));
+    }
+}
@@ -0,0 +1,176 @@
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::animation::AnimationEngine;
+use crate::git::{DiffHunk, FileStatus, LineChangeType};
+use crate::theme::Theme;
+
+/// Columns taken from the editor when the minimap is shown
+pub const MINIMAP_WIDTH: u16 = 2;
+
+/// Thin overview column showing where the current file changes and where the animation is.
+pub struct MinimapPane;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum RowKind {
+    Context,
+    Added,
+    Deleted,
+}
+
+impl MinimapPane {
+    pub fn render(&self, f: &mut Frame, area: Rect, engine: &AnimationEngine, theme: &Theme) {
+        let height = area.height as usize;
+        if height == 0 {
+            return;
+        }
+
+        let change = engine
+            .current_metadata()
+            .and_then(|m| m.changes.get(engine.current_file_index));
+
+        let (rows, total_lines) = match change {
+            Some(change) => {
+                // Deleted files have no new content, so map them onto the old one
+                let deleted = change.status == FileStatus::Deleted;
+                let content = if deleted {
+                    &change.old_content
+                } else {
+                    &change.new_content
+                };
+                let total = content
+                    .as_deref()
+                    .map(|c| c.lines().count())
+                    .unwrap_or(0)
+                    .max(engine.buffer.lines.len())
+                    .max(1);
+                (row_kinds(&change.hunks, deleted, total, height), total)
+            }
+            None => (vec![RowKind::Context; height], 1),
+        };
+
+        let cursor_row = row_for_line(engine.buffer.cursor_line, total_lines, height);
+
+        let lines: Vec<Line> = rows
+            .iter()
+            .enumerate()
+            .map(|(row, kind)| {
+                let style = if row == cursor_row {
+                    Style::default()
+                        .fg(theme.file_tree_current_file_fg)
+                        .bg(theme.editor_cursor_line_bg)
+                } else {
+                    let fg = match kind {
+                        RowKind::Added => theme.file_tree_added,
+                        RowKind::Deleted => theme.file_tree_deleted,
+                        RowKind::Context => theme.separator,
+                    };
+                    Style::default().fg(fg)
+                };
+                let glyph = match (row == cursor_row, kind) {
+                    (true, _) => "▶█",
+                    (false, RowKind::Context) => " │",
+                    (false, _) => " █",
+                };
+                Line::from(Span::styled(glyph, style))
+            })
+            .collect();
+
+        let minimap = Paragraph::new(lines).style(Style::default().bg(theme.background_right));
+        f.render_widget(minimap, area);
+    }
+}
+
+/// Row of a `height`-row minimap that covers the given 0-based line.
+fn row_for_line(line: usize, total_lines: usize, height: usize) -> usize {
+    (line * height / total_lines.max(1)).min(height.saturating_sub(1))
+}
+
+/// Classify each minimap row by the strongest change it covers (deletion > addition > context).
+/// Deletions sit between lines, so they are placed at the line that follows them.
+fn row_kinds(hunks: &[DiffHunk], deleted_file: bool, total: usize, height: usize) -> Vec<RowKind> {
+    let mut rows = vec![RowKind::Context; height];
+    let mut mark = |line: usize, kind: RowKind| {
+        let row = row_for_line(line, total, height);
+        rows[row] = rows[row].max(kind);
+    };
+
+    for hunk in hunks {
+        let start = if deleted_file {
+            hunk.old_start
+        } else {
+            hunk.new_start
+        };
+        let mut line = start.saturating_sub(1);
+        for change in &hunk.lines {
+            match change.change_type {
+                LineChangeType::Addition => {
+                    mark(line, RowKind::Added);
+                    line += 1;
+                }
+                LineChangeType::Deletion => {
+                    mark(line, RowKind::Deleted);
+                    if deleted_file {
+                        line += 1;
+                    }
+                }
+                LineChangeType::Context => line += 1,
+            }
+        }
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::LineChange;
+
+    fn line(change_type: LineChangeType) -> LineChange {
+        LineChange {
+            change_type,
+            content: String::new(),
+            old_line_no: None,
+            new_line_no: None,
+            inline_changes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn rows_follow_change_positions_in_the_new_file() {
+        let hunk = DiffHunk {
+            old_start: 3,
+            old_lines: 5,
+            new_start: 3,
+            new_lines: 5,
+            lines: vec![
+                line(LineChangeType::Context),
+                line(LineChangeType::Deletion),
+                line(LineChangeType::Context),
+                line(LineChangeType::Context),
+                line(LineChangeType::Context),
+                line(LineChangeType::Addition),
+            ],
+        };
+        // 10 lines on 5 rows: two lines per row
+        let rows = row_kinds(&[hunk], false, 10, 5);
+        assert_eq!(
+            rows,
+            vec![
+                RowKind::Context,
+                RowKind::Deleted,
+                RowKind::Context,
+                RowKind::Added,
+                RowKind::Context,
+            ]
+        );
+        assert_eq!(row_for_line(9, 10, 5), 4);
+        assert_eq!(row_for_line(50, 10, 5), 4);
+    }
+}
@@ -1,9 +1,13 @@
+mod caption;
 mod editor;
 mod file_tree;
+mod minimap;
 mod status_bar;
 mod terminal;
 
+pub use caption::{CaptionPane, CAPTION_HEIGHT};
 pub use editor::EditorPane;
 pub use file_tree::FileTreePane;
+pub use minimap::{MinimapPane, MINIMAP_WIDTH};
 pub use status_bar::StatusBarPane;
 pub use terminal::TerminalPane;
@@ -13,11 +13,16 @@ use crate::widgets::SelectableParagraph;
 pub struct StatusBarPane;
 
 impl StatusBarPane {
+    #[allow(clippy::too_many_arguments)]
     pub fn render(
         &self,
         f: &mut Frame,
         area: Rect,
         metadata: Option<&CommitMetadata>,
+        progress: f32,
+        range_position: Option<(usize, usize)>,
+        muted: bool,
+        notice: Option<&str>,
         theme: &Theme,
     ) {
         let block = Block::default()
@@ -52,6 +57,42 @@ impl StatusBarPane {
                 ]));
             }
 
+            let mut progress_line = vec![
+                Span::raw("progress: "),
+                Span::styled(
+                    progress_bar(progress),
+                    Style::default().fg(theme.status_hash),
+                ),
+                Span::raw(format!(" {:>3}%", (progress * 100.0).round() as u8)),
+            ];
+            if muted {
+                progress_line.push(Span::styled(
+                    "  muted",
+                    Style::default().fg(theme.status_author),
+                ));
+            }
+            lines.push(Line::from(progress_line));
+
+            // Whole-range progress counts finished commits plus the current one's share
+            if let Some((position, total)) = range_position {
+                let overall = (position as f32 + progress) / total.max(1) as f32;
+                lines.push(Line::from(vec![
+                    Span::raw("range:    "),
+                    Span::styled(
+                        progress_bar(overall),
+                        Style::default().fg(theme.status_date),
+                    ),
+                    Span::raw(format!(" {}/{}", position + 1, total)),
+                ]));
+            }
+
+            if let Some(notice) = notice {
+                lines.push(Line::from(Span::styled(
+                    notice,
+                    Style::default().fg(theme.status_author),
+                )));
+            }
+
             // Add commit message lines (skip empty lines)
             for msg_line in meta.message.lines() {
                 if !msg_line.trim().is_empty() {
@@ -78,3 +119,15 @@ impl StatusBarPane {
         f.render_widget(content, area);
     }
 }
+
+const PROGRESS_BAR_WIDTH: usize = 20;
+
+/// Text bar for the seek position, e.g. `━━━━━━──────────────`.
+fn progress_bar(progress: f32) -> String {
+    let filled = (progress.clamp(0.0, 1.0) * PROGRESS_BAR_WIDTH as f32).round() as usize;
+    format!(
+        "{}{}",
+        "━".repeat(filled),
+        "─".repeat(PROGRESS_BAR_WIDTH - filled)
+    )
+}
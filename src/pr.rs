@@ -0,0 +1,265 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::git::GitRepository;
+
+// How long to wait for the GitHub or GitLab API
+const API_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Code host serving a pull or merge request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Host {
+    GitHub,
+    GitLab,
+}
+
+/// A GitHub pull request or GitLab merge request, named by its web URL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullRequest {
+    pub host: Host,
+    /// Domain of the web URL, e.g. `github.com` or a self-hosted GitLab
+    pub domain: String,
+    /// `owner/repo`, or the full group path of a GitLab project
+    pub project: String,
+    pub number: u64,
+}
+
+#[derive(Deserialize)]
+struct GitHubPull {
+    base: GitHubRef,
+    head: GitHubRef,
+}
+
+#[derive(Deserialize)]
+struct GitHubRef {
+    sha: String,
+}
+
+#[derive(Deserialize)]
+struct GitLabMergeRequest {
+    diff_refs: Option<GitLabDiffRefs>,
+}
+
+#[derive(Deserialize)]
+struct GitLabDiffRefs {
+    base_sha: String,
+    head_sha: String,
+}
+
+impl PullRequest {
+    /// Parse `https://github.com/OWNER/REPO/pull/N` or
+    /// `https://HOST/GROUP/PROJECT/-/merge_requests/N` (any GitLab host).
+    pub fn parse(url: &str) -> Result<Self> {
+        let invalid = || {
+            anyhow::anyhow!(
+                "Not a pull request URL: {} (expected https://github.com/OWNER/REPO/pull/N or https://gitlab.com/GROUP/PROJECT/-/merge_requests/N)",
+                url
+            )
+        };
+        let url = url.trim();
+        let rest = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .ok_or_else(invalid)?;
+        let (domain, path) = rest.split_once('/').ok_or_else(invalid)?;
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let parts: Vec<&str> = path.split('/').filter(|p| !p.is_empty()).collect();
+
+        let (host, project, number) = match parts.iter().position(|p| *p == "-") {
+            Some(i) if i >= 2 && parts.get(i + 1) == Some(&"merge_requests") => {
+                (Host::GitLab, parts[..i].join("/"), parts.get(i + 2))
+            }
+            _ => match parts.as_slice() {
+                [owner, repo, "pull", number, ..] if domain == "github.com" => {
+                    (Host::GitHub, format!("{}/{}", owner, repo), Some(number))
+                }
+                _ => return Err(invalid()),
+            },
+        };
+        let number = number.and_then(|n| n.parse().ok()).ok_or_else(invalid)?;
+
+        Ok(Self {
+            host,
+            domain: domain.to_string(),
+            project,
+            number,
+        })
+    }
+
+    /// Whether a remote URL (HTTPS or SSH) points at this request's project.
+    pub fn matches_remote(&self, remote: &str) -> bool {
+        let remote = remote.trim_end_matches('/');
+        let remote = remote.strip_suffix(".git").unwrap_or(remote).to_lowercase();
+        let project = self.project.to_lowercase();
+        remote.contains(&self.domain.to_lowercase())
+            && (remote.ends_with(&format!("/{}", project))
+                || remote.ends_with(&format!(":{}", project)))
+    }
+
+    /// Ref that holds the request's commits on the remote.
+    fn head_ref(&self) -> String {
+        match self.host {
+            Host::GitHub => format!("pull/{}/head", self.number),
+            Host::GitLab => format!("merge-requests/{}/head", self.number),
+        }
+    }
+
+    /// Base and head commit hashes from the host's API. `GITHUB_TOKEN` or
+    /// `GITLAB_TOKEN` is sent when set, which private projects need.
+    fn fetch_shas(&self) -> Result<(String, String)> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(API_TIMEOUT)
+            .user_agent(concat!("torvax/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("Failed to create HTTP client")?;
+
+        let (request, token_var) = match self.host {
+            Host::GitHub => {
+                let url = format!(
+                    "https://api.github.com/repos/{}/pulls/{}",
+                    self.project, self.number
+                );
+                let mut request = client
+                    .get(url)
+                    .header("Accept", "application/vnd.github+json");
+                if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+                    request = request.bearer_auth(token);
+                }
+                (request, "GITHUB_TOKEN")
+            }
+            Host::GitLab => {
+                let url = format!(
+                    "https://{}/api/v4/projects/{}/merge_requests/{}",
+                    self.domain,
+                    self.project.replace('/', "%2F"),
+                    self.number
+                );
+                let mut request = client.get(url);
+                if let Ok(token) = std::env::var("GITLAB_TOKEN") {
+                    request = request.header("PRIVATE-TOKEN", token);
+                }
+                (request, "GITLAB_TOKEN")
+            }
+        };
+
+        let response = request
+            .send()
+            .with_context(|| format!("Failed to reach {} for {}", self.domain, self))?;
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND
+            || status == reqwest::StatusCode::UNAUTHORIZED
+            || status == reqwest::StatusCode::FORBIDDEN
+        {
+            anyhow::bail!(
+                "{} could not be read ({}); for a private project set {}",
+                self,
+                status,
+                token_var
+            );
+        }
+        if !status.is_success() {
+            anyhow::bail!("{} could not be read ({})", self, status);
+        }
+
+        match self.host {
+            Host::GitHub => {
+                let pull: GitHubPull = response
+                    .json()
+                    .with_context(|| format!("Unexpected API response for {}", self))?;
+                Ok((pull.base.sha, pull.head.sha))
+            }
+            Host::GitLab => {
+                let request: GitLabMergeRequest = response
+                    .json()
+                    .with_context(|| format!("Unexpected API response for {}", self))?;
+                let refs = request
+                    .diff_refs
+                    .with_context(|| format!("{} has no diff yet", self))?;
+                Ok((refs.base_sha, refs.head_sha))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for PullRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.host {
+            Host::GitHub => write!(f, "{}#{}", self.project, self.number),
+            Host::GitLab => write!(f, "{}!{}", self.project, self.number),
+        }
+    }
+}
+
+/// Commit range `BASE..HEAD` of a pull or merge request, for `--pr`. The current
+/// repository must be a clone of the request's project with its commits fetched;
+/// nothing is cloned or fetched here.
+pub fn commit_range(repo: &GitRepository, url: &str) -> Result<String> {
+    let pr = PullRequest::parse(url)?;
+    if !repo
+        .remote_urls()
+        .iter()
+        .any(|remote| pr.matches_remote(remote))
+    {
+        anyhow::bail!(
+            "This repository has no remote for {}; clone it first (git clone https://{}/{}) and run torvax inside the clone",
+            pr.project,
+            pr.domain,
+            pr.project
+        );
+    }
+
+    let (base, head) = pr.fetch_shas()?;
+    if !repo.has_commit(&head) {
+        anyhow::bail!(
+            "The commits of {} are not in this clone; fetch them first: git fetch origin {}",
+            pr,
+            pr.head_ref()
+        );
+    }
+    if !repo.has_commit(&base) {
+        anyhow::bail!(
+            "The base commit {} of {} is not in this clone; fetch it first: git fetch origin",
+            &base[..7.min(base.len())],
+            pr
+        );
+    }
+    Ok(format!("{}..{}", base, head))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_github_and_gitlab_urls() {
+        let pr = PullRequest::parse("https://github.com/org/repo/pull/123/files").unwrap();
+        assert_eq!(pr.host, Host::GitHub);
+        assert_eq!(pr.project, "org/repo");
+        assert_eq!(pr.number, 123);
+        assert_eq!(pr.to_string(), "org/repo#123");
+
+        let mr = PullRequest::parse(
+            "https://gitlab.example.com/group/sub/app/-/merge_requests/7?tab=diffs",
+        )
+        .unwrap();
+        assert_eq!(mr.host, Host::GitLab);
+        assert_eq!(mr.domain, "gitlab.example.com");
+        assert_eq!(mr.project, "group/sub/app");
+        assert_eq!(mr.number, 7);
+
+        assert!(PullRequest::parse("https://github.com/org/repo/issues/5").is_err());
+        assert!(PullRequest::parse("github.com/org/repo/pull/5").is_err());
+        assert!(PullRequest::parse("https://github.com/org/repo/pull/abc").is_err());
+    }
+
+    #[test]
+    fn matches_https_and_ssh_remotes() {
+        let pr = PullRequest::parse("https://github.com/Org/Repo/pull/1").unwrap();
+        assert!(pr.matches_remote("https://github.com/org/repo.git"));
+        assert!(pr.matches_remote("git@github.com:Org/Repo.git"));
+        assert!(!pr.matches_remote("https://github.com/org/other-repo.git"));
+        assert!(!pr.matches_remote("https://gitlab.com/org/repo.git"));
+    }
+}
@@ -0,0 +1,68 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where playback stopped in a repository, saved on exit for `--resume`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResumeState {
+    pub commit: String,
+    /// Fraction of the commit's animation already played (0.0-1.0)
+    pub progress: f32,
+}
+
+/// State file for a repository, e.g. `<cache_dir>/repo-<hash>/resume.json`
+/// under `config::cache_dir()`.
+/// Keyed by a hash of the repository path so repositories resume independently.
+pub fn state_path(repo_path: &Path) -> Option<PathBuf> {
+    let repo_key = format!(
+        "repo-{:016x}",
+        crate::config::stable_hash(repo_path.as_os_str().as_encoded_bytes())
+    );
+    crate::config::cache_dir().map(|dir| dir.join(repo_key).join("resume.json"))
+}
+
+/// Read a saved position. Missing or unreadable state yields None.
+pub fn load(path: &Path) -> Option<ResumeState> {
+    let json = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+pub fn save(path: &Path, state: &ResumeState) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create state directory: {}", dir.display()))?;
+    }
+    let json = serde_json::to_string(state).context("Failed to serialize resume state")?;
+    fs::write(path, json)
+        .with_context(|| format!("Failed to write resume state: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_then_load_round_trips_and_missing_file_is_none() {
+        let path = std::env::temp_dir()
+            .join(format!("torvax_resume_{}", std::process::id()))
+            .join("resume.json");
+        assert!(load(&path).is_none());
+
+        let state = ResumeState {
+            commit: "abc123".to_string(),
+            progress: 0.4,
+        };
+        save(&path, &state).unwrap();
+        let loaded = load(&path);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+        assert_eq!(loaded, Some(state));
+    }
+
+    #[test]
+    fn repositories_get_separate_state_files() {
+        let a = state_path(Path::new("/work/a"));
+        let b = state_path(Path::new("/work/b"));
+        assert_ne!(a, b);
+    }
+}
@@ -1,10 +1,10 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::sync::Arc;
 
-use crate::audio::{AudioPlayer, VoiceoverProvider};
+use crate::audio::{AudioPlayer, VoiceoverConfig, VoiceoverProvider};
 use crate::cli::Args;
 use crate::config;
-use crate::config::Config;
+use crate::config::{Config, ConfigKeys, ResolvedSetting, SettingSource};
 
 /// Interactively prompt for an API key, persist it to config, and return it.
 pub fn prompt_for_key(label: &str, help_url: &str, config_field: &str) -> Option<String> {
@@ -34,6 +34,47 @@ pub fn prompt_for_key(label: &str, help_url: &str, config_field: &str) -> Option
     Some(key)
 }
 
+/// Apply the CLI and environment settings that shape LLM explanations.
+fn apply_llm_overrides(vc: &mut VoiceoverConfig, args: &Args) -> Result<()> {
+    if let Some(ref path) = args.prompt_file {
+        let prompt = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read prompt file: {}", path.display()))?;
+        vc.narration_prompt = Some(prompt);
+    }
+    if let Some(ref prompt) = vc.narration_prompt {
+        if !prompt.contains("{diff}") {
+            log::warn!(
+                "Narration prompt has no {{diff}} placeholder; the code changes won't be sent"
+            );
+        }
+    }
+    if args.narrate_max_files.is_some() {
+        vc.max_files = args.narrate_max_files;
+    }
+    if args.language.is_some() {
+        vc.language = args.language.clone();
+    }
+    if vc.openai_api_key.is_none() {
+        if let Ok(k) = std::env::var("OPENAI_API_KEY") {
+            vc.openai_api_key = Some(k);
+        }
+    }
+    Ok(())
+}
+
+/// Voiceover config for explanations only (e.g. `--report`): no speech is synthesized,
+/// so only LLM access is required.
+pub fn llm_config(config: &Config, args: &Args) -> Result<VoiceoverConfig> {
+    let mut vc = config.voiceover.clone();
+    apply_llm_overrides(&mut vc, args)?;
+    if !vc.has_llm_access() {
+        anyhow::bail!(
+            "Explanations need an OpenAI API key (openai_api_key or OPENAI_API_KEY) or an llm_base_url"
+        );
+    }
+    Ok(vc)
+}
+
 /// Build an AudioPlayer from config + CLI args, prompting for missing keys if needed.
 #[allow(clippy::arc_with_non_send_sync)]
 pub fn create_audio_player(config: &Config, args: &Args) -> Result<Option<Arc<AudioPlayer>>> {
@@ -50,9 +91,13 @@ pub fn create_audio_player(config: &Config, args: &Args) -> Result<Option<Arc<Au
         vc.provider = match p.to_lowercase().as_str() {
             "elevenlabs" => VoiceoverProvider::ElevenLabs,
             "inworld" => VoiceoverProvider::Inworld,
+            "openai" => VoiceoverProvider::OpenAI,
+            "google" => VoiceoverProvider::GoogleTts,
+            "azure" => VoiceoverProvider::Azure,
+            "piper" => VoiceoverProvider::Piper,
             _ => {
-                eprintln!(
-                    "Warning: Unknown voiceover provider '{}', using default (inworld)",
+                log::warn!(
+                    "Unknown voiceover provider '{}', using default (inworld)",
                     p
                 );
                 vc.provider
@@ -60,19 +105,61 @@ pub fn create_audio_player(config: &Config, args: &Args) -> Result<Option<Arc<Au
         };
     }
 
+    if args.voice.is_some() {
+        vc.voice_id = args.voice.clone();
+    }
+    if args.tts_model.is_some() {
+        vc.model_id = args.tts_model.clone();
+    }
+    if args.no_cache {
+        vc.cache = false;
+    }
+    if args.intro {
+        vc.commit_intro = true;
+    }
+    if args.no_intro {
+        vc.commit_intro = false;
+    }
+    if args.no_file_intros {
+        vc.file_intros = false;
+    }
+    if let Some(factor) = args.narration_speed {
+        vc.narration_speed = factor;
+    }
+    if let Some(volume) = args.volume {
+        vc.volume = volume;
+    }
+    apply_llm_overrides(&mut vc, args)?;
+
     // Fill from environment variables
     if vc.enabled && vc.api_key.is_none() {
-        let env_key = match vc.provider {
-            VoiceoverProvider::ElevenLabs => std::env::var("ELEVENLABS_API_KEY"),
-            VoiceoverProvider::Inworld => std::env::var("INWORLD_API_KEY"),
+        let env_var = match vc.provider {
+            VoiceoverProvider::ElevenLabs => Some("ELEVENLABS_API_KEY"),
+            VoiceoverProvider::Inworld => Some("INWORLD_API_KEY"),
+            // OpenAI TTS reuses openai_api_key, Google and Azure have their own fields,
+            // Piper needs no key
+            VoiceoverProvider::OpenAI
+            | VoiceoverProvider::GoogleTts
+            | VoiceoverProvider::Azure
+            | VoiceoverProvider::Piper => None,
         };
-        if let Ok(k) = env_key {
+        if let Some(Ok(k)) = env_var.map(std::env::var) {
             vc.api_key = Some(k);
         }
     }
-    if vc.openai_api_key.is_none() {
-        if let Ok(k) = std::env::var("OPENAI_API_KEY") {
-            vc.openai_api_key = Some(k);
+    if vc.google_api_key.is_none() {
+        if let Ok(k) = std::env::var("GOOGLE_API_KEY") {
+            vc.google_api_key = Some(k);
+        }
+    }
+    if vc.azure_api_key.is_none() {
+        if let Ok(k) = std::env::var("AZURE_SPEECH_KEY") {
+            vc.azure_api_key = Some(k);
+        }
+    }
+    if vc.azure_region.is_none() {
+        if let Ok(region) = std::env::var("AZURE_SPEECH_REGION") {
+            vc.azure_region = Some(region);
         }
     }
 
@@ -81,7 +168,7 @@ pub fn create_audio_player(config: &Config, args: &Args) -> Result<Option<Arc<Au
     }
 
     // Prompt for missing keys
-    if vc.openai_api_key.is_none() {
+    if !vc.has_llm_access() {
         vc.openai_api_key = prompt_for_key(
             "OpenAI API key (for GPT-5.2 explanations)",
             "https://platform.openai.com/api-keys",
@@ -91,7 +178,39 @@ pub fn create_audio_player(config: &Config, args: &Args) -> Result<Option<Arc<Au
             return Ok(None);
         }
     }
-    if vc.api_key.is_none() {
+    if vc.provider == VoiceoverProvider::GoogleTts {
+        if vc.google_api_key.is_none() {
+            vc.google_api_key = prompt_for_key(
+                "Google Cloud API key (for text-to-speech)",
+                "https://console.cloud.google.com/apis/credentials",
+                "google_api_key",
+            );
+            if vc.google_api_key.is_none() {
+                return Ok(None);
+            }
+        }
+    } else if vc.provider == VoiceoverProvider::Azure {
+        if vc.azure_region.is_none() {
+            anyhow::bail!(
+                "Azure narration needs the Speech resource region (azure_region or AZURE_SPEECH_REGION)"
+            );
+        }
+        if vc.azure_api_key.is_none() {
+            vc.azure_api_key = prompt_for_key(
+                "Azure Speech key (for text-to-speech)",
+                "https://portal.azure.com  →  Speech service  →  Keys and Endpoint",
+                "azure_api_key",
+            );
+            if vc.azure_api_key.is_none() {
+                return Ok(None);
+            }
+        }
+    } else if vc.api_key.is_none()
+        && !matches!(
+            vc.provider,
+            VoiceoverProvider::OpenAI | VoiceoverProvider::Piper
+        )
+    {
         vc.api_key = prompt_for_key(
             "Inworld API key (for text-to-speech)",
             "https://inworld.ai  →  API  →  Basic Auth key",
@@ -107,17 +226,96 @@ pub fn create_audio_player(config: &Config, args: &Args) -> Result<Option<Arc<Au
     let _ = Config::enable_voiceover();
     let _ = Config::save_voiceover_key("use_llm_explanations", "true");
 
-    eprintln!("[SETUP] Creating AudioPlayer...");
+    log::debug!("Creating AudioPlayer...");
     match AudioPlayer::new(vc) {
         Ok(player) => {
-            eprintln!("[SETUP] AudioPlayer created successfully, wrapping in Arc...");
+            log::debug!("AudioPlayer created successfully, wrapping in Arc...");
             let arc_player = Arc::new(player);
-            eprintln!("[SETUP] Arc<AudioPlayer> created, returning...");
+            log::debug!("Arc<AudioPlayer> created, returning...");
             Ok(Some(arc_player))
         }
         Err(e) => {
-            eprintln!("\ntorvax: Failed to initialize audio: {}", e);
+            log::warn!("Failed to initialize audio: {:#}", e);
             Ok(None)
         }
     }
 }
+
+/// Voiceover settings for the About overlay. API keys are only reported as set
+/// or missing, never shown.
+pub fn voiceover_settings(
+    config: &Config,
+    args: &Args,
+    keys: &ConfigKeys,
+    player: Option<&AudioPlayer>,
+) -> Vec<ResolvedSetting> {
+    let vc = player.map_or(&config.voiceover, AudioPlayer::voiceover_config);
+    let enabled_by_cli = args.voiceover.is_some() || args.elevenlabs;
+    let provider_by_cli = args.voiceover_provider.is_some() || args.elevenlabs;
+    let speech_key_env = match vc.provider {
+        VoiceoverProvider::ElevenLabs => Some("ELEVENLABS_API_KEY"),
+        VoiceoverProvider::Inworld => Some("INWORLD_API_KEY"),
+        _ => None,
+    };
+
+    vec![
+        keys.setting(
+            "voiceover.enabled",
+            enabled_by_cli.then_some(player.is_some()),
+            player.is_some(),
+        ),
+        keys.setting(
+            "voiceover.provider",
+            provider_by_cli.then_some(vc.provider.as_str()),
+            vc.provider.as_str(),
+        ),
+        key_setting(
+            "voiceover.api_key",
+            &config.voiceover.api_key,
+            speech_key_env,
+            &vc.api_key,
+        ),
+        key_setting(
+            "voiceover.openai_api_key",
+            &config.voiceover.openai_api_key,
+            Some("OPENAI_API_KEY"),
+            &vc.openai_api_key,
+        ),
+        key_setting(
+            "voiceover.google_api_key",
+            &config.voiceover.google_api_key,
+            Some("GOOGLE_API_KEY"),
+            &vc.google_api_key,
+        ),
+        key_setting(
+            "voiceover.azure_api_key",
+            &config.voiceover.azure_api_key,
+            Some("AZURE_SPEECH_KEY"),
+            &vc.azure_api_key,
+        ),
+    ]
+}
+
+fn key_setting(
+    name: &'static str,
+    from_config: &Option<String>,
+    env_var: Option<&str>,
+    resolved: &Option<String>,
+) -> ResolvedSetting {
+    let from_env = env_var.is_some_and(|var| std::env::var(var).is_ok());
+    let (value, source) = if from_config.is_some() {
+        ("set", SettingSource::ConfigFile)
+    } else if from_env {
+        ("set", SettingSource::Env)
+    } else if resolved.is_some() {
+        // Entered at the prompt, which saves it to the config file
+        ("set", SettingSource::ConfigFile)
+    } else {
+        ("missing", SettingSource::Default)
+    };
+    ResolvedSetting {
+        name,
+        value: value.to_string(),
+        source,
+    }
+}
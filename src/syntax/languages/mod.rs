@@ -31,41 +31,228 @@ pub mod zig;
 use std::path::Path;
 use tree_sitter::Language;
 
+/// Tree-sitter grammar constructor and highlight query
+type Grammar = (fn() -> Language, &'static str);
+
+/// A language by the extensions its files use, and its grammar if it is highlighted
+struct LanguageDef {
+    name: &'static str,
+    extensions: &'static [&'static str],
+    grammar: Option<Grammar>,
+}
+
+const fn highlighted(
+    name: &'static str,
+    extensions: &'static [&'static str],
+    language: fn() -> Language,
+    query: &'static str,
+) -> LanguageDef {
+    LanguageDef {
+        name,
+        extensions,
+        grammar: Some((language, query)),
+    }
+}
+
+const fn plain(name: &'static str, extensions: &'static [&'static str]) -> LanguageDef {
+    LanguageDef {
+        name,
+        extensions,
+        grammar: None,
+    }
+}
+
+const LANGUAGES: &[LanguageDef] = &[
+    highlighted(
+        "Shell",
+        &["sh", "bash", "zsh"],
+        bash::language,
+        bash::HIGHLIGHT_QUERY,
+    ),
+    highlighted(
+        "C++",
+        &[
+            "cpp", "cc", "cxx", "c++", "C", "CPP", "hpp", "hh", "hxx", "h++", "H", "HPP", "tcc",
+            "inl",
+        ],
+        cpp::language,
+        cpp::HIGHLIGHT_QUERY,
+    ),
+    highlighted("C", &["c", "h"], c::language, c::HIGHLIGHT_QUERY),
+    highlighted(
+        "Clojure",
+        &["clj", "cljs", "cljc", "edn"],
+        clojure::language,
+        clojure::HIGHLIGHT_QUERY,
+    ),
+    highlighted(
+        "C#",
+        &["cs", "csx"],
+        csharp::language,
+        csharp::HIGHLIGHT_QUERY,
+    ),
+    highlighted("CSS", &["css"], css::language, css::HIGHLIGHT_QUERY),
+    highlighted(
+        "Sass",
+        &["scss", "sass"],
+        css::language,
+        css::HIGHLIGHT_QUERY,
+    ),
+    highlighted("Dart", &["dart"], dart::language, dart::HIGHLIGHT_QUERY),
+    highlighted(
+        "Elixir",
+        &["ex", "exs"],
+        elixir::language,
+        elixir::HIGHLIGHT_QUERY,
+    ),
+    highlighted(
+        "Erlang",
+        &["erl", "hrl", "es", "escript"],
+        erlang::language,
+        erlang::HIGHLIGHT_QUERY,
+    ),
+    highlighted("Go", &["go"], go_lang::language, go_lang::HIGHLIGHT_QUERY),
+    highlighted(
+        "Haskell",
+        &["hs", "lhs"],
+        haskell::language,
+        haskell::HIGHLIGHT_QUERY,
+    ),
+    highlighted(
+        "HTML",
+        &["html", "htm"],
+        html::language,
+        html::HIGHLIGHT_QUERY,
+    ),
+    highlighted("Java", &["java"], java::language, java::HIGHLIGHT_QUERY),
+    highlighted(
+        "JavaScript",
+        &["js", "jsx", "mjs", "cjs"],
+        javascript::language,
+        javascript::HIGHLIGHT_QUERY,
+    ),
+    highlighted(
+        "JSON",
+        &["json", "jsonc"],
+        json::language,
+        json::HIGHLIGHT_QUERY,
+    ),
+    highlighted(
+        "Kotlin",
+        &["kt", "kts"],
+        kotlin::language,
+        kotlin::HIGHLIGHT_QUERY,
+    ),
+    highlighted("Lua", &["lua"], lua::language, lua::HIGHLIGHT_QUERY),
+    highlighted(
+        "Markdown",
+        &["md", "markdown"],
+        markdown::language,
+        markdown::HIGHLIGHT_QUERY,
+    ),
+    highlighted(
+        "PHP",
+        &["php", "php3", "php4", "php5", "phtml"],
+        php::language,
+        php::HIGHLIGHT_QUERY,
+    ),
+    plain("Perl", &["pl", "pm"]),
+    highlighted(
+        "Python",
+        &["py", "pyw"],
+        python::language,
+        python::HIGHLIGHT_QUERY,
+    ),
+    highlighted(
+        "Ruby",
+        &["rb", "rbw", "rake", "gemspec"],
+        ruby::language,
+        ruby::HIGHLIGHT_QUERY,
+    ),
+    highlighted("Rust", &["rs"], rust::language, rust::HIGHLIGHT_QUERY),
+    highlighted(
+        "Scala",
+        &["scala", "sc", "sbt"],
+        scala::language,
+        scala::HIGHLIGHT_QUERY,
+    ),
+    plain("SQL", &["sql"]),
+    highlighted(
+        "Svelte",
+        &["svelte"],
+        svelte::language,
+        svelte::HIGHLIGHT_QUERY,
+    ),
+    highlighted("Swift", &["swift"], swift::language, swift::HIGHLIGHT_QUERY),
+    plain("TOML", &["toml"]),
+    highlighted(
+        "TypeScript",
+        &["ts", "tsx", "mts", "cts"],
+        typescript::language,
+        typescript::HIGHLIGHT_QUERY,
+    ),
+    plain("Vue", &["vue"]),
+    highlighted(
+        "XML",
+        &["xml", "svg", "xsl", "xslt"],
+        xml::language,
+        xml::HIGHLIGHT_QUERY,
+    ),
+    highlighted(
+        "YAML",
+        &["yaml", "yml"],
+        yaml::language,
+        yaml::HIGHLIGHT_QUERY,
+    ),
+    highlighted("Zig", &["zig"], zig::language, zig::HIGHLIGHT_QUERY),
+];
+
+fn language_for_extension(extension: &str) -> Option<&'static LanguageDef> {
+    LANGUAGES
+        .iter()
+        .find(|language| language.extensions.contains(&extension))
+}
+
 pub fn get_language(path: &Path) -> Option<(Language, &'static str)> {
     let extension = path.extension()?.to_str()?;
+    let (language, query) = language_for_extension(extension)?.grammar?;
+    Some((language(), query))
+}
+
+/// Name of the language a file is written in, from its extension or, for
+/// scripts without one, the interpreter on its shebang line
+pub fn detect_language(path: &str, content: Option<&str>) -> Option<&'static str> {
+    Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .and_then(language_for_extension)
+        .map(|language| language.name)
+        .or_else(|| {
+            content
+                .and_then(|content| content.lines().next())
+                .and_then(language_for_shebang)
+        })
+}
 
-    match extension {
-        "sh" | "bash" | "zsh" => Some((bash::language(), bash::HIGHLIGHT_QUERY)),
-        // C++ before C to handle .h files (can be either)
-        "cpp" | "cc" | "cxx" | "c++" | "C" | "CPP" | "hpp" | "hh" | "hxx" | "h++" | "H" | "HPP"
-        | "tcc" | "inl" => Some((cpp::language(), cpp::HIGHLIGHT_QUERY)),
-        "c" | "h" => Some((c::language(), c::HIGHLIGHT_QUERY)),
-        "clj" | "cljs" | "cljc" | "edn" => Some((clojure::language(), clojure::HIGHLIGHT_QUERY)),
-        "cs" | "csx" => Some((csharp::language(), csharp::HIGHLIGHT_QUERY)),
-        "css" | "scss" | "sass" => Some((css::language(), css::HIGHLIGHT_QUERY)),
-        "dart" => Some((dart::language(), dart::HIGHLIGHT_QUERY)),
-        "ex" | "exs" => Some((elixir::language(), elixir::HIGHLIGHT_QUERY)),
-        "erl" | "hrl" | "es" | "escript" => Some((erlang::language(), erlang::HIGHLIGHT_QUERY)),
-        "go" => Some((go_lang::language(), go_lang::HIGHLIGHT_QUERY)),
-        "hs" | "lhs" => Some((haskell::language(), haskell::HIGHLIGHT_QUERY)),
-        "html" | "htm" => Some((html::language(), html::HIGHLIGHT_QUERY)),
-        "java" => Some((java::language(), java::HIGHLIGHT_QUERY)),
-        "js" | "jsx" | "mjs" | "cjs" => Some((javascript::language(), javascript::HIGHLIGHT_QUERY)),
-        "json" | "jsonc" => Some((json::language(), json::HIGHLIGHT_QUERY)),
-        "kt" | "kts" => Some((kotlin::language(), kotlin::HIGHLIGHT_QUERY)),
-        "lua" => Some((lua::language(), lua::HIGHLIGHT_QUERY)),
-        "md" | "markdown" => Some((markdown::language(), markdown::HIGHLIGHT_QUERY)),
-        "php" | "php3" | "php4" | "php5" | "phtml" => Some((php::language(), php::HIGHLIGHT_QUERY)),
-        "py" | "pyw" => Some((python::language(), python::HIGHLIGHT_QUERY)),
-        "rb" | "rbw" | "rake" | "gemspec" => Some((ruby::language(), ruby::HIGHLIGHT_QUERY)),
-        "rs" => Some((rust::language(), rust::HIGHLIGHT_QUERY)),
-        "scala" | "sc" | "sbt" => Some((scala::language(), scala::HIGHLIGHT_QUERY)),
-        "svelte" => Some((svelte::language(), svelte::HIGHLIGHT_QUERY)),
-        "swift" => Some((swift::language(), swift::HIGHLIGHT_QUERY)),
-        "ts" | "tsx" | "mts" | "cts" => Some((typescript::language(), typescript::HIGHLIGHT_QUERY)),
-        "xml" | "svg" | "xsl" | "xslt" => Some((xml::language(), xml::HIGHLIGHT_QUERY)),
-        "yaml" | "yml" => Some((yaml::language(), yaml::HIGHLIGHT_QUERY)),
-        "zig" => Some((zig::language(), zig::HIGHLIGHT_QUERY)),
-        _ => None,
+/// Language of a `#!/usr/bin/env python3` style line, by the interpreter it names
+fn language_for_shebang(line: &str) -> Option<&'static str> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = words.find(|word| !word.starts_with('-'))?;
     }
+    // python3.12 and python both run Python
+    let name = match program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.') {
+        "sh" | "bash" | "zsh" | "dash" | "ksh" => "Shell",
+        "python" => "Python",
+        "node" | "deno" | "bun" => "JavaScript",
+        "ruby" => "Ruby",
+        "perl" => "Perl",
+        "php" => "PHP",
+        "lua" => "Lua",
+        "elixir" => "Elixir",
+        "escript" => "Erlang",
+        _ => return None,
+    };
+    Some(name)
 }
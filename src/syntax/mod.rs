@@ -6,7 +6,7 @@ use std::path::Path;
 use streaming_iterator::StreamingIterator;
 use tree_sitter::{Language, Parser, Query, QueryCursor};
 
-pub use languages::get_language;
+pub use languages::{detect_language, get_language};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TokenType {
@@ -52,6 +52,51 @@ pub struct HighlightSpan {
     pub token_type: TokenType,
 }
 
+/// Highlight spans grouped by line, so rendering a line doesn't scan the whole
+/// file. A span crossing lines (e.g. a block comment) is listed on each of them.
+#[derive(Debug, Clone, Default)]
+pub struct LineHighlights {
+    spans: Vec<HighlightSpan>,
+    // Spans of line `i` are `spans[line_starts[i]..line_starts[i + 1]]`
+    line_starts: Vec<usize>,
+}
+
+impl LineHighlights {
+    /// Group `spans` by the lines starting at the byte offsets in `line_offsets`.
+    pub fn new(spans: &[HighlightSpan], line_offsets: &[usize]) -> Self {
+        let mut by_line: Vec<Vec<HighlightSpan>> = vec![Vec::new(); line_offsets.len()];
+        for span in spans {
+            let first = line_offsets
+                .partition_point(|&offset| offset <= span.start)
+                .saturating_sub(1);
+            for (line, &offset) in line_offsets.iter().enumerate().skip(first) {
+                if offset >= span.end && line > first {
+                    break;
+                }
+                by_line[line].push(span.clone());
+            }
+        }
+
+        let mut line_starts = Vec::with_capacity(by_line.len() + 1);
+        line_starts.push(0);
+        for line in &by_line {
+            line_starts.push(line_starts.last().unwrap() + line.len());
+        }
+        Self {
+            spans: by_line.into_iter().flatten().collect(),
+            line_starts,
+        }
+    }
+
+    /// Spans touching `line`, in the order they were highlighted
+    pub fn line(&self, line: usize) -> &[HighlightSpan] {
+        match (self.line_starts.get(line), self.line_starts.get(line + 1)) {
+            (Some(&start), Some(&end)) => &self.spans[start..end],
+            _ => &[],
+        }
+    }
+}
+
 pub struct Highlighter {
     parser: Parser,
     language: Option<Language>,
@@ -209,3 +254,63 @@ impl Default for Highlighter {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: usize, end: usize, token_type: TokenType) -> HighlightSpan {
+        HighlightSpan {
+            start,
+            end,
+            token_type,
+        }
+    }
+
+    #[test]
+    fn spans_are_grouped_by_the_lines_they_touch() {
+        // "let a;\n/* x\ny */\nb\n"
+        let offsets = [0, 7, 12, 17, 19];
+        let spans = [
+            span(0, 3, TokenType::Keyword),
+            span(7, 16, TokenType::Comment),
+            span(17, 18, TokenType::Variable),
+        ];
+        let lines = LineHighlights::new(&spans, &offsets);
+
+        assert_eq!(lines.line(0).len(), 1);
+        assert_eq!(lines.line(1)[0].token_type, TokenType::Comment);
+        assert_eq!(lines.line(2)[0].token_type, TokenType::Comment);
+        assert_eq!(lines.line(3)[0].token_type, TokenType::Variable);
+        assert!(lines.line(4).is_empty());
+        assert!(lines.line(9).is_empty());
+    }
+
+    #[test]
+    fn language_comes_from_extension_then_shebang() {
+        assert_eq!(detect_language("src/main.rs", None), Some("Rust"));
+        assert_eq!(detect_language("include/vec.hpp", None), Some("C++"));
+        assert_eq!(
+            detect_language("bin/deploy", Some("#!/usr/bin/env python3.12\nimport os\n")),
+            Some("Python")
+        );
+        assert_eq!(
+            detect_language("scripts/setup", Some("#!/bin/bash -e\n")),
+            Some("Shell")
+        );
+        // The extension wins over a shebang
+        assert_eq!(
+            detect_language("tool.rb", Some("#!/usr/bin/env python\n")),
+            Some("Ruby")
+        );
+        assert_eq!(detect_language("LICENSE", Some("MIT License\n")), None);
+    }
+
+    #[test]
+    fn named_languages_are_not_all_highlighted() {
+        assert_eq!(detect_language("styles/site.scss", None), Some("Sass"));
+        assert!(get_language(Path::new("styles/site.scss")).is_some());
+        assert_eq!(detect_language("Cargo.toml", None), Some("TOML"));
+        assert!(get_language(Path::new("Cargo.toml")).is_none());
+    }
+}
@@ -2,6 +2,8 @@ mod themes;
 
 use anyhow::{Context, Result};
 use ratatui::style::Color;
+use std::path::Path;
+use std::str::FromStr;
 
 #[derive(Debug, Clone)]
 pub struct Theme {
@@ -16,6 +18,8 @@ pub struct Theme {
     pub editor_cursor_char_bg: Color,
     pub editor_cursor_char_fg: Color,
     pub editor_cursor_line_bg: Color,
+    pub editor_added_line_bg: Color,
+    pub editor_word_diff_bg: Color,
 
     // File tree colors
     pub file_tree_added: Color,
@@ -61,6 +65,72 @@ pub struct Theme {
     pub syntax_label: Color,
 }
 
+/// Generates name-based access to the color fields, used when loading and exporting theme files.
+macro_rules! color_fields {
+    ($($field:ident),* $(,)?) => {
+        impl Theme {
+            const COLOR_FIELDS: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            fn color_mut(&mut self, field: &str) -> Option<&mut Color> {
+                match field {
+                    $(stringify!($field) => Some(&mut self.$field),)*
+                    _ => None,
+                }
+            }
+
+            fn colors(&self) -> Vec<(&'static str, Color)> {
+                vec![$((stringify!($field), self.$field)),*]
+            }
+        }
+    };
+}
+
+color_fields!(
+    background_left,
+    background_right,
+    editor_line_number,
+    editor_line_number_cursor,
+    editor_separator,
+    editor_cursor_char_bg,
+    editor_cursor_char_fg,
+    editor_cursor_line_bg,
+    editor_added_line_bg,
+    editor_word_diff_bg,
+    file_tree_added,
+    file_tree_deleted,
+    file_tree_modified,
+    file_tree_renamed,
+    file_tree_directory,
+    file_tree_current_file_bg,
+    file_tree_current_file_fg,
+    file_tree_default,
+    file_tree_stats_added,
+    file_tree_stats_deleted,
+    terminal_command,
+    terminal_output,
+    terminal_cursor_bg,
+    terminal_cursor_fg,
+    status_hash,
+    status_author,
+    status_date,
+    status_message,
+    status_no_commit,
+    separator,
+    syntax_keyword,
+    syntax_type,
+    syntax_function,
+    syntax_variable,
+    syntax_string,
+    syntax_number,
+    syntax_comment,
+    syntax_operator,
+    syntax_punctuation,
+    syntax_constant,
+    syntax_parameter,
+    syntax_property,
+    syntax_label,
+);
+
 impl Default for Theme {
     fn default() -> Self {
         themes::tokyo_night()
@@ -93,10 +163,136 @@ impl Theme {
         }
     }
 
+    /// Load a custom theme from a TOML or JSON file (JSON when the extension is `.json`).
+    ///
+    /// Keys are `Theme` field names mapped to colors (`"#1a1b26"`, `"blue"`, or a
+    /// 256-color index). An optional `base` key names the built-in theme that supplies
+    /// any colors the file leaves out (default: tokyo-night).
+    pub fn load_from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read theme file: {}", path.display()))?;
+
+        let is_json = path.extension().and_then(|e| e.to_str()) == Some("json");
+        let entries: Vec<(String, Option<String>)> = if is_json {
+            let map: serde_json::Map<String, serde_json::Value> =
+                serde_json::from_str(&contents)
+                    .with_context(|| format!("Failed to parse theme file: {}", path.display()))?;
+            map.into_iter()
+                .map(|(k, v)| (k, v.as_str().map(String::from)))
+                .collect()
+        } else {
+            let table: toml::Table = toml::from_str(&contents)
+                .with_context(|| format!("Failed to parse theme file: {}", path.display()))?;
+            table
+                .into_iter()
+                .map(|(k, v)| (k, v.as_str().map(String::from)))
+                .collect()
+        };
+
+        Self::from_entries(entries)
+            .with_context(|| format!("Invalid theme file: {}", path.display()))
+    }
+
+    fn from_entries(entries: Vec<(String, Option<String>)>) -> Result<Self> {
+        let base = entries
+            .iter()
+            .find(|(key, _)| key == "base")
+            .map(|(_, value)| value.as_deref().context("'base' must be a theme name"))
+            .transpose()?;
+        let mut theme = match base {
+            Some(name) => Self::load(name)?,
+            None => Self::default(),
+        };
+
+        for (key, value) in &entries {
+            if key == "base" {
+                continue;
+            }
+            let Some(slot) = theme.color_mut(key) else {
+                anyhow::bail!(
+                    "Unknown theme field '{}'. Valid fields: base, {}",
+                    key,
+                    Self::COLOR_FIELDS.join(", ")
+                );
+            };
+            let value = value
+                .as_deref()
+                .with_context(|| format!("Theme field '{}' must be a color string", key))?;
+            *slot = Color::from_str(value).map_err(|_| {
+                anyhow::anyhow!(
+                    "Invalid color '{}' for theme field '{}' (expected e.g. \"#1a1b26\", \"blue\", or \"42\")",
+                    value,
+                    key
+                )
+            })?;
+        }
+
+        Ok(theme)
+    }
+
+    /// Serialize every color as a TOML theme file that `load_from_file` reads back.
+    /// RGB colors are written as hex strings.
+    pub fn to_toml(&self) -> String {
+        self.colors()
+            .into_iter()
+            .map(|(field, color)| {
+                let value = match color {
+                    Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+                    other => other.to_string(),
+                };
+                format!("{} = \"{}\"\n", field, value)
+            })
+            .collect()
+    }
+
     /// Remove background colors for transparent terminal background
-    pub fn with_transparent_background(mut self) -> Self {
-        self.background_left = Color::Reset;
-        self.background_right = Color::Reset;
+    pub fn with_transparent_background(self) -> Self {
+        self.with_transparent_sides(true, true)
+    }
+
+    /// Remove the background of either side of the layout: the left holds the
+    /// file tree and commit info, the right the editor and terminal.
+    pub fn with_transparent_sides(mut self, left: bool, right: bool) -> Self {
+        if left {
+            self.background_left = Color::Reset;
+        }
+        if right {
+            self.background_right = Color::Reset;
+        }
+        self
+    }
+
+    /// Reset every color to the terminal's default, leaving an unstyled palette
+    /// for `--no-color` and `NO_COLOR`.
+    pub fn monochrome(mut self) -> Self {
+        for field in Self::COLOR_FIELDS {
+            if let Some(color) = self.color_mut(field) {
+                *color = Color::Reset;
+            }
+        }
+        self
+    }
+
+    /// Map RGB colors to the nearest of the 240 extended colors of a 256-color
+    /// terminal, leaving out the 16 ANSI colors the terminal's own scheme redefines.
+    pub fn with_256_colors(self) -> Self {
+        self.map_rgb(|rgb| Color::Indexed(nearest_indexed(rgb, 16..=255)))
+    }
+
+    /// Map RGB colors to the nearest of the 16 ANSI colors, for terminals that
+    /// have nothing more.
+    pub fn with_16_colors(self) -> Self {
+        self.map_rgb(|rgb| ANSI_COLORS[nearest_indexed(rgb, 0..=15) as usize])
+    }
+
+    fn map_rgb(mut self, map: impl Fn([u8; 3]) -> Color) -> Self {
+        for field in Self::COLOR_FIELDS {
+            if let Some(color) = self.color_mut(field) {
+                if let Color::Rgb(r, g, b) = *color {
+                    *color = map([r, g, b]);
+                }
+            }
+        }
         self
     }
 
@@ -123,3 +319,157 @@ impl Theme {
         ]
     }
 }
+
+/// The 16 ANSI colors in palette order
+const ANSI_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+/// RGB value of a 256-color palette index, using xterm's default palette.
+pub fn indexed_rgb(i: u8) -> [u8; 3] {
+    const ANSI: [[u8; 3]; 16] = [
+        [0, 0, 0],
+        [205, 0, 0],
+        [0, 205, 0],
+        [205, 205, 0],
+        [0, 0, 238],
+        [205, 0, 205],
+        [0, 205, 205],
+        [229, 229, 229],
+        [127, 127, 127],
+        [255, 0, 0],
+        [0, 255, 0],
+        [255, 255, 0],
+        [92, 92, 255],
+        [255, 0, 255],
+        [0, 255, 255],
+        [255, 255, 255],
+    ];
+    match i {
+        0..=15 => ANSI[i as usize],
+        16..=231 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let v = i - 16;
+            [level(v / 36), level(v / 6 % 6), level(v % 6)]
+        }
+        _ => {
+            let gray = 8 + (i - 232) * 10;
+            [gray, gray, gray]
+        }
+    }
+}
+
+/// Palette index within `range` closest to `rgb`.
+fn nearest_indexed(rgb: [u8; 3], range: std::ops::RangeInclusive<u8>) -> u8 {
+    let distance = |i: u8| {
+        indexed_rgb(i)
+            .iter()
+            .zip(rgb)
+            .map(|(&a, b)| (a as i32 - b as i32).pow(2))
+            .sum::<i32>()
+    };
+    range.min_by_key(|&i| distance(i)).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, value: &str) -> (String, Option<String>) {
+        (key.to_string(), Some(value.to_string()))
+    }
+
+    #[test]
+    fn theme_entries_override_base_colors() {
+        let theme = Theme::from_entries(vec![
+            entry("base", "dracula"),
+            entry("separator", "#010203"),
+        ])
+        .unwrap();
+        assert_eq!(theme.separator, Color::Rgb(1, 2, 3));
+        assert_eq!(
+            theme.background_left,
+            Theme::load("dracula").unwrap().background_left
+        );
+    }
+
+    #[test]
+    fn exported_theme_loads_back_unchanged() {
+        let theme = Theme::load("nord").unwrap();
+        let table: toml::Table = toml::from_str(&theme.to_toml()).unwrap();
+        assert_eq!(table.len(), Theme::COLOR_FIELDS.len());
+        assert_eq!(table["background_left"].as_str(), Some("#242a38"));
+
+        let entries = table
+            .into_iter()
+            .map(|(k, v)| (k, v.as_str().map(String::from)))
+            .collect();
+        let loaded = Theme::from_entries(entries).unwrap();
+        assert_eq!(loaded.colors(), theme.colors());
+    }
+
+    #[test]
+    fn transparency_can_clear_one_side() {
+        let theme = Theme::default().with_transparent_sides(false, true);
+        assert_eq!(theme.background_right, Color::Reset);
+        assert_eq!(theme.background_left, Theme::default().background_left);
+    }
+
+    #[test]
+    fn limited_palettes_pick_the_nearest_color() {
+        let theme = Theme::from_entries(vec![
+            entry("separator", "#ff0000"),
+            entry("file_tree_added", "#7f7f80"),
+        ])
+        .unwrap();
+        let indexed = theme.clone().with_256_colors();
+        assert_eq!(indexed.separator, Color::Indexed(196));
+        assert_eq!(indexed.file_tree_added, Color::Indexed(244));
+        assert!(indexed
+            .colors()
+            .iter()
+            .all(|(_, color)| !matches!(color, Color::Rgb(..))));
+
+        let ansi = theme.with_16_colors();
+        assert_eq!(ansi.separator, Color::LightRed);
+        assert_eq!(ansi.file_tree_added, Color::DarkGray);
+    }
+
+    #[test]
+    fn monochrome_resets_every_color() {
+        let theme = Theme::load("dracula").unwrap().monochrome();
+        assert!(theme
+            .colors()
+            .iter()
+            .all(|(_, color)| *color == Color::Reset));
+    }
+
+    #[test]
+    fn invalid_color_names_the_field() {
+        let err = Theme::from_entries(vec![entry("editor_cursor_line_bg", "#zzzzzz")])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("editor_cursor_line_bg"));
+        assert!(err.contains("#zzzzzz"));
+
+        let err = Theme::from_entries(vec![entry("sepparator", "red")])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown theme field 'sepparator'"));
+    }
+}
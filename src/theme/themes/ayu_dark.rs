@@ -13,6 +13,8 @@ pub fn ayu_dark() -> Theme {
         editor_cursor_char_bg: Color::Rgb(255, 180, 84),
         editor_cursor_char_fg: Color::Rgb(15, 20, 25),
         editor_cursor_line_bg: Color::Rgb(22, 29, 37),
+        editor_added_line_bg: Color::Rgb(45, 57, 42),
+        editor_word_diff_bg: Color::Rgb(75, 94, 60),
 
         file_tree_added: Color::Rgb(186, 230, 126),
         file_tree_deleted: Color::Rgb(242, 97, 103),
@@ -13,6 +13,8 @@ pub fn catppuccin() -> Theme {
         editor_cursor_char_bg: Color::Rgb(245, 194, 231),
         editor_cursor_char_fg: Color::Rgb(30, 30, 46),
         editor_cursor_line_bg: Color::Rgb(49, 50, 68),
+        editor_added_line_bg: Color::Rgb(54, 64, 66),
+        editor_word_diff_bg: Color::Rgb(78, 99, 86),
 
         file_tree_added: Color::Rgb(166, 227, 161),
         file_tree_deleted: Color::Rgb(243, 139, 168),
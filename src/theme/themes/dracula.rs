@@ -13,6 +13,8 @@ pub fn dracula() -> Theme {
         editor_cursor_char_bg: Color::Rgb(255, 121, 198),
         editor_cursor_char_fg: Color::Rgb(40, 42, 54),
         editor_cursor_line_bg: Color::Rgb(68, 71, 90),
+        editor_added_line_bg: Color::Rgb(47, 78, 66),
+        editor_word_diff_bg: Color::Rgb(54, 115, 78),
 
         file_tree_added: Color::Rgb(80, 250, 123),
         file_tree_deleted: Color::Rgb(255, 85, 85),
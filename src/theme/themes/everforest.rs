@@ -13,6 +13,8 @@ pub fn everforest() -> Theme {
         editor_cursor_char_bg: Color::Rgb(131, 192, 146),
         editor_cursor_char_fg: Color::Rgb(45, 52, 46),
         editor_cursor_line_bg: Color::Rgb(57, 64, 58),
+        editor_added_line_bg: Color::Rgb(60, 76, 63),
+        editor_word_diff_bg: Color::Rgb(75, 101, 81),
 
         file_tree_added: Color::Rgb(131, 192, 146),
         file_tree_deleted: Color::Rgb(230, 126, 128),
@@ -15,6 +15,8 @@ pub fn fluorite() -> Theme {
         editor_cursor_char_bg: Color::Rgb(163, 109, 207),
         editor_cursor_char_fg: Color::Rgb(22, 17, 27),
         editor_cursor_line_bg: Color::Rgb(25, 17, 31),
+        editor_added_line_bg: Color::Rgb(40, 46, 46),
+        editor_word_diff_bg: Color::Rgb(59, 75, 66),
 
         file_tree_added: Color::Rgb(129, 184, 139),
         file_tree_deleted: Color::Rgb(91, 60, 117),
@@ -13,6 +13,8 @@ pub fn github_dark() -> Theme {
         editor_cursor_char_bg: Color::Rgb(88, 166, 255),
         editor_cursor_char_fg: Color::Rgb(22, 27, 34),
         editor_cursor_line_bg: Color::Rgb(33, 38, 45),
+        editor_added_line_bg: Color::Rgb(29, 54, 42),
+        editor_word_diff_bg: Color::Rgb(36, 82, 50),
 
         file_tree_added: Color::Rgb(63, 185, 80),
         file_tree_deleted: Color::Rgb(248, 81, 73),
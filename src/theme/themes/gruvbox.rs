@@ -13,6 +13,8 @@ pub fn gruvbox() -> Theme {
         editor_cursor_char_bg: Color::Rgb(254, 128, 25),
         editor_cursor_char_fg: Color::Rgb(40, 40, 40),
         editor_cursor_line_bg: Color::Rgb(60, 56, 54),
+        editor_added_line_bg: Color::Rgb(65, 65, 39),
+        editor_word_diff_bg: Color::Rgb(90, 91, 39),
 
         file_tree_added: Color::Rgb(184, 187, 38),
         file_tree_deleted: Color::Rgb(251, 73, 52),
@@ -13,6 +13,8 @@ pub fn material() -> Theme {
         editor_cursor_char_bg: Color::Rgb(255, 203, 107),
         editor_cursor_char_fg: Color::Rgb(38, 50, 56),
         editor_cursor_line_bg: Color::Rgb(55, 71, 79),
+        editor_added_line_bg: Color::Rgb(65, 82, 71),
+        editor_word_diff_bg: Color::Rgb(93, 114, 86),
 
         file_tree_added: Color::Rgb(195, 232, 141),
         file_tree_deleted: Color::Rgb(255, 83, 112),
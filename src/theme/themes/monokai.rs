@@ -13,6 +13,8 @@ pub fn monokai() -> Theme {
         editor_cursor_char_bg: Color::Rgb(253, 151, 31),
         editor_cursor_char_fg: Color::Rgb(39, 40, 34),
         editor_cursor_line_bg: Color::Rgb(51, 51, 45),
+        editor_added_line_bg: Color::Rgb(61, 72, 36),
+        editor_word_diff_bg: Color::Rgb(83, 105, 38),
 
         file_tree_added: Color::Rgb(166, 226, 46),
         file_tree_deleted: Color::Rgb(249, 38, 114),
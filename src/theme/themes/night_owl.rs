@@ -13,6 +13,8 @@ pub fn night_owl() -> Theme {
         editor_cursor_char_bg: Color::Rgb(122, 162, 247),
         editor_cursor_char_fg: Color::Rgb(1, 22, 39),
         editor_cursor_line_bg: Color::Rgb(1, 41, 72),
+        editor_added_line_bg: Color::Rgb(31, 56, 50),
+        editor_word_diff_bg: Color::Rgb(61, 91, 61),
 
         file_tree_added: Color::Rgb(173, 219, 103),
         file_tree_deleted: Color::Rgb(239, 83, 80),
@@ -13,6 +13,8 @@ pub fn nord() -> Theme {
         editor_cursor_char_bg: Color::Rgb(136, 192, 208),
         editor_cursor_char_fg: Color::Rgb(46, 52, 64),
         editor_cursor_line_bg: Color::Rgb(59, 66, 82),
+        editor_added_line_bg: Color::Rgb(66, 76, 77),
+        editor_word_diff_bg: Color::Rgb(87, 100, 91),
 
         file_tree_added: Color::Rgb(163, 190, 140),
         file_tree_deleted: Color::Rgb(191, 97, 106),
@@ -13,6 +13,8 @@ pub fn one_dark() -> Theme {
         editor_cursor_char_bg: Color::Rgb(97, 175, 239),
         editor_cursor_char_fg: Color::Rgb(40, 44, 52),
         editor_cursor_line_bg: Color::Rgb(47, 52, 61),
+        editor_added_line_bg: Color::Rgb(59, 70, 64),
+        editor_word_diff_bg: Color::Rgb(79, 97, 76),
 
         file_tree_added: Color::Rgb(152, 195, 121),
         file_tree_deleted: Color::Rgb(224, 108, 117),
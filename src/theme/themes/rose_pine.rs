@@ -13,6 +13,8 @@ pub fn rose_pine() -> Theme {
         editor_cursor_char_bg: Color::Rgb(235, 188, 186),
         editor_cursor_char_fg: Color::Rgb(35, 33, 54),
         editor_cursor_line_bg: Color::Rgb(42, 39, 63),
+        editor_added_line_bg: Color::Rgb(56, 63, 82),
+        editor_word_diff_bg: Color::Rgb(77, 94, 111),
 
         file_tree_added: Color::Rgb(156, 207, 216),
         file_tree_deleted: Color::Rgb(235, 111, 146),
@@ -13,6 +13,8 @@ pub fn solarized_dark() -> Theme {
         editor_cursor_char_bg: Color::Rgb(38, 139, 210),
         editor_cursor_char_fg: Color::Rgb(0, 43, 54),
         editor_cursor_line_bg: Color::Rgb(7, 54, 66),
+        editor_added_line_bg: Color::Rgb(23, 62, 44),
+        editor_word_diff_bg: Color::Rgb(47, 82, 35),
 
         file_tree_added: Color::Rgb(133, 153, 0),
         file_tree_deleted: Color::Rgb(220, 50, 47),
@@ -13,6 +13,8 @@ pub fn solarized_light() -> Theme {
         editor_cursor_char_bg: Color::Rgb(38, 139, 210),
         editor_cursor_char_fg: Color::Rgb(253, 246, 227),
         editor_cursor_line_bg: Color::Rgb(238, 232, 213),
+        editor_added_line_bg: Color::Rgb(232, 229, 187),
+        editor_word_diff_bg: Color::Rgb(211, 213, 148),
 
         file_tree_added: Color::Rgb(133, 153, 0),
         file_tree_deleted: Color::Rgb(220, 50, 47),
@@ -14,6 +14,8 @@ pub fn telemetry() -> Theme {
         editor_cursor_char_bg: Color::Rgb(244, 174, 89),
         editor_cursor_char_fg: Color::Rgb(15, 27, 29),
         editor_cursor_line_bg: Color::Rgb(29, 46, 49),
+        editor_added_line_bg: Color::Rgb(43, 58, 54),
+        editor_word_diff_bg: Color::Rgb(64, 82, 72),
 
         file_tree_added: Color::Rgb(141, 172, 139),
         file_tree_deleted: Color::Rgb(194, 113, 102),
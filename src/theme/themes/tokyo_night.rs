@@ -13,6 +13,8 @@ pub fn tokyo_night() -> Theme {
         editor_cursor_char_bg: Color::Rgb(122, 162, 247),
         editor_cursor_char_fg: Color::Rgb(26, 27, 38),
         editor_cursor_line_bg: Color::Rgb(42, 47, 68),
+        editor_added_line_bg: Color::Rgb(49, 58, 50),
+        editor_word_diff_bg: Color::Rgb(72, 90, 62),
 
         file_tree_added: Color::Rgb(158, 206, 106),
         file_tree_deleted: Color::Rgb(247, 118, 142),
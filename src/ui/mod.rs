@@ -1,7 +1,10 @@
 mod playback;
+mod preview;
 mod rendering;
+mod stats;
 
-use std::io;
+use std::io::{self, Write};
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
@@ -12,21 +15,83 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Rect, Size},
+    Terminal,
+};
 
 use crate::animation::{AnimationEngine, SpeedRule};
-use crate::audio::AudioPlayer;
-use crate::git::{CommitMetadata, DiffMode, GitRepository};
-use crate::panes::{EditorPane, FileTreePane, StatusBarPane, TerminalPane};
+use crate::audio::{AudioPlayer, GenerationProgress};
+use crate::cli::{CursorStyle, FileOrder};
+use crate::config::{EditPauses, ResolvedSetting};
+use crate::export::{
+    self, CastRecorder, EventWriter, GifOptions, GifRecorder, NarrationTrackWriter,
+    RecordingWriter, SrtWriter,
+};
+use crate::git::{CommitMetadata, DiffMode, GitRepository, RangeChapter};
+use crate::panes::{
+    CaptionPane, EditorPane, FileTreePane, MinimapPane, StatusBarPane, TerminalPane,
+    CAPTION_HEIGHT, MINIMAP_WIDTH,
+};
+use crate::resume::{self, ResumeState};
 use crate::theme::Theme;
+use crate::watch::WorkingTreeWatch;
 use crate::PlaybackOrder;
 
+type TuiTerminal = Terminal<CrosstermBackend<RecordingWriter<Box<dyn Write>>>>;
+
+// Set when `--events -` claims stdout; the interface is then drawn on stderr
+static TUI_ON_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Stream the interface is drawn on.
+fn tui_output() -> Box<dyn Write> {
+    if TUI_ON_STDERR.load(Ordering::SeqCst) {
+        Box::new(io::stderr())
+    } else {
+        Box::new(io::stdout())
+    }
+}
+
+// Share of the commit skipped per arrow-key seek
+const SEEK_FRACTION: f32 = 0.05;
+
+// Narration volume change per +/- key press
+const VOLUME_STEP: f32 = 0.1;
+
+// How long a status notice (e.g. clipboard copy) stays in the commit info pane
+const NOTICE_DURATION: Duration = Duration::from_secs(2);
+
+// Editor lines scrolled per mouse wheel notch
+const WHEEL_SCROLL_LINES: isize = 3;
+
+// Redraw rate while nothing is typing (paused, dialogs, between commits)
+const IDLE_FPS: u32 = 5;
+
+// Input poll interval while typing, matching the engine's frame rate
+const ACTIVE_POLL: Duration = Duration::from_millis(8);
+
+// How often `diff --watch` checks for saves once the last one has played
+const WATCH_POLL: Duration = Duration::from_millis(100);
+
+/// Minimum time between redraws: the `--fps` cap, lowered to `IDLE_FPS` when idle.
+fn redraw_interval(max_fps: Option<u32>, idle: bool) -> Duration {
+    let fps = match (max_fps, idle) {
+        (Some(fps), true) => fps.min(IDLE_FPS),
+        (None, true) => IDLE_FPS,
+        (Some(fps), false) => fps,
+        (None, false) => return Duration::ZERO,
+    };
+    Duration::from_secs_f64(1.0 / fps.max(1) as f64)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 enum UIState {
     Playing,
     WaitingForNext { resume_at: Instant },
     GeneratingAudio,
     Menu,
+    Chapters,
     KeyBindings,
     About,
     Finished,
@@ -42,8 +107,19 @@ enum PlaybackState {
 pub struct UI<'a> {
     state: UIState,
     speed_ms: u64,
+    // Fixed wait between commits in milliseconds (None scales with typing speed)
+    commit_gap_ms: Option<u64>,
     file_tree: FileTreePane,
     editor: EditorPane,
+    minimap: Option<MinimapPane>,
+    captions: Option<CaptionPane>,
+    // Editor takes the whole screen, side panes and terminal hidden
+    fullscreen: bool,
+    // Commit stats overlay drawn over the panes while playing
+    show_stats: bool,
+    show_hints: bool,
+    // Redraw cap from --fps; ticks in between are coalesced into the next frame
+    max_fps: Option<u32>,
     terminal: TerminalPane,
     status_bar: StatusBarPane,
     engine: AnimationEngine,
@@ -53,17 +129,66 @@ pub struct UI<'a> {
     order: PlaybackOrder,
     loop_playback: bool,
     commit_spec: Option<String>,
+    compare_spec: Option<String>,
     is_range_mode: bool,
+    // Filters such as --author keep playing past a single --commit
+    is_filtered: bool,
+    show_deletions: bool,
+    only_additions: bool,
+    collapse_threshold: Option<usize>,
+    stat_mode: bool,
+    line_pause_scale: f64,
+    pauses: EditPauses,
+    file_order: FileOrder,
     diff_mode: Option<DiffMode>,
+    // Working tree watched by `diff --watch`, played each time files are saved
+    watch: Option<WorkingTreeWatch>,
     playback_state: PlaybackState,
+    // Pause length without input after which playback resumes by itself
+    auto_resume: Option<Duration>,
     history: Vec<CommitMetadata>,
     history_index: Option<usize>,
     menu_index: usize,
+    // Commits of the range, listed by the chapters overlay
+    chapters: Vec<RangeChapter>,
+    chapter_index: usize,
     prev_state: Option<Box<UIState>>,
     audio_player: Option<Arc<AudioPlayer>>,
     audio_gen_handle: Option<std::thread::JoinHandle<()>>,
+    audio_gen_cancel: Arc<AtomicBool>,
+    // Play while narration is generated rather than waiting for all of it
+    voiceover_wait: bool,
+    // Chunks the engine's steps were last built with, while generating in the background
+    narrated_chunks: usize,
     pending_metadata: Option<CommitMetadata>,
-    audio_progress: Arc<Mutex<(String, f32)>>, // (status message, progress 0.0-1.0)
+    audio_progress: Arc<Mutex<GenerationProgress>>,
+    record_path: Option<PathBuf>,
+    events_path: Option<PathBuf>,
+    events: Option<EventWriter>,
+    gif_path: Option<PathBuf>,
+    gif_options: GifOptions,
+    gif: Option<GifRecorder>,
+    subtitles_path: Option<PathBuf>,
+    subtitles: Option<SrtWriter>,
+    audio_out_path: Option<PathBuf>,
+    audio_out: Option<NarrationTrackWriter>,
+    resume_path: Option<PathBuf>,
+    // Position to seek to once the loaded commit starts playing
+    pending_seek: Option<f32>,
+    // File to jump to once the loaded commit starts playing
+    pending_start_file: Option<String>,
+    // Transient message shown under the progress bar until it expires
+    notice: Option<(String, Instant)>,
+    // Opened on first copy and kept for the session: on X11 and Wayland the copied
+    // text is only served while the clipboard handle is alive
+    clipboard: Option<arboard::Clipboard>,
+    // Where the file tree and editor were last drawn, for mouse hit-testing
+    file_tree_area: Option<Rect>,
+    editor_area: Option<Rect>,
+    // Origin for subtitle cue times; matches the cast timeline when recording
+    timeline_start: Instant,
+    // Effective settings and their sources, listed in the About overlay
+    settings: Vec<ResolvedSetting>,
 }
 
 impl<'a> UI<'a> {
@@ -94,8 +219,15 @@ impl<'a> UI<'a> {
         Self {
             state: UIState::Playing,
             speed_ms,
+            commit_gap_ms: None,
             file_tree: FileTreePane::new(),
-            editor: EditorPane,
+            editor: EditorPane::new(),
+            minimap: None,
+            captions: None,
+            fullscreen: false,
+            show_stats: false,
+            show_hints: true,
+            max_fps: None,
             terminal: TerminalPane,
             status_bar: StatusBarPane,
             engine,
@@ -105,17 +237,56 @@ impl<'a> UI<'a> {
             order,
             loop_playback,
             commit_spec,
+            compare_spec: None,
             is_range_mode,
+            is_filtered: false,
+            show_deletions: false,
+            only_additions: false,
+            collapse_threshold: None,
+            stat_mode: false,
+            line_pause_scale: 1.0,
+            pauses: EditPauses::default(),
+            file_order: FileOrder::Flow,
             diff_mode: None,
+            watch: None,
             playback_state: PlaybackState::Playing,
+            auto_resume: None,
             history: Vec::new(),
             history_index: None,
             menu_index: 0,
+            chapters: Vec::new(),
+            chapter_index: 0,
             prev_state: None,
             audio_player,
             audio_gen_handle: None,
+            audio_gen_cancel: Arc::new(AtomicBool::new(false)),
+            voiceover_wait: true,
+            narrated_chunks: 0,
             pending_metadata: None,
-            audio_progress: Arc::new(Mutex::new((String::new(), 0.0))),
+            audio_progress: Arc::new(Mutex::new((String::new(), 0.0, None))),
+            record_path: None,
+            events_path: None,
+            events: None,
+            gif_path: None,
+            gif_options: GifOptions {
+                fps: 10,
+                max_width: 1280,
+                max_height: 720,
+            },
+            gif: None,
+            subtitles_path: None,
+            subtitles: None,
+            audio_out_path: None,
+            audio_out: None,
+            resume_path: None,
+            pending_seek: None,
+            pending_start_file: None,
+            notice: None,
+            clipboard: None,
+            file_tree_area: None,
+            editor_area: None,
+            timeline_start: Instant::now(),
+            settings: Vec::new(),
         }
     }
 
@@ -124,16 +295,228 @@ impl<'a> UI<'a> {
         self.diff_mode = mode;
     }
 
+    /// Keep playing saves to the working tree once the current diff finishes.
+    pub fn set_watch(&mut self, watch: Option<WorkingTreeWatch>) {
+        self.watch = watch;
+    }
+
+    /// Replays a `REVA...REVB` comparison instead of individual commits.
+    pub fn set_compare_spec(&mut self, spec: Option<String>) {
+        self.compare_spec = spec;
+    }
+
+    /// Commits are narrowed by filters such as `--author` or `--grep`.
+    pub fn set_filtered(&mut self, filtered: bool) {
+        self.is_filtered = filtered;
+    }
+
+    /// Saves the commit and position to this file on exit, for `--resume`.
+    pub fn set_resume_path(&mut self, path: Option<PathBuf>) {
+        self.resume_path = path;
+    }
+
+    /// Seeks the first commit to `progress` (0.0-1.0) as soon as it starts playing.
+    pub fn seek_on_start(&mut self, progress: f32) {
+        self.pending_seek = Some(progress);
+    }
+
+    /// Starts the first commit at this file instead of from the top.
+    pub fn start_at_file(&mut self, path: Option<String>) {
+        self.pending_start_file = path;
+    }
+
+    /// Writes the narration as SRT subtitles to the given path (requires voiceover).
+    pub fn set_subtitles_path(&mut self, path: Option<PathBuf>) {
+        self.subtitles_path = path;
+    }
+
+    /// Writes the narration as one WAV track to the given path (requires voiceover).
+    pub fn set_audio_out_path(&mut self, path: Option<PathBuf>) {
+        self.audio_out_path = path;
+    }
+
+    /// Shows or hides the line-number gutter in the editor pane.
+    pub fn set_line_numbers(&mut self, enabled: bool) {
+        self.engine.set_line_numbers(enabled);
+    }
+
+    /// Soft-wraps long lines in the editor pane instead of cutting them off.
+    pub fn set_wrap(&mut self, enabled: bool) {
+        self.engine.set_wrap(enabled);
+    }
+
+    /// Sets the shape the editor cursor is drawn with.
+    pub fn set_cursor_style(&mut self, style: CursorStyle) {
+        self.editor.set_cursor_style(style);
+    }
+
+    /// Blinks the editor cursor, or keeps it steadily visible.
+    pub fn set_cursor_blink(&mut self, enabled: bool) {
+        self.engine.set_cursor_blink(enabled);
+    }
+
+    /// Arranges each commit's files by development flow, path or size, for both
+    /// playback and narration.
+    pub fn set_file_order(&mut self, order: FileOrder) {
+        self.file_order = order;
+        self.engine.set_file_order(order);
+    }
+
+    /// Lists the effective settings and where they came from in the About overlay.
+    pub fn set_settings(&mut self, settings: Vec<ResolvedSetting>) {
+        self.settings = settings;
+    }
+
+    /// Shows an overview column of the current file's changes beside the editor.
+    pub fn set_minimap(&mut self, enabled: bool) {
+        self.minimap = enabled.then_some(MinimapPane);
+    }
+
+    /// Shows the narration being spoken in a caption bar at the bottom (requires voiceover).
+    pub fn set_captions(&mut self, enabled: bool) {
+        self.captions = enabled.then(CaptionPane::default);
+    }
+
+    /// Shows the commit stats overlay from the start (toggled with `s`).
+    pub fn set_show_stats(&mut self, enabled: bool) {
+        self.show_stats = enabled;
+    }
+
+    /// Shows the key hint line at the bottom of the screen.
+    pub fn set_hints(&mut self, enabled: bool) {
+        self.show_hints = enabled;
+    }
+
+    /// The hint line is left out of recordings, which have no one to press keys.
+    fn hints_visible(&self) -> bool {
+        self.show_hints && self.record_path.is_none() && self.gif.is_none()
+    }
+
+    /// Starts each commit right away and narrates files as their chunks are ready.
+    pub fn set_voiceover_wait(&mut self, enabled: bool) {
+        self.voiceover_wait = enabled;
+    }
+
+    /// Caps how many frames per second are drawn while typing.
+    pub fn set_max_fps(&mut self, fps: Option<u32>) {
+        self.max_fps = fps;
+    }
+
+    /// Highlights only the changed words within modified lines.
+    pub fn set_word_diff(&mut self, enabled: bool) {
+        self.engine.set_word_diff(enabled);
+    }
+
+    /// Reveals whole syntax tokens per typing step instead of single characters.
+    pub fn set_type_by_token(&mut self, enabled: bool) {
+        self.engine.set_type_by_token(enabled);
+    }
+
+    /// Starts paused so nothing animates until Space or a manual step.
+    pub fn set_start_paused(&mut self, paused: bool) {
+        if paused {
+            self.playback_state = PlaybackState::Paused;
+        }
+    }
+
+    /// Resumes a paused playback after this long without key or mouse input.
+    pub fn set_auto_resume(&mut self, after: Option<Duration>) {
+        self.auto_resume = after;
+    }
+
+    /// Waits this many milliseconds between commits instead of scaling with speed.
+    pub fn set_commit_gap(&mut self, gap_ms: Option<u64>) {
+        self.commit_gap_ms = gap_ms;
+    }
+
+    /// Pauses this many milliseconds between files instead of scaling with speed.
+    pub fn set_file_gap(&mut self, gap_ms: Option<u64>) {
+        self.engine.set_file_gap(gap_ms);
+    }
+
+    /// Stretches the pause after each typed line, e.g. for presenting.
+    pub fn set_line_pause_scale(&mut self, scale: f64) {
+        self.line_pause_scale = scale;
+        self.engine.set_line_pause_scale(scale);
+    }
+
+    /// Uses these pauses around typed edits, for the animation and the narration sized to it.
+    pub fn set_pauses(&mut self, pauses: EditPauses) {
+        self.pauses = pauses;
+        self.engine.set_pauses(pauses);
+    }
+
+    /// Plays a click for each keystroke, through the narration's output when there is one.
+    /// Stays silent if no audio device is available.
+    pub fn set_keystroke_sound(&mut self, enabled: bool) {
+        if !enabled {
+            return;
+        }
+        let sound = match self.audio_player.as_ref().and_then(|p| p.output_handle()) {
+            Some(handle) => Ok(crate::audio::keystroke::KeystrokeSound::with_output(handle)),
+            None => crate::audio::keystroke::KeystrokeSound::new(),
+        };
+        match sound {
+            Ok(sound) => self.engine.set_keystroke_sound(sound),
+            Err(e) => log::warn!("{:#}, typing without keystroke sound", e),
+        }
+    }
+
+    /// Shows deleted lines struck through for a beat before removing them.
+    pub fn set_show_deletions(&mut self, enabled: bool) {
+        self.show_deletions = enabled;
+        self.engine.set_show_deletions(enabled);
+    }
+
+    /// Removes deleted lines instantly so playback shows only the additions.
+    pub fn set_only_additions(&mut self, enabled: bool) {
+        self.only_additions = enabled;
+        self.engine.set_only_additions(enabled);
+    }
+
+    /// Summarizes files changing more than `threshold` lines instead of animating them.
+    pub fn set_collapse_threshold(&mut self, threshold: Option<usize>) {
+        self.collapse_threshold = threshold;
+        self.engine.set_collapse_threshold(threshold);
+    }
+
+    /// Summarizes every file as a `path | +N −M` line for a fast overview of big commits.
+    pub fn set_stat_mode(&mut self, enabled: bool) {
+        self.stat_mode = enabled;
+        self.engine.set_stat_mode(enabled);
+    }
+
+    /// Records every rendered frame to an asciicast v2 file at the given path.
+    pub fn set_record_path(&mut self, path: Option<PathBuf>) {
+        self.record_path = path;
+    }
+
+    /// Streams playback events as JSON lines to the given path (`-` for stdout).
+    pub fn set_events_path(&mut self, path: Option<PathBuf>) {
+        if let Some(path) = &path {
+            TUI_ON_STDERR.store(export::events::is_stdout(path), Ordering::SeqCst);
+        }
+        self.engine.set_record_events(path.is_some());
+        self.events_path = path;
+    }
+
+    /// Renders every frame to an animated GIF at the given path.
+    pub fn set_gif(&mut self, path: Option<PathBuf>, options: GifOptions) {
+        self.gif_path = path;
+        self.gif_options = options;
+    }
+
     fn setup_signal_handler(should_exit: Arc<AtomicBool>) {
         ctrlc::set_handler(move || {
             // Restore terminal state before exiting
             let _ = disable_raw_mode();
             let _ = execute!(
-                io::stdout(),
+                tui_output(),
                 LeaveAlternateScreen,
                 DisableMouseCapture,
                 crossterm::cursor::Show
             );
+            crate::logging::release();
             should_exit.store(true, Ordering::SeqCst);
             // Exit immediately for external signals (SIGTERM)
             std::process::exit(0);
@@ -148,20 +531,169 @@ impl<'a> UI<'a> {
 
     /// Runs the main UI event loop.
     pub fn run(&mut self) -> Result<()> {
+        if let Some(path) = &self.subtitles_path {
+            if self.audio_player.is_some() {
+                self.subtitles = Some(SrtWriter::create(path)?);
+            } else {
+                log::warn!("--subtitles requires voiceover, no subtitle file written");
+            }
+        }
+        if let Some(path) = &self.audio_out_path {
+            if self.audio_player.is_some() {
+                self.audio_out = Some(NarrationTrackWriter::create(path)?);
+            } else {
+                log::warn!("--audio-out requires voiceover, no audio file written");
+            }
+        }
+        if self.captions.is_some() && self.audio_player.is_none() {
+            log::warn!("--captions requires voiceover, no captions shown");
+            self.captions = None;
+        }
+
+        self.timeline_start = Instant::now();
+        let recorder = match &self.record_path {
+            Some(path) => {
+                let (width, height) = crossterm::terminal::size()?;
+                Some(CastRecorder::create(path, width, height, &self.theme)?)
+            }
+            None => None,
+        };
+        if let Some(path) = &self.events_path {
+            self.events = Some(EventWriter::create(path)?);
+        }
+        if let Some(path) = &self.gif_path {
+            let (width, height) = crossterm::terminal::size()?;
+            self.gif = Some(GifRecorder::create(
+                path,
+                width,
+                height,
+                self.gif_options,
+                &self.theme,
+            )?);
+        }
+
+        crate::logging::hold();
         enable_raw_mode()?;
-        let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-        let backend = CrosstermBackend::new(stdout);
+        let mut output = tui_output();
+        execute!(output, EnterAlternateScreen, EnableMouseCapture)?;
+        let backend = CrosstermBackend::new(RecordingWriter::new(output, recorder));
         let mut terminal = Terminal::new(backend)?;
 
-        let result = self.run_loop(&mut terminal);
+        let mut result = self.run_loop(&mut terminal);
+        if let Some(mut gif) = self.gif.take() {
+            result = result.and(gif.finish());
+        }
+        if let Some(track) = self.audio_out.take() {
+            result = result.and(track.finish(self.timeline_start.elapsed().as_secs_f64()));
+        }
 
         self.cleanup(&mut terminal)?;
+        self.save_resume_state();
 
         result
     }
 
-    fn cleanup(&mut self, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+    /// Append a subtitle cue for each narration chunk the engine just started.
+    fn write_subtitles(&mut self, started: &[usize]) -> Result<()> {
+        let (Some(writer), Some(player)) = (&mut self.subtitles, &self.audio_player) else {
+            return Ok(());
+        };
+
+        let now = self.timeline_start.elapsed().as_secs_f64();
+        let speed = player.voiceover_config().narration_speed as f64;
+        let chunks = player.chunks_handle();
+        for chunk_id in started {
+            let chunk = chunks.lock().ok().and_then(|g| g.get(chunk_id).cloned());
+            if let Some(chunk) = chunk.filter(|c| c.has_audio) {
+                let duration = chunk.audio_duration_secs as f64 / speed;
+                writer.write_chunk(now, duration, &chunk.explanation)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Add the audio of each narration chunk the engine just started to the track.
+    fn write_audio_track(&mut self, started: &[usize]) -> Result<()> {
+        let (Some(track), Some(player)) = (&mut self.audio_out, &self.audio_player) else {
+            return Ok(());
+        };
+
+        let now = self.timeline_start.elapsed().as_secs_f64();
+        let speed = player.voiceover_config().narration_speed;
+        let chunks = player.chunks_handle();
+        for chunk_id in started {
+            let audio = chunks
+                .lock()
+                .ok()
+                .and_then(|g| g.get(chunk_id).and_then(|c| c.audio_data.clone()));
+            if let Some(audio) = audio {
+                track.write_chunk(now, audio, speed)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Show newly started narration in the caption bar, and clear it once
+    /// playback leaves that chunk (it finished, or the user sought away).
+    fn update_captions(&mut self, started: &[usize]) {
+        let (Some(captions), Some(player)) = (&mut self.captions, &self.audio_player) else {
+            return;
+        };
+
+        let speed = player.voiceover_config().narration_speed as f64;
+        let chunks = player.chunks_handle();
+        for chunk_id in started {
+            let chunk = chunks.lock().ok().and_then(|g| g.get(chunk_id).cloned());
+            if let Some(chunk) = chunk.filter(|c| c.has_audio) {
+                let duration = chunk.audio_duration_secs as f64 / speed;
+                captions.start(*chunk_id, &chunk.explanation, duration);
+            }
+        }
+        if captions.chunk_id().is_some() && captions.chunk_id() != self.engine.active_audio_chunk()
+        {
+            captions.clear();
+        }
+        captions.tick(self.playback_state == PlaybackState::Playing);
+    }
+
+    /// Write the playback events the engine collected since the last frame.
+    fn write_events(&mut self) -> Result<()> {
+        let events = self.engine.take_events();
+        if let Some(writer) = &mut self.events {
+            let now = self.timeline_start.elapsed().as_secs_f64();
+            for event in &events {
+                writer.write_event(now, event)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remember where playback stopped so the next `--resume` can continue from there.
+    fn save_resume_state(&self) {
+        let Some(path) = &self.resume_path else {
+            return;
+        };
+        // Quitting while narration is generated leaves the pending commit unstarted
+        let state = match (&self.pending_metadata, self.engine.current_metadata()) {
+            (Some(pending), _) => ResumeState {
+                commit: pending.hash.clone(),
+                progress: self.pending_seek.unwrap_or(0.0),
+            },
+            (None, Some(current)) => ResumeState {
+                commit: current.hash.clone(),
+                progress: self.engine.progress(),
+            },
+            (None, None) => return,
+        };
+        if state.commit == "working-tree" {
+            return;
+        }
+        if let Err(e) = resume::save(path, &state) {
+            log::warn!("Could not save resume position: {:#}", e);
+        }
+    }
+
+    fn cleanup(&mut self, terminal: &mut TuiTerminal) -> Result<()> {
         disable_raw_mode()?;
         execute!(
             terminal.backend_mut(),
@@ -169,10 +701,64 @@ impl<'a> UI<'a> {
             DisableMouseCapture
         )?;
         terminal.show_cursor()?;
+        crate::logging::release();
         Ok(())
     }
 
-    fn run_loop(&mut self, terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+    /// Tell the engine how much of the editor is visible, for scrolling and wrapping.
+    fn sync_viewport(&mut self, mut size: Size) {
+        // Once the editor was drawn, rendering keeps the engine at its real size
+        if self.editor_area.is_some() {
+            return;
+        }
+        if self.captions.is_some() {
+            size.height = size.height.saturating_sub(CAPTION_HEIGHT);
+        }
+        if self.hints_visible() {
+            size.height = size.height.saturating_sub(1);
+        }
+        let minimap_width = self.minimap.as_ref().map_or(0, |_| MINIMAP_WIDTH as usize);
+        let (viewport_height, content_width) = if self.fullscreen {
+            // Whole screen, minus the editor's vertical padding
+            (
+                (size.height as usize).saturating_sub(2),
+                (size.width as usize).saturating_sub(minimap_width),
+            )
+        } else {
+            // Editor area: 70% (right column) × 80% (editor pane) = 56% of total height
+            // Editor width: 70% (right column), minus the minimap if shown
+            (
+                (size.height as f32 * 0.70 * 0.80) as usize,
+                ((size.width as f32 * 0.70) as usize).saturating_sub(minimap_width),
+            )
+        };
+        self.engine.set_viewport_height(viewport_height);
+        self.engine.set_content_width(content_width);
+    }
+
+    /// Whether a pause has gone unattended for `--auto-resume`; menus and
+    /// dialogs are left alone, since someone is reading them.
+    fn auto_resume_due(&self, since_input: Duration) -> bool {
+        self.playback_state == PlaybackState::Paused
+            && matches!(
+                self.state,
+                UIState::Playing | UIState::WaitingForNext { .. }
+            )
+            && self.auto_resume.is_some_and(|after| since_input >= after)
+    }
+
+    /// Nothing is typing, so the screen only changes slowly (cursor blink, progress).
+    fn is_idle(&self) -> bool {
+        self.state != UIState::Playing || self.playback_state == PlaybackState::Paused
+    }
+
+    fn run_loop(&mut self, terminal: &mut TuiTerminal) -> Result<()> {
+        let mut last_size = None;
+        let mut last_draw: Option<Instant> = None;
+        let mut redraw_pending = false;
+        // Input is drawn straight away rather than waiting for the next frame
+        let mut had_input = true;
+        let mut last_input = Instant::now();
         loop {
             // Check for Ctrl+C signal
             if self.should_exit.load(Ordering::Relaxed) {
@@ -181,23 +767,57 @@ impl<'a> UI<'a> {
 
             // Update viewport dimensions for scroll calculation
             let size = terminal.size()?;
-            // Editor area: 70% (right column) × 80% (editor pane) = 56% of total height
-            let viewport_height = (size.height as f32 * 0.70 * 0.80) as usize;
-            // Editor width: 70% (right column)
-            let content_width = (size.width as f32 * 0.70) as usize;
-            self.engine.set_viewport_height(viewport_height);
-            self.engine.set_content_width(content_width);
+            self.sync_viewport(size);
+            // Redraw after a resize even while paused, e.g. to leave the too-small screen
+            let resized = last_size.replace(size) != Some(size);
 
             // Tick the animation engine (force redraw during audio generation)
-            let needs_redraw = self.engine.tick() || matches!(self.state, UIState::GeneratingAudio);
+            redraw_pending |=
+                self.engine.tick() || resized || matches!(self.state, UIState::GeneratingAudio);
+            let started = self.engine.take_started_audio_chunks();
+            self.write_subtitles(&started)?;
+            self.write_audio_track(&started)?;
+            self.update_captions(&started);
+            self.write_events()?;
 
-            if needs_redraw {
-                terminal.draw(|f| self.render(f))?;
+            let idle = self.is_idle();
+            let interval = redraw_interval(self.max_fps, idle);
+            let since_draw = last_draw.map_or(interval, |t| t.elapsed());
+            if redraw_pending && (had_input || resized || since_draw >= interval) {
+                let frame = terminal.draw(|f| self.render(f))?;
+                if let Some(gif) = &mut self.gif {
+                    gif.capture(frame.buffer)?;
+                }
+                last_draw = Some(Instant::now());
+                redraw_pending = false;
             }
 
-            // Poll for keyboard events at frame rate
-            if event::poll(std::time::Duration::from_millis(8))? {
-                if let Event::Key(key) = event::read()? {
+            // Poll for keyboard events at frame rate, or until the next frame when idle
+            let poll = if idle {
+                interval
+                    .saturating_sub(last_draw.map_or(Duration::ZERO, |t| t.elapsed()))
+                    .max(ACTIVE_POLL)
+            } else {
+                ACTIVE_POLL
+            };
+            had_input = event::poll(poll)?;
+            if had_input {
+                redraw_pending = true;
+                let event = event::read()?;
+                if matches!(event, Event::Key(_) | Event::Mouse(_)) {
+                    last_input = Instant::now();
+                }
+                if let Event::Mouse(mouse) = event {
+                    self.handle_mouse(mouse);
+                }
+                if let Event::Key(key) = event {
+                    // Repaint everything when stray output or a resize garbled the screen
+                    if key.code == KeyCode::Char('l')
+                        && key.modifiers.contains(KeyModifiers::CONTROL)
+                    {
+                        terminal.clear()?;
+                        continue;
+                    }
                     match &self.state {
                         UIState::Menu => match key.code {
                             KeyCode::Esc => self.close_menu(),
@@ -214,6 +834,20 @@ impl<'a> UI<'a> {
                             },
                             _ => {}
                         },
+                        UIState::Chapters => match key.code {
+                            KeyCode::Esc | KeyCode::Char('c') | KeyCode::Char('q') => {
+                                self.close_menu()
+                            }
+                            KeyCode::Up | KeyCode::Char('k') => {
+                                self.chapter_index = self.chapter_index.saturating_sub(1);
+                            }
+                            KeyCode::Down | KeyCode::Char('j') => {
+                                self.chapter_index = (self.chapter_index + 1)
+                                    .min(self.chapters.len().saturating_sub(1));
+                            }
+                            KeyCode::Enter => self.jump_to_chapter(),
+                            _ => {}
+                        },
                         UIState::KeyBindings | UIState::About => match key.code {
                             KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
                                 self.state = UIState::Menu;
@@ -230,14 +864,15 @@ impl<'a> UI<'a> {
                             _ => {}
                         },
                         UIState::GeneratingAudio => match key.code {
+                            KeyCode::Esc => self.cancel_audio_generation(),
                             KeyCode::Char('q') => {
+                                self.audio_gen_cancel.store(true, Ordering::SeqCst);
                                 self.audio_gen_handle = None;
-                                self.pending_metadata = None;
                                 self.state = UIState::Finished;
                             }
                             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                self.audio_gen_cancel.store(true, Ordering::SeqCst);
                                 self.audio_gen_handle = None;
-                                self.pending_metadata = None;
                                 self.state = UIState::Finished;
                             }
                             _ => {}
@@ -253,13 +888,31 @@ impl<'a> UI<'a> {
                             KeyCode::Char(' ') => {
                                 self.toggle_pause();
                             }
+                            KeyCode::Left => self.seek_by(-SEEK_FRACTION),
+                            KeyCode::Right => self.seek_by(SEEK_FRACTION),
+                            KeyCode::PageUp => self.scroll_page(false),
+                            KeyCode::PageDown => self.scroll_page(true),
                             KeyCode::Char(ch) => match ch {
                                 'h' => self.step_line_back(),
                                 'l' => self.step_line(),
                                 'H' => self.step_change_back(),
                                 'L' => self.step_change(),
+                                '[' => self.skip_file(false),
+                                ']' => self.skip_file(true),
                                 'p' => self.handle_prev(),
                                 'n' => self.handle_next(),
+                                'f' => self.fullscreen = !self.fullscreen,
+                                'y' => self.copy_current_diff(),
+                                'r' => self.replay_narration(),
+                                'm' => self.toggle_mute(),
+                                'e' => self.expand_collapsed_file(),
+                                'c' => self.open_chapters(),
+                                's' => self.toggle_stats(),
+                                '?' => self.show_hints = !self.show_hints,
+                                '+' | '=' => self.adjust_volume(VOLUME_STEP),
+                                '-' => self.adjust_volume(-VOLUME_STEP),
+                                '>' => self.adjust_speed(true),
+                                '<' => self.adjust_speed(false),
                                 _ => {}
                             },
                             _ => {}
@@ -268,14 +921,23 @@ impl<'a> UI<'a> {
                 }
             }
 
+            if self.auto_resume_due(last_input.elapsed()) {
+                self.toggle_pause();
+                redraw_pending = true;
+            }
+
             // State machine
             match self.state {
                 UIState::Playing => {
-                    if self.engine.is_finished() {
+                    self.catch_up_narration();
+                    // Watching a clean working tree starts out waiting for the first save
+                    let nothing_loaded =
+                        self.watch.is_some() && self.engine.current_metadata().is_none();
+                    if self.engine.is_finished() || nothing_loaded {
                         if self.repo.is_some() {
+                            let gap_ms = self.commit_gap_ms.unwrap_or(self.speed_ms * 100);
                             self.state = UIState::WaitingForNext {
-                                resume_at: Instant::now()
-                                    + Duration::from_millis(self.speed_ms * 100),
+                                resume_at: Instant::now() + Duration::from_millis(gap_ms),
                             };
                         } else {
                             self.state = UIState::Finished;
@@ -305,7 +967,7 @@ impl<'a> UI<'a> {
                         }
                     }
                 }
-                UIState::Menu | UIState::KeyBindings | UIState::About => {
+                UIState::Menu | UIState::Chapters | UIState::KeyBindings | UIState::About => {
                     // Paused while in menu/dialog
                 }
                 UIState::Finished => {
@@ -317,3 +979,17 @@ impl<'a> UI<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn idle_redraws_slow_down_and_fps_caps_typing() {
+        assert_eq!(redraw_interval(None, false), Duration::ZERO);
+        assert_eq!(redraw_interval(Some(20), false), Duration::from_millis(50));
+        assert_eq!(redraw_interval(None, true), Duration::from_millis(200));
+        // A cap below the idle rate still applies when idle
+        assert_eq!(redraw_interval(Some(2), true), Duration::from_millis(500));
+    }
+}
@@ -1,10 +1,16 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
 use anyhow::Result;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
 
 use crate::animation::StepMode;
 use crate::git::{CommitMetadata, GitRepository};
 use crate::PlaybackOrder;
 
-use super::{PlaybackState, UIState, UI};
+use super::{PlaybackState, UIState, NOTICE_DURATION, UI, WATCH_POLL, WHEEL_SCROLL_LINES};
 
 impl<'a> UI<'a> {
     pub(super) fn open_menu(&mut self) {
@@ -14,6 +20,63 @@ impl<'a> UI<'a> {
         self.engine.pause();
     }
 
+    /// List the commits of a range to jump between (range playback only).
+    pub(super) fn open_chapters(&mut self) {
+        let Some(repo) = self.repo else {
+            return;
+        };
+        if !self.is_range_mode || self.compare_spec.is_some() {
+            return;
+        }
+        self.chapters = repo.range_chapters();
+        if self.chapters.is_empty() {
+            return;
+        }
+
+        let current = self.engine.current_metadata().map(|m| m.hash.as_str());
+        self.chapter_index = self
+            .chapters
+            .iter()
+            .position(|c| Some(c.hash.as_str()) == current)
+            .unwrap_or(0);
+        self.prev_state = Some(Box::new(self.state.clone()));
+        self.state = UIState::Chapters;
+        self.engine.pause();
+    }
+
+    /// Play the selected chapter; ordered playback continues from there.
+    pub(super) fn jump_to_chapter(&mut self) {
+        let Some(repo) = self.repo else {
+            return;
+        };
+        let Some(chapter) = self.chapters.get(self.chapter_index) else {
+            return;
+        };
+
+        let result = match self.order {
+            PlaybackOrder::Random => repo.get_commit(&chapter.hash),
+            PlaybackOrder::Asc => {
+                repo.set_range_position(self.chapter_index);
+                self.fetch_repo_commit(repo)
+            }
+            PlaybackOrder::Desc => {
+                repo.set_range_position(self.chapters.len() - 1 - self.chapter_index);
+                self.fetch_repo_commit(repo)
+            }
+        };
+
+        match result {
+            Ok(metadata) => {
+                self.prev_state = None;
+                self.load_commit(metadata);
+            }
+            Err(e) => {
+                self.close_menu();
+                self.show_notice(format!("Could not open commit: {}", e));
+            }
+        }
+    }
+
     pub(super) fn close_menu(&mut self) {
         let restored = self
             .prev_state
@@ -30,8 +93,8 @@ impl<'a> UI<'a> {
     }
 
     pub(super) fn play_commit(&mut self, metadata: CommitMetadata, record_history: bool) {
-        eprintln!(
-            "[UI] play_commit called, has_audio_player={}",
+        log::debug!(
+            "play_commit called, has_audio_player={}",
             self.audio_player.is_some()
         );
         if record_history {
@@ -39,45 +102,124 @@ impl<'a> UI<'a> {
         }
 
         // If audio is enabled, generate chunks in a background thread
-        // and WAIT for completion before starting the video.
-        // Show progress modal during generation.
+        // and WAIT for completion before starting the video, showing a progress
+        // modal. With --no-voiceover-wait the video starts right away instead.
         if let Some(audio_player) = &self.audio_player {
-            eprintln!("[UI] Starting audio generation in background thread...");
+            log::debug!("Starting audio generation in background thread...");
             let config = audio_player.voiceover_config().clone();
             let chunks_map = audio_player.chunks_handle();
-            let file_changes: Vec<(String, String, crate::git::FileStatus)> = metadata
-                .changes
-                .iter()
-                .filter(|c| !c.is_excluded)
-                .map(|c| (c.path.clone(), Self::build_diff_text(c), c.status.clone()))
-                .collect();
-            let message = metadata.message.clone();
-            let speed_ms = self.speed_ms;
+            // Stat summaries are narrated from an excerpt, like collapsed files
+            let collapse_threshold = if self.stat_mode {
+                Some(0)
+            } else {
+                self.collapse_threshold
+            };
+            let file_changes: Vec<(String, String, crate::git::FileStatus)> =
+                crate::animation::file_play_order(&metadata, self.file_order)
+                    .into_iter()
+                    .map(|i| &metadata.changes[i])
+                    .filter(|c| !c.is_excluded)
+                    .map(|c| {
+                        let diff = if c.exceeds_collapse_threshold(collapse_threshold) {
+                            c.collapsed_narration_diff()
+                        } else {
+                            c.narration_diff()
+                        };
+                        (c.path.clone(), diff, c.status.clone())
+                    })
+                    .collect();
+            let commit_hash = metadata.hash.clone();
+            let commit = crate::audio::CommitContext::new(&metadata);
+            let file_order = self.file_order;
+            let timing = crate::audio::llm::AnimationTiming {
+                speed_ms: self.speed_ms,
+                show_deletions: self.show_deletions,
+                only_additions: self.only_additions,
+                collapse_threshold,
+                line_pause_scale: self.line_pause_scale,
+                pauses: self.pauses,
+            };
             let progress = self.audio_progress.clone();
-
-            self.pending_metadata = Some(metadata);
-            self.state = UIState::GeneratingAudio;
-            self.audio_gen_handle = Some(std::thread::spawn(move || {
+            // A commit skipped to mid-generation must not publish into this one
+            self.audio_gen_cancel.store(true, Ordering::SeqCst);
+            // Fresh flag per run so cancelling can't affect a later generation
+            self.audio_gen_cancel = Arc::new(AtomicBool::new(false));
+            let cancel = Arc::clone(&self.audio_gen_cancel);
+
+            if !self.voiceover_wait {
+                // Steps are built before the thread clears the last commit's chunks
+                let _ = chunks_map.lock().map(|mut g| g.clear());
+            }
+            let handle = std::thread::spawn(move || {
                 crate::audio::generate_audio_chunks_with_progress(
                     config,
                     chunks_map,
-                    message,
+                    commit_hash,
+                    commit,
                     file_changes,
-                    speed_ms,
+                    file_order,
+                    timing,
                     progress,
+                    cancel,
                 );
-            }));
-            return;
+            });
+            self.audio_gen_handle = Some(handle);
+            if self.voiceover_wait {
+                self.pending_metadata = Some(metadata);
+                self.state = UIState::GeneratingAudio;
+                return;
+            }
+            self.narrated_chunks = 0;
         }
 
-        // No audio - start video immediately
+        // No audio (or not waiting for it) - start video immediately
         self.finish_play_commit(metadata);
     }
 
+    /// Stop voiceover generation for the pending commit and play it with the
+    /// narration finished so far. The background thread stops at its next
+    /// checkpoint and publishes nothing after the flag is set.
+    pub(super) fn cancel_audio_generation(&mut self) {
+        self.audio_gen_cancel.store(true, Ordering::SeqCst);
+        self.audio_gen_handle = None;
+        if let Some(metadata) = self.pending_metadata.take() {
+            self.finish_play_commit(metadata);
+        }
+    }
+
+    /// Weave narration generated since the commit started into the steps not
+    /// played yet (`--no-voiceover-wait`), one last time once generation ends.
+    pub(super) fn catch_up_narration(&mut self) {
+        let (Some(handle), Some(player)) = (&self.audio_gen_handle, &self.audio_player) else {
+            return;
+        };
+        let finished = handle.is_finished();
+        let count = player.chunk_count();
+        if count != self.narrated_chunks || finished {
+            self.narrated_chunks = count;
+            self.engine.refresh_narration();
+        }
+        if finished {
+            let _ = self.audio_gen_handle.take().map(|h| h.join());
+        }
+    }
+
     /// Called once audio generation is done (or skipped) to actually start
     /// the animation with whatever audio chunks are available.
     pub(super) fn finish_play_commit(&mut self, metadata: CommitMetadata) {
         self.engine.load_commit(&metadata);
+        if let Some(progress) = self.pending_seek.take() {
+            self.engine.seek_to_fraction(progress);
+        }
+        if let Some(path) = self.pending_start_file.take() {
+            let found = metadata.find_file(&path);
+            if !found.is_some_and(|index| self.engine.seek_to_file(index)) {
+                self.show_notice(format!(
+                    "{} isn't in this commit, starting at the top",
+                    path
+                ));
+            }
+        }
         match self.playback_state {
             PlaybackState::Playing => self.engine.resume(),
             PlaybackState::Paused => self.engine.pause(),
@@ -85,34 +227,6 @@ impl<'a> UI<'a> {
         self.state = UIState::Playing;
     }
 
-    /// Build a text representation of file diff (including @@ hunk headers for duration calculation)
-    fn build_diff_text(change: &crate::git::FileChange) -> String {
-        let mut diff = String::new();
-
-        for hunk in &change.hunks {
-            // Include hunk header so calculate_animation_duration can parse it
-            diff.push_str(&format!(
-                "@@ -{},{} +{},{} @@\n",
-                hunk.old_start, hunk.old_lines, hunk.new_start, hunk.new_lines
-            ));
-            for line in &hunk.lines {
-                match line.change_type {
-                    crate::git::LineChangeType::Addition => {
-                        diff.push_str(&format!("+{}\n", line.content));
-                    }
-                    crate::git::LineChangeType::Deletion => {
-                        diff.push_str(&format!("-{}\n", line.content));
-                    }
-                    crate::git::LineChangeType::Context => {
-                        diff.push_str(&format!(" {}\n", line.content));
-                    }
-                }
-            }
-        }
-
-        diff
-    }
-
     pub(super) fn record_history(&mut self, metadata: &CommitMetadata) {
         if let Some(index) = self.history_index {
             if index + 1 < self.history.len() {
@@ -156,6 +270,12 @@ impl<'a> UI<'a> {
         }
     }
 
+    /// Page through the current file; pauses so the view stays put.
+    pub(super) fn scroll_page(&mut self, forward: bool) {
+        self.ensure_manual_pause();
+        self.engine.scroll_page(forward);
+    }
+
     pub(super) fn step_line(&mut self) {
         self.ensure_manual_pause();
         let _ = self.engine.manual_step(StepMode::Line);
@@ -176,6 +296,145 @@ impl<'a> UI<'a> {
         let _ = self.engine.restore_change_checkpoint();
     }
 
+    /// Seek forward or backward by a fraction of the current commit.
+    pub(super) fn seek_by(&mut self, delta: f32) {
+        let target = self.engine.progress() + delta;
+        if self.engine.seek_to_fraction(target)
+            && matches!(self.state, UIState::WaitingForNext { .. })
+        {
+            self.state = UIState::Playing;
+        }
+    }
+
+    /// Click a file in the tree to jump to it; scroll the wheel over the editor to scroll it.
+    pub(super) fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if !matches!(
+            self.state,
+            UIState::Playing | UIState::WaitingForNext { .. }
+        ) {
+            return;
+        }
+        let position = Position::new(mouse.column, mouse.row);
+        let hit = |area: Option<Rect>| area.filter(|a| a.contains(position));
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let file = hit(self.file_tree_area)
+                    .and_then(|area| self.file_tree.file_at(area, mouse.row, &self.theme));
+                if let Some(file_index) = file {
+                    self.jump_to_file(file_index);
+                }
+            }
+            MouseEventKind::ScrollUp if hit(self.editor_area).is_some() => {
+                self.engine.scroll_by(-WHEEL_SCROLL_LINES);
+            }
+            MouseEventKind::ScrollDown if hit(self.editor_area).is_some() => {
+                self.engine.scroll_by(WHEEL_SCROLL_LINES);
+            }
+            _ => {}
+        }
+    }
+
+    fn jump_to_file(&mut self, file_index: usize) {
+        if self.engine.seek_to_file(file_index)
+            && matches!(self.state, UIState::WaitingForNext { .. })
+        {
+            self.state = UIState::Playing;
+        }
+    }
+
+    /// Move to the next or previous file of the commit.
+    pub(super) fn skip_file(&mut self, forward: bool) {
+        if self.engine.skip_file(forward) {
+            if matches!(self.state, UIState::WaitingForNext { .. }) {
+                self.state = UIState::Playing;
+            }
+        } else if forward {
+            self.show_notice("Already at the last file".to_string());
+        } else {
+            self.show_notice("Already at the first file".to_string());
+        }
+    }
+
+    /// Nudge narration volume up or down.
+    pub(super) fn adjust_volume(&mut self, delta: f32) {
+        if let Some(player) = &self.audio_player {
+            player.set_volume(player.volume() + delta);
+        }
+    }
+
+    /// Halve or double the typing delay for the rest of playback.
+    pub(super) fn adjust_speed(&mut self, faster: bool) {
+        let speed = self.engine.adjust_speed(faster);
+        let message = if self.audio_player.is_some() {
+            format!("Typing at {}ms per character, narration may drift", speed)
+        } else {
+            format!("Typing at {}ms per character", speed)
+        };
+        self.show_notice(message);
+    }
+
+    /// Play the collapsed or stat-summarized file being shown in full.
+    pub(super) fn expand_collapsed_file(&mut self) {
+        if !self.engine.expand_current_file() {
+            self.show_notice("Nothing collapsed to expand here".to_string());
+        }
+    }
+
+    /// Silence narration or bring it back from the next chunk.
+    pub(super) fn toggle_mute(&mut self) {
+        if self.audio_player.is_none() {
+            self.show_notice("No narration to mute".to_string());
+            return;
+        }
+        let message = if self.engine.toggle_mute() {
+            "Narration muted"
+        } else {
+            "Narration unmuted, resuming at the next chunk"
+        };
+        self.show_notice(message.to_string());
+    }
+
+    /// Restart the narration chunk covering the current position.
+    pub(super) fn replay_narration(&mut self) {
+        if self.engine.is_muted() {
+            self.show_notice("Narration is muted (m to unmute)".to_string());
+        } else if !self.engine.replay_audio_chunk() {
+            self.show_notice("No narration to replay here".to_string());
+        }
+    }
+
+    /// Copy the diff of the file being animated to the system clipboard.
+    pub(super) fn copy_current_diff(&mut self) {
+        let Some(change) = self
+            .engine
+            .current_metadata()
+            .and_then(|m| m.changes.get(self.engine.current_file_index))
+        else {
+            return;
+        };
+        let text = change.narration_diff();
+        let path = change.path.clone();
+
+        let clipboard = match self.clipboard.take() {
+            Some(clipboard) => Ok(clipboard),
+            None => arboard::Clipboard::new(),
+        };
+        let message = match clipboard.and_then(|mut c| {
+            let copied = c.set_text(text);
+            self.clipboard = Some(c);
+            copied
+        }) {
+            Ok(()) => format!("Copied diff of {} to clipboard", path),
+            Err(e) => format!("Clipboard unavailable: {}", e),
+        };
+        self.show_notice(message);
+    }
+
+    pub(super) fn show_notice(&mut self, message: String) {
+        self.notice = Some((message, Instant::now() + NOTICE_DURATION));
+    }
+
     pub(super) fn handle_prev(&mut self) {
         if let Some(index) = self.history_index {
             if index > 0 {
@@ -204,6 +463,16 @@ impl<'a> UI<'a> {
 
     pub(super) fn advance_to_next_commit(&mut self) -> bool {
         if let Some(diff_mode) = self.diff_mode {
+            if let (Some(watch), Some(repo)) = (&mut self.watch, self.repo) {
+                if let Some(metadata) = watch.next_delta(repo) {
+                    self.load_commit(metadata);
+                    return true;
+                }
+                self.state = UIState::WaitingForNext {
+                    resume_at: Instant::now() + WATCH_POLL,
+                };
+                return false;
+            }
             if let Some(repo) = self.repo {
                 match repo.get_working_tree_diff(diff_mode) {
                     Ok(metadata) if !metadata.changes.is_empty() => {
@@ -225,6 +494,20 @@ impl<'a> UI<'a> {
             return false;
         };
 
+        if let Some(spec) = self.single_commit_spec() {
+            // A single --commit starts over from the top when looping instead of moving on
+            return match repo.get_commit(&spec) {
+                Ok(metadata) if self.loop_playback => {
+                    self.play_commit(metadata, false);
+                    true
+                }
+                _ => {
+                    self.state = UIState::Finished;
+                    false
+                }
+            };
+        }
+
         match self.fetch_repo_commit(repo) {
             Ok(metadata) => {
                 self.load_commit(metadata);
@@ -248,7 +531,20 @@ impl<'a> UI<'a> {
         }
     }
 
+    /// The `--commit` hash when playback is that one commit rather than a range,
+    /// comparison or walk through the (filtered) history.
+    fn single_commit_spec(&self) -> Option<String> {
+        if self.is_range_mode || self.is_filtered || self.compare_spec.is_some() {
+            return None;
+        }
+        self.commit_spec.clone()
+    }
+
     pub(super) fn fetch_repo_commit(&self, repo: &GitRepository) -> Result<CommitMetadata> {
+        if let Some(spec) = &self.compare_spec {
+            return repo.diff_refs(spec);
+        }
+
         if self.is_range_mode {
             return match self.order {
                 PlaybackOrder::Random => repo.random_range_commit(),
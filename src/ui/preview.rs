@@ -0,0 +1,186 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use chrono::Utc;
+use crossterm::{
+    event::{self, EnableMouseCapture, Event},
+    execute,
+    terminal::{enable_raw_mode, EnterAlternateScreen},
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+
+use super::{TuiTerminal, UI};
+use crate::export::RecordingWriter;
+use crate::git::{CommitMetadata, DiffHunk, FileChange, FileStatus, LineChange, LineChangeType};
+
+// Start the sample partway through so the first frame already shows changes
+const PREVIEW_START: f32 = 0.4;
+
+impl<'a> UI<'a> {
+    /// Plays a built-in sample commit with the current theme until `duration`
+    /// elapses or a key is pressed. Needs no repository.
+    pub fn preview(&mut self, duration: Duration) -> Result<()> {
+        crate::logging::hold();
+        enable_raw_mode()?;
+        let mut output = super::tui_output();
+        execute!(output, EnterAlternateScreen, EnableMouseCapture)?;
+        let backend = CrosstermBackend::new(RecordingWriter::new(output, None));
+        let mut terminal = Terminal::new(backend)?;
+
+        self.seek_on_start(PREVIEW_START);
+        self.finish_play_commit(sample_commit());
+        let result = self.preview_loop(&mut terminal, duration);
+
+        self.cleanup(&mut terminal)?;
+        result
+    }
+
+    fn preview_loop(&mut self, terminal: &mut TuiTerminal, duration: Duration) -> Result<()> {
+        let deadline = Instant::now() + duration;
+        let mut first_frame = true;
+        while Instant::now() < deadline {
+            self.sync_viewport(terminal.size()?);
+            if self.engine.tick() || first_frame {
+                terminal.draw(|f| self.render(f))?;
+                first_frame = false;
+            }
+
+            if event::poll(Duration::from_millis(8))? {
+                if let Event::Key(_) = event::read()? {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn line(change_type: LineChangeType, content: &str) -> LineChange {
+    LineChange {
+        change_type,
+        content: content.to_string(),
+        old_line_no: None,
+        new_line_no: None,
+        inline_changes: Vec::new(),
+    }
+}
+
+fn file_change(
+    path: &str,
+    status: FileStatus,
+    old_content: Option<&str>,
+    new_content: &str,
+    hunk: DiffHunk,
+) -> FileChange {
+    FileChange {
+        path: path.to_string(),
+        old_path: None,
+        status,
+        is_binary: false,
+        is_excluded: false,
+        exclusion_reason: None,
+        old_content: old_content.map(str::to_string),
+        new_content: Some(new_content.to_string()),
+        hunks: vec![hunk],
+        diff: String::new(),
+        language: crate::syntax::detect_language(path, Some(new_content)),
+    }
+}
+
+/// Small fixed commit covering additions, deletions and context lines.
+fn sample_commit() -> CommitMetadata {
+    use LineChangeType::{Addition, Context, Deletion};
+
+    let greeting = file_change(
+        "src/greeting.rs",
+        FileStatus::Modified,
+        Some(
+            "pub fn greet(name: &str) -> String {\n    format!(\"Hello, {}\", name)\n}\n\n\
+             pub fn farewell(name: &str) -> String {\n    format!(\"Bye, {}\", name)\n}\n",
+        ),
+        "/// Greets someone by name.\npub fn greet(name: &str) -> String {\n    \
+         format!(\"Hello, {}!\", name)\n}\n\npub fn farewell(name: &str) -> String {\n    \
+         format!(\"Goodbye, {}.\", name)\n}\n",
+        DiffHunk {
+            old_start: 1,
+            old_lines: 7,
+            new_start: 1,
+            new_lines: 8,
+            lines: vec![
+                line(Addition, "/// Greets someone by name."),
+                line(Context, "pub fn greet(name: &str) -> String {"),
+                line(Deletion, "    format!(\"Hello, {}\", name)"),
+                line(Addition, "    format!(\"Hello, {}!\", name)"),
+                line(Context, "}"),
+                line(Context, ""),
+                line(Context, "pub fn farewell(name: &str) -> String {"),
+                line(Deletion, "    format!(\"Bye, {}\", name)"),
+                line(Addition, "    format!(\"Goodbye, {}.\", name)"),
+                line(Context, "}"),
+            ],
+        },
+    );
+
+    let main = file_change(
+        "src/main.rs",
+        FileStatus::Added,
+        None,
+        "mod greeting;\n\nfn main() {\n    println!(\"{}\", greeting::greet(\"world\"));\n}\n",
+        DiffHunk {
+            old_start: 0,
+            old_lines: 0,
+            new_start: 1,
+            new_lines: 5,
+            lines: vec![
+                line(Addition, "mod greeting;"),
+                line(Addition, ""),
+                line(Addition, "fn main() {"),
+                line(
+                    Addition,
+                    "    println!(\"{}\", greeting::greet(\"world\"));",
+                ),
+                line(Addition, "}"),
+            ],
+        },
+    );
+
+    CommitMetadata {
+        hash: "preview".to_string(),
+        author: "torvax".to_string(),
+        date: Utc::now(),
+        message: "Polish greetings and add an entry point".to_string(),
+        changes: vec![greeting, main],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_hunks_match_file_contents() {
+        for change in sample_commit().changes {
+            let hunk = &change.hunks[0];
+            let old_lines = change
+                .old_content
+                .as_deref()
+                .map_or(0, |c| c.lines().count());
+            let new_lines = change
+                .new_content
+                .as_deref()
+                .map_or(0, |c| c.lines().count());
+            let old_side = hunk
+                .lines
+                .iter()
+                .filter(|l| !matches!(l.change_type, LineChangeType::Addition))
+                .count();
+            let new_side = hunk
+                .lines
+                .iter()
+                .filter(|l| !matches!(l.change_type, LineChangeType::Deletion))
+                .count();
+            assert_eq!(old_side, old_lines, "{}", change.path);
+            assert_eq!(new_side, new_lines, "{}", change.path);
+        }
+    }
+}
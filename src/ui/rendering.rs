@@ -1,18 +1,137 @@
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::Style,
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Gauge, Padding, Paragraph},
+    widgets::{Block, Borders, Clear, Gauge, Padding, Paragraph, Wrap},
     Frame,
 };
+use std::time::{Duration, Instant};
 use unicode_width::UnicodeWidthStr;
 
-use super::{UIState, UI};
+use super::{PlaybackState, UIState, UI};
+use crate::panes::{CAPTION_HEIGHT, MINIMAP_WIDTH};
+
+// Smallest terminal the pane layout and dialogs fit in
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 20;
 
 impl<'a> UI<'a> {
     pub(super) fn render(&mut self, f: &mut Frame) {
         let size = f.area();
 
+        if size.width < MIN_WIDTH || size.height < MIN_HEIGHT {
+            self.file_tree_area = None;
+            self.editor_area = None;
+            self.render_too_small(f, size);
+            return;
+        }
+
+        // The hint line and captions take the bottom rows under the panes;
+        // overlays still center on the whole screen
+        let panes_area = if self.hints_visible() {
+            let layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(1)])
+                .split(size);
+            let paused = self.playback_state == PlaybackState::Paused;
+            let hints = Paragraph::new(key_hints(&self.state, paused)).style(
+                Style::default()
+                    .fg(self.theme.status_message)
+                    .bg(self.theme.background_left),
+            );
+            f.render_widget(hints, layout[1]);
+            layout[0]
+        } else {
+            size
+        };
+        let panes_area = match &self.captions {
+            Some(captions) => {
+                let layout = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(0), Constraint::Length(CAPTION_HEIGHT)])
+                    .split(panes_area);
+                captions.render(f, layout[1], &self.theme);
+                layout[0]
+            }
+            None => panes_area,
+        };
+
+        if self.fullscreen {
+            self.file_tree_area = None;
+            self.render_editor(f, panes_area);
+        } else {
+            self.render_panes(f, panes_area);
+        }
+
+        // Render dialog if present
+        if let Some(ref title) = self.engine.dialog_title {
+            let text = &self.engine.dialog_typing_text;
+            let text_display_width = text.width();
+            let dialog_width = (text_display_width + 10).max(60).min(size.width as usize) as u16;
+            let dialog_height = 3;
+            let dialog_x = (size.width.saturating_sub(dialog_width)) / 2;
+            let dialog_y = (size.height.saturating_sub(dialog_height)) / 2;
+
+            let dialog_area = Rect {
+                x: dialog_x,
+                y: dialog_y,
+                width: dialog_width,
+                height: dialog_height,
+            };
+
+            // Calculate content width (dialog_width - borders(2) - padding(2))
+            let content_width = dialog_width.saturating_sub(4) as usize;
+            let padding_len = content_width.saturating_sub(text_display_width);
+
+            let spans = vec![
+                Span::styled(
+                    text.clone(),
+                    Style::default().fg(self.theme.file_tree_current_file_fg),
+                ),
+                Span::styled(
+                    " ".repeat(padding_len),
+                    Style::default().bg(self.theme.editor_cursor_line_bg),
+                ),
+            ];
+
+            let dialog_text = vec![Line::from(spans)];
+
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .title(title.clone())
+                .padding(Padding::horizontal(1))
+                .style(
+                    Style::default()
+                        .fg(self.theme.file_tree_current_file_fg)
+                        .bg(self.theme.editor_cursor_line_bg),
+                );
+
+            let dialog = Paragraph::new(dialog_text).block(block);
+            f.render_widget(dialog, dialog_area);
+        }
+
+        if self.show_stats
+            && matches!(
+                self.state,
+                UIState::Playing | UIState::WaitingForNext { .. }
+            )
+        {
+            self.render_stats(f, size);
+        }
+
+        // Render menu / key bindings / about overlays
+        match self.state {
+            UIState::Menu => self.render_menu(f, size),
+            UIState::Chapters => self.render_chapters(f, size),
+            UIState::KeyBindings => self.render_keybindings(f, size),
+            UIState::About => self.render_about(f, size),
+            UIState::GeneratingAudio => self.render_generating_audio(f, size),
+            _ => {}
+        }
+    }
+
+    /// File tree and commit info on the left, editor and terminal on the right.
+    fn render_panes(&mut self, f: &mut Frame, size: Rect) {
         // Split horizontally: left column | right column
         let main_layout = Layout::default()
             .direction(Direction::Horizontal)
@@ -61,6 +180,7 @@ impl<'a> UI<'a> {
 
         // Render file tree
         self.file_tree.render(f, left_layout[0], &self.theme);
+        self.file_tree_area = Some(left_layout[0]);
 
         // Render horizontal separator between file tree and commit info (left column)
         let left_sep = Paragraph::new(Line::from("─".repeat(left_layout[1].width as usize))).style(
@@ -75,12 +195,18 @@ impl<'a> UI<'a> {
             f,
             left_layout[2],
             self.engine.current_metadata(),
+            self.engine.progress(),
+            self.range_position(),
+            self.engine.is_muted(),
+            self.notice
+                .as_ref()
+                .filter(|(_, expires)| Instant::now() < *expires)
+                .map(|(message, _)| message.as_str()),
             &self.theme,
         );
 
         // Render editor
-        self.editor
-            .render(f, right_layout[0], &self.engine, &self.theme);
+        self.render_editor(f, right_layout[0]);
 
         // Render horizontal separator between editor and terminal (right column)
         let right_sep = Paragraph::new(Line::from("─".repeat(right_layout[1].width as usize)))
@@ -94,62 +220,27 @@ impl<'a> UI<'a> {
         // Render terminal
         self.terminal
             .render(f, right_layout[2], &self.engine, &self.theme);
+    }
 
-        // Render dialog if present
-        if let Some(ref title) = self.engine.dialog_title {
-            let text = &self.engine.dialog_typing_text;
-            let text_display_width = text.width();
-            let dialog_width = (text_display_width + 10).max(60).min(size.width as usize) as u16;
-            let dialog_height = 3;
-            let dialog_x = (size.width.saturating_sub(dialog_width)) / 2;
-            let dialog_y = (size.height.saturating_sub(dialog_height)) / 2;
-
-            let dialog_area = Rect {
-                x: dialog_x,
-                y: dialog_y,
-                width: dialog_width,
-                height: dialog_height,
-            };
-
-            // Calculate content width (dialog_width - borders(2) - padding(2))
-            let content_width = dialog_width.saturating_sub(4) as usize;
-            let padding_len = content_width.saturating_sub(text_display_width);
-
-            let spans = vec![
-                Span::styled(
-                    text.clone(),
-                    Style::default().fg(self.theme.file_tree_current_file_fg),
-                ),
-                Span::styled(
-                    " ".repeat(padding_len),
-                    Style::default().bg(self.theme.editor_cursor_line_bg),
-                ),
-            ];
-
-            let dialog_text = vec![Line::from(spans)];
-
-            let block = Block::default()
-                .borders(Borders::ALL)
-                .title(title.clone())
-                .padding(Padding::horizontal(1))
-                .style(
-                    Style::default()
-                        .fg(self.theme.file_tree_current_file_fg)
-                        .bg(self.theme.editor_cursor_line_bg),
-                );
-
-            let dialog = Paragraph::new(dialog_text).block(block);
-            f.render_widget(dialog, dialog_area);
-        }
-
-        // Render menu / key bindings / about overlays
-        match self.state {
-            UIState::Menu => self.render_menu(f, size),
-            UIState::KeyBindings => self.render_keybindings(f, size),
-            UIState::About => self.render_about(f, size),
-            UIState::GeneratingAudio => self.render_generating_audio(f, size),
-            _ => {}
-        }
+    /// Editor pane, with the minimap taking a few columns on its right when enabled.
+    fn render_editor(&mut self, f: &mut Frame, area: Rect) {
+        self.editor_area = Some(area);
+        let editor = if let Some(ref minimap) = self.minimap {
+            let editor_layout = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Min(0), Constraint::Length(MINIMAP_WIDTH)])
+                .split(area);
+            minimap.render(f, editor_layout[1], &self.engine, &self.theme);
+            editor_layout[0]
+        } else {
+            area
+        };
+        // The engine picks the visible lines, so give it the pane's real size
+        // (minus its vertical padding) rather than the estimate from the screen size
+        self.engine
+            .set_viewport_height(editor.height.saturating_sub(2) as usize);
+        self.engine.set_content_width(editor.width as usize);
+        self.editor.render(f, editor, &self.engine, &self.theme);
     }
 
     pub(super) fn render_menu(&self, f: &mut Frame, size: Rect) {
@@ -186,6 +277,75 @@ impl<'a> UI<'a> {
         f.render_widget(Paragraph::new(lines).block(block), area);
     }
 
+    /// Position of the playing commit in the range, for the status bar.
+    fn range_position(&self) -> Option<(usize, usize)> {
+        if !self.is_range_mode || self.compare_spec.is_some() {
+            return None;
+        }
+        let hash = &self.engine.current_metadata()?.hash;
+        self.repo?.range_position(hash)
+    }
+
+    pub(super) fn render_chapters(&self, f: &mut Frame, size: Rect) {
+        let dialog_width = 72u16.min(size.width.saturating_sub(4));
+        let dialog_height = (self.chapters.len() as u16 + 4).min(size.height.saturating_sub(4));
+        let area = Self::centered_rect(size, dialog_width, dialog_height);
+
+        // Scroll so the selection stays roughly centered
+        let visible = dialog_height.saturating_sub(4).max(1) as usize;
+        let offset = self
+            .chapter_index
+            .saturating_sub(visible / 2)
+            .min(self.chapters.len().saturating_sub(visible));
+
+        let current = self.engine.current_metadata().map(|m| m.hash.as_str());
+        let number_width = self.chapters.len().to_string().len();
+        let text_width = dialog_width.saturating_sub(6) as usize;
+        let lines: Vec<Line> = self
+            .chapters
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible)
+            .map(|(i, chapter)| {
+                let marker = if i == self.chapter_index { "> " } else { "  " };
+                let playing = if Some(chapter.hash.as_str()) == current {
+                    "▶ "
+                } else {
+                    "  "
+                };
+                let text = format!(
+                    "{marker}{playing}{:>number_width$}. {} {}",
+                    i + 1,
+                    &chapter.hash[..7.min(chapter.hash.len())],
+                    chapter.subject
+                );
+                let style = if i == self.chapter_index {
+                    Style::default().fg(self.theme.file_tree_current_file_fg)
+                } else {
+                    Style::default().fg(self.theme.status_message)
+                };
+                Line::from(Span::styled(
+                    text.chars().take(text_width).collect::<String>(),
+                    style,
+                ))
+            })
+            .collect();
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Chapters (Enter to play, Esc to close) ")
+            .padding(Padding::new(2, 2, 1, 1))
+            .style(
+                Style::default()
+                    .fg(self.theme.file_tree_current_file_fg)
+                    .bg(self.theme.editor_cursor_line_bg),
+            );
+
+        f.render_widget(Clear, area);
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
     pub(super) fn render_keybindings(&self, f: &mut Frame, size: Rect) {
         let lines = vec![
             Line::from(Span::styled(
@@ -195,6 +355,7 @@ impl<'a> UI<'a> {
             Line::from("  Esc     Menu"),
             Line::from("  q       Quit"),
             Line::from("  Ctrl+c  Quit"),
+            Line::from("  Ctrl+l  Redraw screen"),
             Line::from(""),
             Line::from(Span::styled(
                 "Playback Controls",
@@ -203,7 +364,22 @@ impl<'a> UI<'a> {
             Line::from("  Space   Play / Pause"),
             Line::from("  h / l   Step line back / forward"),
             Line::from("  H / L   Step change back / forward"),
+            Line::from("  [ / ]   Previous / next file"),
+            Line::from("  ← / →   Seek back / forward 5%"),
+            Line::from("  PgUp/Dn Pause and scroll the file"),
+            Line::from("  + / -   Narration volume up / down"),
+            Line::from("  < / >   Type slower / faster"),
+            Line::from("  r       Replay current narration"),
+            Line::from("  m       Mute / unmute narration"),
+            Line::from("  f       Toggle fullscreen editor"),
+            Line::from("  y       Copy current file diff"),
+            Line::from("  e       Expand collapsed or summarized file"),
+            Line::from("  c       Chapters (commit range)"),
+            Line::from("  s       Commit stats"),
+            Line::from("  ?       Show / hide key hints"),
             Line::from("  p / n   Previous / Next commit"),
+            Line::from("  Click   Jump to file in tree"),
+            Line::from("  Wheel   Scroll editor"),
         ];
 
         let block = Block::default()
@@ -225,7 +401,7 @@ impl<'a> UI<'a> {
 
     pub(super) fn render_about(&self, f: &mut Frame, size: Rect) {
         let version = env!("CARGO_PKG_VERSION");
-        let lines = vec![
+        let mut lines = vec![
             Line::from(Span::styled(
                 "torvax",
                 Style::default().fg(self.theme.file_tree_current_file_fg),
@@ -237,6 +413,30 @@ impl<'a> UI<'a> {
             Line::from("https://github.com/Munasco/torvax"),
         ];
 
+        if !self.settings.is_empty() {
+            let name_width = self
+                .settings
+                .iter()
+                .map(|s| s.name.len())
+                .max()
+                .unwrap_or(0);
+            let dim = Style::default().fg(self.theme.status_no_commit);
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Effective settings",
+                Style::default().fg(self.theme.file_tree_current_file_fg),
+            )));
+            for setting in &self.settings {
+                lines.push(Line::from(vec![
+                    Span::raw(format!(
+                        "{:<name_width$}  {}  ",
+                        setting.name, setting.value
+                    )),
+                    Span::styled(format!("({})", setting.source.as_str()), dim),
+                ]));
+            }
+        }
+
         let block = Block::default()
             .borders(Borders::ALL)
             .title(" About (Esc to close) ")
@@ -248,19 +448,38 @@ impl<'a> UI<'a> {
             );
 
         let dialog_height = (lines.len() as u16) + 4;
-        let area = Self::centered_rect(size, 48, dialog_height);
+        let dialog_width = if self.settings.is_empty() { 48 } else { 64 };
+        let area = Self::centered_rect(size, dialog_width, dialog_height);
 
         f.render_widget(Clear, area);
         f.render_widget(Paragraph::new(lines).block(block), area);
     }
 
+    /// Stands in for the whole layout until the terminal is resized large enough.
+    fn render_too_small(&self, f: &mut Frame, size: Rect) {
+        let message = Paragraph::new(format!(
+            "Terminal too small (need {}×{})",
+            MIN_WIDTH, MIN_HEIGHT
+        ))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .style(
+            Style::default()
+                .fg(self.theme.status_message)
+                .bg(self.theme.background_right),
+        );
+        let background = Block::default().style(Style::default().bg(self.theme.background_right));
+        f.render_widget(background, size);
+        f.render_widget(message, Self::centered_rect(size, size.width, 2));
+    }
+
     pub(super) fn render_generating_audio(&self, f: &mut Frame, size: Rect) {
-        let (status, progress) = self
+        let (status, progress, eta) = self
             .audio_progress
             .lock()
             .ok()
             .map(|p| p.clone())
-            .unwrap_or_else(|| ("Initializing...".to_string(), 0.0));
+            .unwrap_or_else(|| ("Initializing...".to_string(), 0.0, None));
 
         let area = Self::centered_rect(size, 70, 11);
         f.render_widget(Clear, area);
@@ -305,14 +524,21 @@ impl<'a> UI<'a> {
                     .bg(self.theme.background_right),
             )
             .ratio(progress as f64)
-            .label(format!("{}%", (progress * 100.0) as u8));
+            .label(match eta {
+                Some(finish) => format!(
+                    "{}%  {}",
+                    (progress * 100.0) as u8,
+                    format_eta(finish.saturating_duration_since(Instant::now()))
+                ),
+                None => format!("{}%", (progress * 100.0) as u8),
+            });
         f.render_widget(progress_bar, chunks[2]);
 
         let status_line = Paragraph::new(Line::from(status));
         f.render_widget(status_line, chunks[4]);
 
         let quit_hint = Paragraph::new(Line::from(Span::styled(
-            "q  quit",
+            "Esc  play what's ready    q  quit",
             Style::default().fg(self.theme.status_message),
         )));
         f.render_widget(quit_hint, chunks[6]);
@@ -327,3 +553,89 @@ impl<'a> UI<'a> {
         }
     }
 }
+
+/// The most useful keys in `state`, for the hint line.
+fn key_hints(state: &UIState, paused: bool) -> &'static str {
+    match state {
+        UIState::Playing | UIState::WaitingForNext { .. } if paused => {
+            " space resume · n/p commit · Esc menu · ? hide hints · q quit"
+        }
+        UIState::Playing | UIState::WaitingForNext { .. } => {
+            " space pause · n/p commit · Esc menu · ? hide hints · q quit"
+        }
+        UIState::Menu => " ↑/↓ select · Enter open · Esc back to playback",
+        UIState::Chapters => " ↑/↓ select · Enter jump to commit · Esc close",
+        UIState::KeyBindings | UIState::About => " Esc back to menu",
+        UIState::GeneratingAudio => " Esc play what's ready · q quit",
+        UIState::Finished => "",
+    }
+}
+
+/// Time left as shown in the generation dialog, e.g. "~2m 10s remaining".
+fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs().max(1);
+    if secs < 60 {
+        format!("~{}s remaining", secs)
+    } else {
+        format!("~{}m {}s remaining", secs / 60, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::Theme;
+    use crate::PlaybackOrder;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn screen_text(ui: &mut UI, width: u16, height: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        let frame = terminal.draw(|f| ui.render(f)).unwrap();
+        frame
+            .buffer
+            .content
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn eta_reads_in_minutes_and_seconds() {
+        assert_eq!(format_eta(Duration::from_secs(130)), "~2m 10s remaining");
+        assert_eq!(format_eta(Duration::from_millis(300)), "~1s remaining");
+    }
+
+    #[test]
+    fn small_terminals_get_a_resize_hint_instead_of_the_layout() {
+        let mut ui = UI::new(
+            30,
+            None,
+            Theme::default(),
+            PlaybackOrder::Asc,
+            false,
+            None,
+            false,
+            Vec::new(),
+            None,
+        );
+        for (width, height) in [(0, 0), (1, 1), (40, 10), (59, 40)] {
+            let text = screen_text(&mut ui, width, height);
+            assert!(ui.editor_area.is_none(), "{}x{}", width, height);
+            if width >= 40 {
+                assert!(text.contains("Terminal too small"), "{}x{}", width, height);
+            }
+        }
+
+        let text = screen_text(&mut ui, MIN_WIDTH, MIN_HEIGHT);
+        assert!(!text.contains("Terminal too small"));
+        assert!(ui.editor_area.is_some());
+    }
+
+    #[test]
+    fn hint_line_follows_the_state() {
+        assert!(key_hints(&UIState::Playing, false).contains("space pause"));
+        assert!(key_hints(&UIState::Playing, true).contains("space resume"));
+        assert!(key_hints(&UIState::Menu, false).contains("Enter open"));
+        assert_eq!(key_hints(&UIState::Finished, false), "");
+    }
+}
@@ -0,0 +1,231 @@
+use std::path::Path;
+
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+    Frame,
+};
+
+use super::UI;
+use crate::git::CommitMetadata;
+
+// Extensions listed in the breakdown; the rest are summed as "other"
+const MAX_EXTENSIONS: usize = 6;
+
+/// Size of a commit at a glance, for the stats overlay.
+struct CommitStats {
+    files: usize,
+    insertions: usize,
+    deletions: usize,
+    /// Path and line counts of the file with the most changed lines
+    largest: Option<(String, usize, usize)>,
+    /// Files, insertions and deletions per extension, most files first
+    extensions: Vec<(String, usize, usize, usize)>,
+}
+
+impl CommitStats {
+    fn new(metadata: &CommitMetadata) -> Self {
+        let mut stats = Self {
+            files: metadata.changes.len(),
+            insertions: 0,
+            deletions: 0,
+            largest: None,
+            extensions: Vec::new(),
+        };
+
+        for change in &metadata.changes {
+            let (added, deleted) = change.line_counts();
+            stats.insertions += added;
+            stats.deletions += deleted;
+            if stats
+                .largest
+                .as_ref()
+                .is_none_or(|(_, a, d)| added + deleted > a + d)
+            {
+                stats.largest = Some((change.path.clone(), added, deleted));
+            }
+
+            let extension = Path::new(&change.path).extension().map_or_else(
+                || "(none)".to_string(),
+                |e| format!(".{}", e.to_string_lossy()),
+            );
+            match stats.extensions.iter_mut().find(|(e, ..)| *e == extension) {
+                Some((_, files, a, d)) => {
+                    *files += 1;
+                    *a += added;
+                    *d += deleted;
+                }
+                None => stats.extensions.push((extension, 1, added, deleted)),
+            }
+        }
+
+        // Stable sort keeps first-seen order among equally common extensions
+        stats
+            .extensions
+            .sort_by(|a, b| b.1.cmp(&a.1).then((b.2 + b.3).cmp(&(a.2 + a.3))));
+        if stats.extensions.len() > MAX_EXTENSIONS {
+            let rest = stats.extensions.split_off(MAX_EXTENSIONS - 1);
+            stats.extensions.push(rest.into_iter().fold(
+                ("other".to_string(), 0, 0, 0),
+                |(name, f, a, d), (_, files, added, deleted)| {
+                    (name, f + files, a + added, d + deleted)
+                },
+            ));
+        }
+        stats
+    }
+}
+
+fn plural(count: usize, word: &str) -> String {
+    format!("{} {}{}", count, word, if count == 1 { "" } else { "s" })
+}
+
+impl<'a> UI<'a> {
+    pub(super) fn toggle_stats(&mut self) {
+        self.show_stats = !self.show_stats;
+    }
+
+    /// Overlay summarizing the commit being played; playback continues underneath.
+    pub(super) fn render_stats(&self, f: &mut Frame, size: Rect) {
+        let Some(metadata) = self.engine.current_metadata() else {
+            return;
+        };
+        let stats = CommitStats::new(metadata);
+        let heading = Style::default().fg(self.theme.file_tree_current_file_fg);
+        let added = Style::default().fg(self.theme.status_date);
+        let deleted = Style::default().fg(self.theme.status_author);
+        let dim = Style::default().fg(self.theme.status_no_commit);
+
+        let hash = &metadata.hash[..7.min(metadata.hash.len())];
+        let mut lines = vec![
+            Line::from(Span::styled(format!("Commit {}", hash), heading)),
+            Line::from(""),
+            Line::from(format!("Files changed  {}", stats.files)),
+            Line::from(vec![
+                Span::raw("Insertions     "),
+                Span::styled(format!("+{}", stats.insertions), added),
+            ]),
+            Line::from(vec![
+                Span::raw("Deletions      "),
+                Span::styled(format!("-{}", stats.deletions), deleted),
+            ]),
+        ];
+        if let Some((path, a, d)) = &stats.largest {
+            lines.push(Line::from(vec![
+                Span::raw(format!("Largest file   {} ", path)),
+                Span::styled(format!("+{}", a), added),
+                Span::raw(" "),
+                Span::styled(format!("-{}", d), deleted),
+            ]));
+        }
+
+        if !stats.extensions.is_empty() {
+            let name_width = stats
+                .extensions
+                .iter()
+                .map(|(e, ..)| e.len())
+                .max()
+                .unwrap_or(0);
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled("By extension", heading)));
+            for (extension, files, a, d) in &stats.extensions {
+                lines.push(Line::from(vec![
+                    Span::raw(format!("{:<name_width$}  ", extension)),
+                    Span::styled(format!("{:<8}", plural(*files, "file")), dim),
+                    Span::styled(format!("+{}", a), added),
+                    Span::raw(" "),
+                    Span::styled(format!("-{}", d), deleted),
+                ]));
+            }
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(" Commit Stats (s to close) ")
+            .padding(Padding::new(2, 2, 1, 1))
+            .style(
+                Style::default()
+                    .fg(self.theme.status_message)
+                    .bg(self.theme.editor_cursor_line_bg),
+            );
+
+        let content_width = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+        let dialog_width = (content_width + 6).clamp(36, size.width);
+        let dialog_height = (lines.len() as u16) + 4;
+        let area = Self::centered_rect(size, dialog_width, dialog_height);
+
+        f.render_widget(Clear, area);
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{DiffHunk, FileChange, FileStatus, LineChange, LineChangeType};
+    use chrono::Utc;
+
+    fn change(path: &str, added: usize, deleted: usize) -> FileChange {
+        let line = |change_type| LineChange {
+            change_type,
+            content: String::new(),
+            old_line_no: None,
+            new_line_no: None,
+            inline_changes: Vec::new(),
+        };
+        let lines = std::iter::repeat_with(|| line(LineChangeType::Addition))
+            .take(added)
+            .chain(std::iter::repeat_with(|| line(LineChangeType::Deletion)).take(deleted))
+            .chain(std::iter::once(line(LineChangeType::Context)))
+            .collect();
+        FileChange {
+            path: path.to_string(),
+            old_path: None,
+            status: FileStatus::Modified,
+            is_binary: false,
+            is_excluded: false,
+            exclusion_reason: None,
+            old_content: None,
+            new_content: None,
+            hunks: vec![DiffHunk {
+                old_start: 1,
+                old_lines: deleted + 1,
+                new_start: 1,
+                new_lines: added + 1,
+                lines,
+            }],
+            diff: String::new(),
+            language: None,
+        }
+    }
+
+    #[test]
+    fn stats_total_lines_and_group_by_extension() {
+        let metadata = CommitMetadata {
+            hash: "abc1234def".to_string(),
+            author: "a".to_string(),
+            date: Utc::now(),
+            message: "m".to_string(),
+            changes: vec![
+                change("src/a.rs", 3, 1),
+                change("README.md", 1, 1),
+                change("src/b.rs", 10, 2),
+                change("Makefile", 0, 4),
+            ],
+        };
+        let stats = CommitStats::new(&metadata);
+        assert_eq!(stats.files, 4);
+        assert_eq!((stats.insertions, stats.deletions), (14, 8));
+        assert_eq!(stats.largest, Some(("src/b.rs".to_string(), 10, 2)));
+        assert_eq!(
+            stats.extensions,
+            vec![
+                (".rs".to_string(), 2, 13, 3),
+                ("(none)".to_string(), 1, 0, 4),
+                (".md".to_string(), 1, 1, 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use anyhow::{Context, Result};
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Component, Path};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use crate::git::{self, CommitMetadata, DiffMode, FileStatus, GitRepository};
+
+/// How long the working tree has to stay quiet before a burst of saves is replayed
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watches the working tree for `diff --watch` and hands out what changed since
+/// the last render, so each save plays only its own edits.
+pub struct WorkingTreeWatch {
+    // Events stop arriving once the watcher is dropped
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    last_event: Option<Instant>,
+    /// Content each played file was left in, keyed by path
+    rendered: HashMap<String, String>,
+}
+
+impl WorkingTreeWatch {
+    pub fn new(root: &Path) -> Result<Self> {
+        let (tx, events) = mpsc::channel();
+        let mut watcher =
+            notify::recommended_watcher(tx).context("Failed to start file watcher")?;
+        watcher
+            .watch(root, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {}", root.display()))?;
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            last_event: None,
+            rendered: HashMap::new(),
+        })
+    }
+
+    /// Remember the content `metadata` leaves each file in, so the next save
+    /// plays only what changed after it.
+    pub fn mark_rendered(&mut self, metadata: &CommitMetadata) {
+        for change in &metadata.changes {
+            match (&change.new_content, &change.status) {
+                (Some(content), _) => {
+                    self.rendered.insert(change.path.clone(), content.clone());
+                }
+                (None, FileStatus::Deleted) => {
+                    self.rendered.insert(change.path.clone(), String::new());
+                }
+                (None, _) => {
+                    self.rendered.remove(&change.path);
+                }
+            }
+        }
+    }
+
+    /// Unstaged changes made since the last render, once saves have settled.
+    /// `None` while there is nothing new to play.
+    pub fn next_delta(&mut self, repo: &GitRepository) -> Option<CommitMetadata> {
+        if !self.settled(repo) {
+            return None;
+        }
+        let current = repo.get_working_tree_diff(DiffMode::Unstaged).ok()?;
+        let delta = self.delta(current);
+        if delta.changes.is_empty() {
+            return None;
+        }
+        self.mark_rendered(&delta);
+        Some(delta)
+    }
+
+    /// Whether files changed and then stayed quiet for `DEBOUNCE`. Ignored files
+    /// such as build output don't count, so a running build can't hold playback back.
+    fn settled(&mut self, repo: &GitRepository) -> bool {
+        while let Ok(event) = self.events.try_recv() {
+            if event.is_ok_and(|e| is_edit(&e, |path| repo.is_ignored(path))) {
+                self.last_event = Some(Instant::now());
+            }
+        }
+        match self.last_event {
+            Some(at) if at.elapsed() >= DEBOUNCE => {
+                self.last_event = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Narrow the working tree diff to what changed since each file was last
+    /// played. Files not played yet keep their whole diff against the index.
+    fn delta(&self, mut current: CommitMetadata) -> CommitMetadata {
+        let changes = std::mem::take(&mut current.changes);
+        for change in changes {
+            let Some(rendered) = self.rendered.get(&change.path) else {
+                current.changes.push(change);
+                continue;
+            };
+            let new = match (&change.new_content, &change.status) {
+                (Some(content), _) => content.as_str(),
+                (None, FileStatus::Deleted) => "",
+                // Binary or too large to diff again; it was shown once already
+                (None, _) => continue,
+            };
+            if let Ok(Some(mut delta)) = git::content_delta(&change.path, rendered, new) {
+                delta.status = match change.status {
+                    FileStatus::Deleted => FileStatus::Deleted,
+                    _ => FileStatus::Modified,
+                };
+                current.changes.push(delta);
+            }
+        }
+        current
+    }
+}
+
+/// A file written, created or removed outside `.git` and not `ignored`. Reads and
+/// metadata changes don't count, or diffing the working tree would set the watch off again.
+fn is_edit(event: &Event, ignored: impl Fn(&Path) -> bool) -> bool {
+    let kind = match event.kind {
+        EventKind::Create(_) | EventKind::Remove(_) => true,
+        EventKind::Modify(ModifyKind::Metadata(_)) => false,
+        EventKind::Modify(_) => true,
+        _ => false,
+    };
+    kind && event.paths.iter().any(|p| {
+        !p.components()
+            .any(|c| c == Component::Normal(".git".as_ref()))
+            && !ignored(p)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn working_tree(files: &[(&str, &str, &str)]) -> CommitMetadata {
+        let changes = files
+            .iter()
+            .map(|(path, old, new)| git::content_delta(path, old, new).unwrap().unwrap())
+            .collect();
+        CommitMetadata {
+            hash: "working-tree".to_string(),
+            author: String::new(),
+            date: Utc::now(),
+            message: "Unstaged changes".to_string(),
+            changes,
+        }
+    }
+
+    #[test]
+    fn delta_plays_only_edits_since_the_last_render() {
+        let dir = std::env::temp_dir().join(format!("torvax_watch_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut watch = WorkingTreeWatch::new(&dir).unwrap();
+
+        let first = working_tree(&[("a.rs", "one\n", "one\ntwo\n")]);
+        watch.mark_rendered(&first);
+
+        let next = watch.delta(working_tree(&[
+            ("a.rs", "one\n", "one\ntwo\nthree\n"),
+            ("b.rs", "", "new\n"),
+        ]));
+        assert_eq!(next.changes.len(), 2);
+        let a = &next.changes[0];
+        assert_eq!(a.old_content.as_deref(), Some("one\ntwo\n"));
+        assert_eq!(a.line_counts(), (1, 0));
+        // A file not played yet keeps its whole diff
+        assert_eq!(next.changes[1].path, "b.rs");
+
+        // Saving without changing anything plays nothing
+        watch.mark_rendered(&next);
+        let unchanged = watch.delta(working_tree(&[("a.rs", "one\n", "one\ntwo\nthree\n")]));
+        assert!(unchanged.changes.is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn edits_to_ignored_or_git_files_are_not_edits() {
+        let modify =
+            |path: &str| Event::new(EventKind::Modify(ModifyKind::Any)).add_path(path.into());
+        let ignored = |path: &Path| path.starts_with("/repo/target");
+
+        assert!(is_edit(&modify("/repo/src/main.rs"), ignored));
+        assert!(!is_edit(&modify("/repo/target/debug/torvax"), ignored));
+        assert!(!is_edit(&modify("/repo/.git/index"), ignored));
+    }
+}
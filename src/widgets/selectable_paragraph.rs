@@ -7,6 +7,9 @@ use ratatui::{
 };
 use unicode_width::UnicodeWidthStr;
 
+/// A wrapped display line: original line index, content, first wrap of it, and whether it wrapped
+type WrappedLine<'a> = (usize, Line<'a>, bool, bool);
+
 /// A paragraph widget that wraps at character boundaries and supports line selection
 pub struct SelectableParagraph<'a> {
     lines: Vec<Line<'a>>,
@@ -17,6 +20,9 @@ pub struct SelectableParagraph<'a> {
     padding: Padding,
     dim_max_distance: Option<usize>,
     dim_min_opacity: f32,
+    wrap: bool,
+    continuation_indent: u16,
+    scroll_to_selected: bool,
 }
 
 impl<'a> SelectableParagraph<'a> {
@@ -30,6 +36,9 @@ impl<'a> SelectableParagraph<'a> {
             padding: Padding::ZERO,
             dim_max_distance: None,
             dim_min_opacity: 0.6,
+            wrap: true,
+            continuation_indent: 0,
+            scroll_to_selected: true,
         }
     }
 
@@ -64,6 +73,124 @@ impl<'a> SelectableParagraph<'a> {
         self
     }
 
+    /// Wrap long lines (the default); when disabled they are cut off at the right padding.
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Columns to indent wrapped continuation rows by, e.g. to line them up past a gutter.
+    pub fn continuation_indent(mut self, indent: u16) -> Self {
+        self.continuation_indent = indent;
+        self
+    }
+
+    /// Scroll to keep the selected line centered (the default); when disabled the
+    /// lines are drawn from the top and whatever doesn't fit is cut off.
+    pub fn scroll_to_selected(mut self, enabled: bool) -> Self {
+        self.scroll_to_selected = enabled;
+        self
+    }
+
+    /// Index of the line drawn at screen row `y` when rendered into `area`, if any.
+    pub fn line_at(&self, area: Rect, y: u16) -> Option<usize> {
+        let (inner_area, wrapped_lines, scroll_offset) = self.layout(area)?;
+        let row = y.checked_sub(inner_area.y)?;
+        if row >= inner_area.height {
+            return None;
+        }
+        wrapped_lines
+            .get(scroll_offset + row as usize)
+            .map(|(original_idx, _, _, _)| *original_idx)
+    }
+
+    /// Wraps every line to fit the area and picks the scroll offset that keeps the
+    /// selected line centered. Returns the content area, the wrapped lines tagged with
+    /// their original index, and the offset; None when there is no room to draw.
+    fn layout(&self, area: Rect) -> Option<(Rect, Vec<WrappedLine<'a>>, usize)> {
+        let area = match self.block {
+            Some(ref block) => block.inner(area),
+            None => area,
+        };
+
+        if area.width == 0 || area.height == 0 {
+            return None;
+        }
+
+        // Apply padding
+        let inner_area = Rect {
+            x: area.x,
+            y: area.y.saturating_add(self.padding.top),
+            width: area.width,
+            height: area
+                .height
+                .saturating_sub(self.padding.top + self.padding.bottom),
+        };
+
+        if inner_area.width == 0 || inner_area.height == 0 {
+            return None;
+        }
+
+        // For wrapping: first line uses full width minus left padding (no right padding when wrapping)
+        let first_line_width = inner_area.width.saturating_sub(self.padding.left) as usize;
+        let continuation_width = inner_area.width.saturating_sub(self.continuation_indent) as usize;
+        let no_wrap_content_width = inner_area
+            .width
+            .saturating_sub(self.padding.left + self.padding.right)
+            as usize;
+        let height = inner_area.height as usize;
+
+        // Wrap all lines and track which wrapped line corresponds to which original line
+        // Also track if this is the first wrapped line and if wrapping occurred
+        let mut wrapped_lines_with_indices = Vec::new();
+        for (original_idx, line) in self.lines.iter().enumerate() {
+            if !self.wrap {
+                // Keep only what fits on the first row
+                let mut rows = Self::wrap_line(line, no_wrap_content_width, no_wrap_content_width);
+                wrapped_lines_with_indices.push((original_idx, rows.swap_remove(0), true, false));
+                continue;
+            }
+            let wrapped = Self::wrap_line(line, first_line_width, continuation_width);
+            let has_wrap = wrapped.len() > 1;
+            for (wrap_idx, wrapped_line) in wrapped.into_iter().enumerate() {
+                let is_first_wrap = wrap_idx == 0;
+                wrapped_lines_with_indices.push((
+                    original_idx,
+                    wrapped_line,
+                    is_first_wrap,
+                    has_wrap,
+                ));
+            }
+        }
+
+        // Calculate scroll offset to keep selected line centered
+        let scroll_offset =
+            if let Some(selected_idx) = self.selected_line.filter(|_| self.scroll_to_selected) {
+                // Find the first display line of the selected original line
+                let selected_display_line = wrapped_lines_with_indices
+                    .iter()
+                    .position(|(orig_idx, _, _, _)| *orig_idx == selected_idx)
+                    .unwrap_or(0);
+
+                let total_lines = wrapped_lines_with_indices.len();
+
+                if total_lines <= height {
+                    // All lines fit, no scrolling needed
+                    0
+                } else {
+                    // Keep selected line in the middle of viewport
+                    let preferred_position = height / 2;
+                    let offset = selected_display_line.saturating_sub(preferred_position);
+                    let max_offset = total_lines.saturating_sub(height);
+                    offset.min(max_offset)
+                }
+            } else {
+                0
+            };
+
+        Some((inner_area, wrapped_lines_with_indices, scroll_offset))
+    }
+
     fn apply_opacity(&self, foreground: Color, opacity: f32, background: Color) -> Color {
         match (foreground, background) {
             (Color::Rgb(fr, fg, fb), Color::Rgb(br, bg, bb)) => {
@@ -180,35 +307,14 @@ impl<'a> SelectableParagraph<'a> {
 
 impl Widget for SelectableParagraph<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let area = match self.block {
-            Some(ref block) => {
-                let inner = block.inner(area);
-                block.clone().render(area, buf);
-                inner
-            }
-            None => area,
-        };
-
-        if area.width == 0 || area.height == 0 {
-            return;
+        if let Some(ref block) = self.block {
+            block.clone().render(area, buf);
         }
-
-        // Apply padding
-        let inner_area = Rect {
-            x: area.x,
-            y: area.y.saturating_add(self.padding.top),
-            width: area.width,
-            height: area
-                .height
-                .saturating_sub(self.padding.top + self.padding.bottom),
-        };
-
-        if inner_area.width == 0 || inner_area.height == 0 {
+        let Some((inner_area, wrapped_lines_with_indices, scroll_offset)) = self.layout(area)
+        else {
             return;
-        }
+        };
 
-        // For wrapping: first line uses full width minus left padding (no right padding when wrapping)
-        let first_line_width = inner_area.width.saturating_sub(self.padding.left) as usize;
         let continuation_width = inner_area.width as usize;
         let no_wrap_content_width = inner_area
             .width
@@ -216,47 +322,6 @@ impl Widget for SelectableParagraph<'_> {
             as usize;
         let height = inner_area.height as usize;
 
-        // Wrap all lines and track which wrapped line corresponds to which original line
-        // Also track if this is the first wrapped line and if wrapping occurred
-        let mut wrapped_lines_with_indices = Vec::new();
-        for (original_idx, line) in self.lines.iter().enumerate() {
-            let wrapped = Self::wrap_line(line, first_line_width, continuation_width);
-            let has_wrap = wrapped.len() > 1;
-            for (wrap_idx, wrapped_line) in wrapped.into_iter().enumerate() {
-                let is_first_wrap = wrap_idx == 0;
-                wrapped_lines_with_indices.push((
-                    original_idx,
-                    wrapped_line,
-                    is_first_wrap,
-                    has_wrap,
-                ));
-            }
-        }
-
-        // Calculate scroll offset to keep selected line centered
-        let scroll_offset = if let Some(selected_idx) = self.selected_line {
-            // Find the first display line of the selected original line
-            let selected_display_line = wrapped_lines_with_indices
-                .iter()
-                .position(|(orig_idx, _, _, _)| *orig_idx == selected_idx)
-                .unwrap_or(0);
-
-            let total_lines = wrapped_lines_with_indices.len();
-
-            if total_lines <= height {
-                // All lines fit, no scrolling needed
-                0
-            } else {
-                // Keep selected line in the middle of viewport
-                let preferred_position = height / 2;
-                let offset = selected_display_line.saturating_sub(preferred_position);
-                let max_offset = total_lines.saturating_sub(height);
-                offset.min(max_offset)
-            }
-        } else {
-            0
-        };
-
         let visible_lines: Vec<_> = wrapped_lines_with_indices
             .into_iter()
             .skip(scroll_offset)
@@ -401,8 +466,14 @@ impl Widget for SelectableParagraph<'_> {
                     }
                 }
             } else {
-                // Wrapped continuation line: use full width (no padding)
-                let mut x_pos = 0;
+                // Wrapped continuation line: use full width past the indent (no padding)
+                let indent = self.continuation_indent.min(inner_area.width);
+                for x in 0..indent {
+                    if let Some(cell) = buf.cell_mut((inner_area.x + x, render_y)) {
+                        cell.set_style(fill_style);
+                    }
+                }
+                let mut x_pos = indent as usize;
 
                 for span in &line.spans {
                     let mut style = span.style;
@@ -442,3 +513,37 @@ impl Widget for SelectableParagraph<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(buf: &Buffer, y: u16) -> String {
+        (0..buf.area.width)
+            .map(|x| buf.cell((x, y)).map_or(" ", |c| c.symbol()))
+            .collect()
+    }
+
+    #[test]
+    fn long_lines_wrap_past_the_indent_or_are_cut_off() {
+        let area = Rect::new(0, 0, 8, 3);
+        let lines = || vec![Line::from("abcdefghijkl"), Line::from("xy")];
+
+        let mut buf = Buffer::empty(area);
+        SelectableParagraph::new(lines())
+            .padding(Padding::horizontal(1))
+            .continuation_indent(3)
+            .render(area, &mut buf);
+        assert_eq!(row(&buf, 0), " abcdefg");
+        assert_eq!(row(&buf, 1), "   hijkl");
+        assert_eq!(row(&buf, 2), " xy     ");
+
+        let mut buf = Buffer::empty(area);
+        SelectableParagraph::new(lines())
+            .padding(Padding::horizontal(1))
+            .wrap(false)
+            .render(area, &mut buf);
+        assert_eq!(row(&buf, 0), " abcdef ");
+        assert_eq!(row(&buf, 1), " xy     ");
+    }
+}